@@ -0,0 +1,26 @@
+use std::process::Command;
+
+// Embeds the commit the BIOS was built from as KAZETA_BUILD_COMMIT, so About and the
+// logs can say exactly which build is running. Packagers building from a tarball can
+// set KAZETA_BUILD_COMMIT themselves; otherwise it's read from git, or "unknown".
+fn main() {
+    println!("cargo:rerun-if-env-changed=KAZETA_BUILD_COMMIT");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/index");
+
+    let commit = std::env::var("KAZETA_BUILD_COMMIT").ok()
+        .filter(|c| !c.trim().is_empty())
+        .or_else(|| {
+            let output = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output().ok()?;
+            let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !output.status.success() || hash.is_empty() {
+                return None;
+            }
+            let dirty = Command::new("git").args(["status", "--porcelain", "--untracked-files=no"]).output()
+                .is_ok_and(|o| !o.stdout.is_empty());
+            Some(if dirty { format!("{}-dirty", hash) } else { hash })
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=KAZETA_BUILD_COMMIT={}", commit.trim());
+}
@@ -0,0 +1,176 @@
+use std::{fs, path::Path, process::Command, sync::mpsc, thread};
+
+use crate::{config::get_user_data_dir, save, utils::{has_asset_extension, FONT_EXTENSIONS, IMAGE_EXTENSIONS}};
+
+/// Loose asset folders and the file types the asset scan picks up from each
+const ASSET_FOLDERS: &[(&str, &[&str])] = &[
+    ("backgrounds", &["png", "jpg", "jpeg", "webp", "mp4"]),
+    ("logos", IMAGE_EXTENSIONS),
+    ("fonts", FONT_EXTENSIONS),
+    ("bgm", &["ogg", "wav"]),
+];
+
+/// Audio formats the BGM player can't decode, converted to OGG with ffmpeg when imported into bgm
+const CONVERTIBLE_AUDIO: &[&str] = &["mp3", "flac", "m4a", "aac", "opus", "wma"];
+
+/// What an import did: files copied or converted, plus each file left out and why
+#[derive(Default)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: Vec<String>,
+}
+
+/// What the import thread started by spawn_import_from_usb() reports
+pub enum ImportProgress {
+    /// Files imported so far
+    Imported(usize),
+    Done(Result<ImportSummary, String>),
+}
+
+/// Folders holding one subfolder per sound pack / theme
+const PACK_FOLDERS: &[(&str, &[&str])] = &[
+    ("sfx", &["wav"]),
+    ("themes", &["toml", "png", "jpg", "jpeg", "webp", "mp4", "ttf", "otf", "ogg", "wav"]),
+];
+
+/// Re-encodes an audio file the player can't decode as `<name>.ogg` in `to`.
+/// An existing `<name>.ogg` is left alone and the file is skipped.
+fn convert_to_ogg(path: &Path, to: &Path) -> Result<(), String> {
+    let target = to.join(path.with_extension("ogg").file_name().ok_or("no file name")?);
+    if target.exists() {
+        return Err(format!("{} already exists", target.file_name().unwrap_or_default().to_string_lossy()));
+    }
+    let output = Command::new("ffmpeg")
+        .args(["-nostdin", "-loglevel", "error", "-n", "-i"])
+        .arg(path)
+        .args(["-vn", "-acodec", "libvorbis"]) // -vn drops embedded cover art
+        .arg(&target)
+        .output()
+        .map_err(|_| "ffmpeg is not installed".to_string())?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&target);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().last().unwrap_or("ffmpeg failed").to_string());
+    }
+    log_info!("Imported {} (converted from {})", target.display(), path.display());
+    Ok(())
+}
+
+/// Copies the files in `from` with an accepted extension into `to`, recursing into
+/// subfolders when `recursive` is set (a theme's sound pack lives in one). With `convert_audio`,
+/// audio in a format the player can't decode is converted to OGG rather than left behind.
+/// `progress` hears the running count after each imported file.
+fn copy_assets(from: &Path, to: &Path, extensions: &[&str], recursive: bool, convert_audio: bool, summary: &mut ImportSummary, progress: &dyn Fn(usize)) -> Result<(), String> {
+    let Ok(entries) = fs::read_dir(from) else { return Ok(()) };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            if recursive {
+                copy_assets(&path, &target, extensions, recursive, convert_audio, summary, progress)?;
+            }
+        } else if has_asset_extension(&path, extensions) {
+            fs::create_dir_all(to).map_err(|e| e.to_string())?;
+            fs::copy(&path, &target).map_err(|e| format!("{}: {}", path.display(), e))?;
+            log_info!("Imported {}", target.display());
+            summary.imported += 1;
+            progress(summary.imported);
+        } else if has_asset_extension(&path, CONVERTIBLE_AUDIO) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let result = if convert_audio {
+                fs::create_dir_all(to).map_err(|e| e.to_string())?;
+                convert_to_ogg(&path, to)
+            } else {
+                let wanted = if extensions.contains(&"ogg") { "OGG or WAV" } else { "WAV" };
+                Err(format!("unsupported audio format, use {}", wanted))
+            };
+            match result {
+                Ok(()) => {
+                    summary.imported += 1;
+                    progress(summary.imported);
+                }
+                Err(reason) => {
+                    log_warn!("Skipping {}: {}", path.display(), reason);
+                    summary.skipped.push(format!("{} ({})", name, reason));
+                }
+            }
+        } else {
+            log_debug!("Skipping {} (not a supported asset type)", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Runs import_from_usb() on its own thread, since converting audio can take a while.
+/// The receiver gets a running count, then the result.
+pub fn spawn_import_from_usb() -> mpsc::Receiver<ImportProgress> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = import_from_usb(&|count| {
+            let _ = tx.send(ImportProgress::Imported(count));
+        });
+        let _ = tx.send(ImportProgress::Done(result));
+    });
+    rx
+}
+
+/// Looks for backgrounds/logos/fonts/bgm/sfx/themes folders at the root of every
+/// USB drive and copies what they hold into the data dir.
+fn import_from_usb(progress: &dyn Fn(usize)) -> Result<ImportSummary, String> {
+    let data_dir = get_user_data_dir().ok_or("COULD NOT FIND DATA DIRECTORY")?;
+    let drives: Vec<_> = save::list_devices().unwrap_or_default()
+        .into_iter()
+        .filter(|(name, _, _)| name != "internal")
+        .map(|(name, _, _)| save::get_drive_root(&name))
+        .collect();
+    if drives.is_empty() {
+        return Err("NO USB DRIVE FOUND".to_string());
+    }
+
+    let mut found_any = false;
+    let mut summary = ImportSummary::default();
+    for drive in &drives {
+        for (folder, extensions) in ASSET_FOLDERS {
+            let source = drive.join(folder);
+            if source.is_dir() {
+                found_any = true;
+                copy_assets(&source, &data_dir.join(folder), extensions, false, *folder == "bgm", &mut summary, progress)?;
+            }
+        }
+        for (folder, extensions) in PACK_FOLDERS {
+            let source = drive.join(folder);
+            let Ok(packs) = fs::read_dir(&source) else { continue };
+            found_any = true;
+            for pack in packs.flatten().filter(|p| p.path().is_dir()) {
+                let target = data_dir.join(folder).join(pack.file_name());
+                copy_assets(&pack.path(), &target, extensions, true, false, &mut summary, progress)?;
+            }
+        }
+    }
+
+    if !found_any {
+        return Err("NO ASSET FOLDERS FOUND ON USB".to_string());
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_never_overwrites_an_existing_track() {
+        let usb = tempfile::tempdir().unwrap();
+        let bgm = tempfile::tempdir().unwrap();
+        fs::write(usb.path().join("song.mp3"), "new").unwrap();
+        fs::write(bgm.path().join("song.ogg"), "old").unwrap();
+
+        let mut summary = ImportSummary::default();
+        copy_assets(usb.path(), bgm.path(), &["ogg", "wav"], false, true, &mut summary, &|_| {}).unwrap();
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.skipped, ["song.mp3 (song.ogg already exists)"]);
+        assert_eq!(fs::read_to_string(bgm.path().join("song.ogg")).unwrap(), "old");
+    }
+}
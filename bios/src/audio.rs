@@ -8,6 +8,7 @@ use std::io::{BufReader, Cursor};
 use std::path::{Path, PathBuf};
 use std::collections::{HashSet, HashMap};
 use crate::config::{Config, get_user_data_dir};
+use crate::rumble;
 
 // --- Rodio Global Audio System ---
 pub struct AudioSystem {
@@ -18,22 +19,59 @@ pub struct AudioSystem {
 
 // [!] Note: If you get an error that AudioSystem cannot be shared between threads
 // (Sync trait), we may need to wrap this in a Mutex. For now, we keep it simple.
-pub static AUDIO: Lazy<AudioSystem> = Lazy::new(|| {
-    let stream = OutputStreamBuilder::open_default_stream()
-    .expect("Failed to load audio stream");
-    AudioSystem { stream }
+// None when there is no output device at all (headless boxes, missing sinks);
+// everything that plays sound goes through new_sink() and quietly does nothing.
+pub static AUDIO: Lazy<Option<AudioSystem>> = Lazy::new(|| {
+    match OutputStreamBuilder::open_default_stream() {
+        Ok(stream) => Some(AudioSystem { stream }),
+        Err(e) => {
+            log_error!("Failed to open audio stream, continuing without sound: {}", e);
+            None
+        }
+    }
 });
 
+/// Whether an audio output stream could be opened
+pub fn audio_available() -> bool {
+    AUDIO.is_some()
+}
+
+/// Creates a sink on the global mixer, or None if audio is unavailable
+pub fn new_sink() -> Option<Sink> {
+    AUDIO.as_ref().map(|audio| Sink::connect_new(audio.stream.mixer()))
+}
+
+/// An empty buffer, used in place of sounds that can't be decoded or played
+fn silence() -> SamplesBuffer {
+    SamplesBuffer::new(1, 44100, Vec::<f32>::new())
+}
+
 // --- Helper functions for loading audio into rodio buffers ---
 
-pub fn load_sound_from_bytes(bytes: &[u8]) -> SamplesBuffer {
+pub fn load_sound_from_bytes(bytes: &[u8]) -> Result<SamplesBuffer, rodio::decoder::DecoderError> {
     let owned = bytes.to_vec().into_boxed_slice();
     let cursor = Cursor::new(owned);               // Cursor<Box<[u8]>> is 'static
-    let decoder = rodio::Decoder::new(cursor).unwrap();
+    let decoder = rodio::Decoder::new(cursor)?;
     let channels = decoder.channels();
     let sample_rate = decoder.sample_rate();
     let samples: Vec<f32> = decoder.collect();
-    SamplesBuffer::new(channels, sample_rate, samples)
+    Ok(SamplesBuffer::new(channels, sample_rate, samples))
+}
+
+/// Below this a file can't hold an audio header plus any samples, so it isn't worth decoding
+const MIN_AUDIO_FILE_BYTES: usize = 64;
+
+/// load_sound_from_bytes() for BGM tracks, which also turns away empty and truncated files
+/// so they never show up as a track that can be picked but won't play.
+pub fn load_track_from_bytes(bytes: &[u8]) -> Result<SamplesBuffer, String> {
+    if bytes.len() < MIN_AUDIO_FILE_BYTES {
+        return Err(format!("file is too small to be audio ({} bytes)", bytes.len()));
+    }
+    let track = load_sound_from_bytes(bytes).map_err(|e| format!("{:?}", e))?;
+    if track.total_duration().is_none_or(|d| d.is_zero()) {
+        return Err("no audio could be decoded (truncated file?)".to_string());
+    }
+    Ok(track)
 }
 
 pub fn load_from_file(path: &Path) -> Result<SamplesBuffer, Box<dyn std::error::Error>> {
@@ -46,6 +84,47 @@ pub fn load_from_file(path: &Path) -> Result<SamplesBuffer, Box<dyn std::error::
     Ok(SamplesBuffer::new(channels, sample_rate, samples))
 }
 
+// --- Speaker Test ---
+
+const TEST_SAMPLE_RATE: u32 = 44100;
+const TEST_TONE_HZ: f32 = 440.0;
+const TEST_TONE_SECS: f32 = 0.5;
+const TEST_GAP_SECS: f32 = 0.25;
+const TEST_TONE_LEVEL: f32 = 0.4;
+
+/// A stereo clip that beeps on the left channel, then the right, then both, so a
+/// swapped or dead channel on the current output is easy to hear
+fn speaker_test_clip() -> SamplesBuffer {
+    let tone_frames = (TEST_SAMPLE_RATE as f32 * TEST_TONE_SECS) as usize;
+    let gap_frames = (TEST_SAMPLE_RATE as f32 * TEST_GAP_SECS) as usize;
+    let mut samples = Vec::with_capacity((tone_frames + gap_frames) * 3 * 2);
+
+    for (left, right) in [(true, false), (false, true), (true, true)] {
+        for i in 0..tone_frames {
+            let t = i as f32 / TEST_SAMPLE_RATE as f32;
+            // short fade in/out so the tone doesn't click
+            let edge = (i.min(tone_frames - 1 - i) as f32 / 400.0).min(1.0);
+            let sample = (t * TEST_TONE_HZ * std::f32::consts::TAU).sin() * TEST_TONE_LEVEL * edge;
+            samples.push(if left { sample } else { 0.0 });
+            samples.push(if right { sample } else { 0.0 });
+        }
+        samples.extend(std::iter::repeat_n(0.0, gap_frames * 2));
+    }
+    SamplesBuffer::new(2, TEST_SAMPLE_RATE, samples)
+}
+
+/// Plays the left/right/both test clip on the current output. Not scaled by the
+/// SFX volume, so the test is still audible with menu sounds turned down.
+pub fn play_speaker_test() {
+    match new_sink() {
+        Some(sink) => {
+            sink.append(speaker_test_clip());
+            sink.detach();
+        }
+        None => log_warn!("Speaker test skipped: no audio output available."),
+    }
+}
+
 // --- SoundEffects Struct and Impl ---
 
 #[derive(Clone)]
@@ -54,17 +133,44 @@ pub struct SoundEffects {
     pub select: SamplesBuffer,
     pub reject: SamplesBuffer,
     pub back: SamplesBuffer,
+    // Optional events; packs without them get the nearest sound above (or nothing for boot)
+    pub dialog_open: SamplesBuffer,
+    pub error: SamplesBuffer,
+    pub launch: SamplesBuffer,
+    pub boot: SamplesBuffer,
 }
 
 impl SoundEffects {
+    /// No-op effects for when there is no audio device to play them on
+    pub fn silent() -> Self {
+        SoundEffects {
+            cursor_move: silence(),
+            select: silence(),
+            reject: silence(),
+            back: silence(),
+            dialog_open: silence(),
+            error: silence(),
+            launch: silence(),
+            boot: silence(),
+        }
+    }
+
     pub fn load(pack_name: &str) -> Self {
-        let default_move = load_sound_from_bytes(include_bytes!("../move.wav"));
-        let default_select = load_sound_from_bytes(include_bytes!("../select.wav"));
-        let default_reject = load_sound_from_bytes(include_bytes!("../reject.wav"));
-        let default_back = load_sound_from_bytes(include_bytes!("../back.wav"));
+        if !audio_available() {
+            return Self::silent();
+        }
+
+        let default_move = load_sound_from_bytes(include_bytes!("../move.wav")).unwrap_or_else(|_| silence());
+        let default_select = load_sound_from_bytes(include_bytes!("../select.wav")).unwrap_or_else(|_| silence());
+        let default_reject = load_sound_from_bytes(include_bytes!("../reject.wav")).unwrap_or_else(|_| silence());
+        let default_back = load_sound_from_bytes(include_bytes!("../back.wav")).unwrap_or_else(|_| silence());
 
         if pack_name == "Default" {
             return SoundEffects {
+                dialog_open: default_select.clone(),
+                error: default_reject.clone(),
+                launch: default_select.clone(),
+                boot: silence(),
                 cursor_move: default_move,
                 select: default_select,
                 reject: default_reject,
@@ -98,44 +204,83 @@ impl SoundEffects {
         let reject = load_one_sfx("reject.wav", &user_pack_path, &system_pack_path, &default_reject);
         let back = load_one_sfx("back.wav", &user_pack_path, &system_pack_path, &default_back);
 
-        SoundEffects { cursor_move, select, reject, back }
+        // The extra events fall back to this pack's own sounds, not the defaults
+        let dialog_open = load_one_sfx("dialog_open.wav", &user_pack_path, &system_pack_path, &select);
+        let error = load_one_sfx("error.wav", &user_pack_path, &system_pack_path, &reject);
+        let launch = load_one_sfx("launch.wav", &user_pack_path, &system_pack_path, &select);
+        let boot = load_one_sfx("boot.wav", &user_pack_path, &system_pack_path, &silence());
+
+        SoundEffects { cursor_move, select, reject, back, dialog_open, error, launch, boot }
     }
 
     // [!] FIX: We manually create the Sink using .mixer() instead of .play_once()
     // because play_once requires OutputStreamHandle which you don't have.
 
     pub fn play_cursor_move(&self, config: &Config) {
-        let source = self.cursor_move.clone().amplify(config.sfx_volume);
-        let sink = Sink::connect_new(&AUDIO.stream.mixer());
-        sink.append(source);
-        sink.detach(); // Fire and forget
+        if let Some(sink) = new_sink() {
+            sink.append(self.cursor_move.clone().amplify(config.sfx_volume));
+            sink.detach(); // Fire and forget
+        }
     }
 
     pub fn play_select(&self, config: &Config) {
-        let source = self.select.clone().amplify(config.sfx_volume);
-        let sink = Sink::connect_new(&AUDIO.stream.mixer());
-        sink.append(source);
-        sink.detach();
+        if let Some(sink) = new_sink() {
+            sink.append(self.select.clone().amplify(config.sfx_volume));
+            sink.detach();
+        }
+        rumble::pulse_select(config);
     }
 
     pub fn play_reject(&self, config: &Config) {
-        let source = self.reject.clone().amplify(config.sfx_volume);
-        let sink = Sink::connect_new(&AUDIO.stream.mixer());
-        sink.append(source);
-        sink.detach();
+        if let Some(sink) = new_sink() {
+            sink.append(self.reject.clone().amplify(config.sfx_volume));
+            sink.detach();
+        }
+        rumble::pulse_reject(config);
     }
 
     pub fn play_back(&self, config: &Config) {
-        let source = self.back.clone().amplify(config.sfx_volume);
-        let sink = Sink::connect_new(&AUDIO.stream.mixer());
-        sink.append(source);
-        sink.detach();
+        if let Some(sink) = new_sink() {
+            sink.append(self.back.clone().amplify(config.sfx_volume));
+            sink.detach();
+        }
+    }
+
+    pub fn play_dialog_open(&self, config: &Config) {
+        self.play(&self.dialog_open, config);
+    }
+
+    pub fn play_error(&self, config: &Config) {
+        self.play(&self.error, config);
+        rumble::pulse_reject(config);
+    }
+
+    pub fn play_launch(&self, config: &Config) {
+        self.play(&self.launch, config);
+        rumble::pulse_select(config);
+    }
+
+    pub fn play_boot(&self, config: &Config) {
+        self.play(&self.boot, config);
+    }
+
+    fn play(&self, sound: &SamplesBuffer, config: &Config) {
+        if let Some(sink) = new_sink() {
+            sink.append(sound.clone().amplify(config.sfx_volume));
+            sink.detach();
+        }
     }
 }
 
 // --- Filesystem Functions ---
 // (This section is unchanged)
 pub fn find_sfx_pack_path(pack_name: &str) -> Option<PathBuf> {
+    // Packs installed on their own (e.g. imported from USB)
+    if let Some(user_pack) = get_user_data_dir().map(|d| d.join("sfx").join(pack_name)) {
+        if user_pack.is_dir() {
+            return Some(user_pack);
+        }
+    }
     if let Some(themes_dir) = get_user_data_dir().map(|d| d.join("themes")) {
         if let Ok(theme_entries) = fs::read_dir(themes_dir) {
             for theme_entry in theme_entries.flatten() {
@@ -208,9 +353,7 @@ pub fn play_new_bgm(
     }
 
     if track_name != "OFF" {
-        if let Some(sound_to_play) = music_cache.get(track_name) {
-            // [!] FIX: Use Sink::connect_new with the mixer
-            let sink = Sink::connect_new(&AUDIO.stream.mixer());
+        if let (Some(sound_to_play), Some(sink)) = (music_cache.get(track_name), new_sink()) {
 
             let source = sound_to_play
             .clone()
@@ -0,0 +1,100 @@
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::save;
+
+const USAGE: &str = "\
+Usage: kazeta-bios [OPTION]
+
+Headless options (print JSON to stdout and exit without opening a window):
+  --list-carts           List every .kzi/.kzp cartridge found under /run/media/
+  --list-saves <DRIVE>   List save data on a drive (use \"internal\" for the built-in storage)
+  --help                 Show this message
+
+Other options:
+  --safe-mode            Boot with the built-in assets only, skipping custom ones and themes";
+
+/// Handles headless command line flags.
+/// Returns Some(exit_code) if a flag was handled and the GUI should not start.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first()?;
+
+    let code = match command.as_str() {
+        "--list-carts" => print_json(list_carts()),
+        "--list-saves" => match args.get(1) {
+            Some(drive) => print_json(list_saves(drive)),
+            None => {
+                eprintln!("[ERROR] --list-saves needs a drive name, e.g. --list-saves internal");
+                2
+            }
+        },
+        "--help" | "-h" => {
+            println!("{}", USAGE);
+            0
+        }
+        // Anything else is left alone so the BIOS boots normally
+        _ => return None,
+    };
+
+    Some(code)
+}
+
+fn print_json(result: Result<Value, String>) -> i32 {
+    match result {
+        Ok(value) => {
+            println!("{}", serde_json::to_string_pretty(&value).unwrap_or_default());
+            0
+        }
+        Err(e) => {
+            println!("{}", json!({ "error": e }));
+            1
+        }
+    }
+}
+
+fn list_carts() -> Result<Value, String> {
+    let (game_paths, _debug_log) = save::find_all_game_files().map_err(|e| e.to_string())?;
+
+    let carts: Vec<Value> = game_paths.iter().map(|path| {
+        let is_package = path.extension().is_some_and(|e| e == "kzp");
+        if is_package {
+            // Same as the PLAY menu: a .kzp can't be read without mounting it
+            let id = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            return json!({ "path": path, "type": "kzp", "id": id, "name": id });
+        }
+
+        match save::parse_kzi_file(path) {
+            Ok(info) => json!({
+                "path": path,
+                "type": "kzi",
+                "id": info.id,
+                "name": info.name,
+                "exec": info.exec,
+                "icon": info.icon,
+                "runtime": info.runtime,
+            }),
+            Err(e) => json!({ "path": path, "type": "kzi", "error": e.to_string() }),
+        }
+    }).collect();
+
+    Ok(json!(carts))
+}
+
+fn list_saves(drive_name: &str) -> Result<Value, String> {
+    let details = save::get_save_details(drive_name).map_err(|e| e.to_string())?;
+    let save_dir = save::get_save_dir_from_drive_name(drive_name);
+
+    let saves: Vec<Value> = details.into_iter().map(|(cart_id, name, icon)| {
+        json!({
+            "id": cart_id,
+            "name": if name.is_empty() { None } else { Some(name) },
+            "icon": icon,
+            "has_icon": Path::new(&icon).exists(),
+            "playtime_hours": save::calculate_playtime(&cart_id, drive_name),
+            "size_mb": save::calculate_save_size(&cart_id, drive_name),
+        })
+    }).collect();
+
+    Ok(json!({ "drive": drive_name, "save_dir": save_dir, "saves": saves }))
+}
@@ -1,22 +1,238 @@
+use once_cell::sync::Lazy;
+use rand::seq::IndexedRandom;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf, error::Error};
-use crate::MenuPosition;
+use std::{collections::BTreeMap, ffi::OsString, fs, io::Write, path::{Path, PathBuf}, error::Error, time::{Duration, Instant}};
+use crate::{
+    GameSelectionView, LaunchMode, MenuPosition,
+    ui::settings::{ASPECT_RATIOS, BATTERY_DISPLAYS, BGM_ON_DEBUG, COLORS, COLOR_PALETTES, CURSOR_STYLES, DISPLAY_HOTPLUG, GAME_EXIT_ACTIONS, GRADIENT_DIRECTIONS, ICON_SCALES, LAYOUTS, LOADING_PERSONAS, LOGO_ANCHORS, STATUS_CORNERS, RESOLUTIONS, SHAKE_LEVELS, SPEEDS, TIMEZONES},
+    utils::parse_hex_color,
+};
+
+/// Selection value meaning "pick a different asset every boot" (background, logo and BGM).
+pub const RANDOM_CHOICE: &str = "RANDOM";
+
+/// Background selection value meaning "no image, just background_color".
+pub const SOLID_COLOR_CHOICE: &str = "SOLID COLOR";
+
+/// Background selection value meaning "no image, blend gradient_start into gradient_end".
+pub const GRADIENT_CHOICE: &str = "GRADIENT";
+
+/// Allowed logo_scale values; themes asking for more are clamped into it.
+pub const LOGO_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.25..=3.0;
+
+/// Allowed step sizes, in percent, for the volume and brightness settings.
+pub const STEP_PERCENT_RANGE: std::ops::RangeInclusive<u32> = 1..=25;
+
+/// A configured step size pulled into STEP_PERCENT_RANGE.
+pub fn step_percent(percent: u32) -> u32 {
+    percent.clamp(*STEP_PERCENT_RANGE.start(), *STEP_PERCENT_RANGE.end())
+}
+
+/// Minimum time between config writes while settings are being changed.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Environment variable that overrides the data directory location.
+pub const DATA_DIR_ENV_VAR: &str = "KAZETA_PLUS_DATA_DIR";
+
+/// The data directory override, resolved once at startup.
+/// The env var wins; otherwise we look for a `data_dir` entry in the config
+/// file at the default location (so it can point somewhere else entirely).
+static DATA_DIR_OVERRIDE: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    resolve_data_dir_override(std::env::var_os(DATA_DIR_ENV_VAR), default_user_data_dir())
+});
+
+/// DATA_DIR_OVERRIDE from the env var's value and the default data directory
+fn resolve_data_dir_override(env_value: Option<OsString>, default_dir: Option<PathBuf>) -> Option<PathBuf> {
+    if let Some(path) = env_value.filter(|v| !v.is_empty()) {
+        // println! on purpose: the logger itself needs this value to find its log file
+        println!("[INFO] Using data directory from {}: {}", DATA_DIR_ENV_VAR, PathBuf::from(&path).display());
+        return Some(PathBuf::from(path));
+    }
+
+    let content = fs::read_to_string(default_dir?.join("config.toml")).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    let path = value.get("data_dir")?.as_str().filter(|s| !s.trim().is_empty())?;
+    println!("[INFO] Using data directory from config: {}", path);
+    Some(PathBuf::from(path))
+}
+
+/// The stock location: ~/.local/share/kazeta-plus
+fn default_user_data_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|path| path.join(".local/share/kazeta-plus"))
+}
 
 /// Returns the path to the user's data directory for Kazeta+.
 /// This is a public helper function for other modules to use.
+/// Honors `KAZETA_PLUS_DATA_DIR` or a `data_dir` config value, falling back to the default.
 pub fn get_user_data_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|path| path.join(".local/share/kazeta-plus"))
+    DATA_DIR_OVERRIDE.clone().or_else(default_user_data_dir)
 }
 
 /// Gets the full path to the kazeta.toml configuration file.
-fn get_config_path() -> Result<PathBuf, Box<dyn Error>> {
+pub fn get_config_path() -> Result<PathBuf, Box<dyn Error>> {
     let mut config_path = get_user_data_dir().ok_or("Could not find user's data directory.")?;
     fs::create_dir_all(&config_path)?; // Create the directory if it doesn't exist
     config_path.push("config.toml");
     Ok(config_path)
 }
 
-#[derive(Serialize, Deserialize)]
+/// Writes `contents` to `path` via a temp file + rename.
+/// The previous file is kept as `<name>.bak` if it was still a valid config.
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("toml.tmp");
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    // Keep one backup of the last-good config
+    if Config::read_from(path).is_some() {
+        let _ = fs::copy(path, path.with_extension("toml.bak"));
+    }
+
+    fs::rename(&tmp_path, path)
+}
+
+/// One entry of the optional theme schedule, e.g. in config.toml:
+///
+/// [[theme_schedule]]
+/// theme = "Night"
+/// start = "20:00"
+/// end = "06:00"
+///
+/// `start`/`end` are HH:MM (local time per the TIME ZONE setting), `start_date`/`end_date` are MM-DD.
+/// Either window may be left out; both wrap around (over midnight / the new year).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ThemeScheduleEntry {
+    pub theme: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+/// The assets RANDOM resolved to this session
+#[derive(Default, Clone)]
+struct SessionPicks {
+    background: Option<String>,
+    logo: Option<String>,
+    bgm_track: Option<String>,
+    // Menu assets supplied by the inserted cart (cache keys), these win over everything else
+    cart_background: Option<String>,
+    cart_bgm_track: Option<String>,
+    /// The user's own settings from before the theme schedule swapped its theme in
+    theme_before_schedule: Option<Box<Config>>,
+    /// Set once the user picks a theme by hand, which the schedule then leaves alone
+    theme_picked: bool,
+    /// Saved selections swapped for the built-in one this session, written back by save()
+    held: HeldSelections,
+}
+
+/// Saved asset selections that are replaced by the built-in one for this session only,
+/// because their category isn't loaded or the BIOS booted in safe mode.
+#[derive(Default, Clone)]
+struct HeldSelections {
+    /// Safe mode: everything stays held, whatever the load switches say
+    builtin_only: bool,
+    theme: Option<String>,
+    sfx_pack: Option<String>,
+    bgm_track: Option<Option<String>>,
+    logo_selection: Option<String>,
+    background_selection: Option<String>,
+    font_selection: Option<String>,
+}
+
+/// Swaps `value` for `default` for this session, keeping the first saved value in `held`.
+fn hold<T>(value: &mut T, held: &mut Option<T>, default: T) {
+    let saved = std::mem::replace(value, default);
+    held.get_or_insert(saved);
+}
+
+/// Puts a value taken by hold() back.
+fn release<T>(value: &mut T, held: &mut Option<T>) {
+    if let Some(saved) = held.take() {
+        *value = saved;
+    }
+}
+
+/// Picks a random real asset from `choices`, skipping RANDOM itself and the "nothing" entries.
+fn pick_random(choices: &[String]) -> Option<String> {
+    let candidates: Vec<&String> = choices.iter()
+    .filter(|c| !matches!(c.as_str(), RANDOM_CHOICE | SOLID_COLOR_CHOICE | GRADIENT_CHOICE | "None" | "OFF"))
+    .collect();
+    candidates.choose(&mut rand::rng()).map(|c| c.to_string())
+}
+
+/// Stores `new` in `slot`, returning whether it was different.
+fn replace<T: PartialEq>(slot: &mut T, new: T) -> bool {
+    if *slot == new {
+        return false;
+    }
+    *slot = new;
+    true
+}
+
+fn one_of(key: &str, value: &str, choices: &[&str]) -> Result<String, String> {
+    if choices.contains(&value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("'{}' is not a valid {}", value, key))
+    }
+}
+
+fn color(key: &str, value: &str) -> Result<String, String> {
+    if COLORS.contains(&value) || parse_hex_color(value).is_some() {
+        Ok(value.to_string())
+    } else {
+        Err(format!("'{}' is not a valid {} (use a color name or #RRGGBB)", value, key))
+    }
+}
+
+fn volume(key: &str, value: &str) -> Result<f32, String> {
+    let volume: f32 = value.parse().map_err(|_| format!("{} must be a number, not '{}'", key, value))?;
+    // Round off the float drift from stepping by 0.1
+    Ok((volume.clamp(0.0, 1.0) * 100.0).round() / 100.0)
+}
+
+fn name(key: &str, value: &str) -> Result<String, String> {
+    if value.trim().is_empty() {
+        Err(format!("{} can't be empty", key))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+fn default_true() -> bool { true }
+fn default_icon_scale() -> f32 { 1.0 }
+fn default_game_selection_view() -> GameSelectionView { GameSelectionView::Grid }
+fn default_rumble_intensity() -> f32 { 0.5 }
+fn default_battery_display() -> String { "PERCENT".to_string() }
+fn default_color_palette() -> String { "DEFAULT".to_string() }
+fn default_loading_persona() -> String { "KAZETA".to_string() }
+fn default_shake_intensity() -> String { "NORMAL".to_string() }
+fn default_shake_duration() -> f32 { 0.2 }
+fn default_on_game_exit() -> String { "MAIN MENU".to_string() }
+fn default_display_hotplug() -> String { "ASK".to_string() }
+fn default_layout() -> String { "AUTO".to_string() }
+fn default_bgm_on_debug() -> String { "DUCK".to_string() }
+fn default_step_percent() -> u32 { 10 }
+fn default_logo_scale() -> f32 { 1.0 }
+fn default_logo_anchor() -> String { "TOP CENTER".to_string() }
+fn default_status_corner() -> String { "TOP RIGHT".to_string() }
+fn default_background_color() -> String { "BLACK".to_string() }
+fn default_gradient_start() -> String { "BLUE".to_string() }
+fn default_gradient_end() -> String { "BLACK".to_string() }
+fn default_gradient_direction() -> String { "VERTICAL".to_string() }
+fn default_battery_warning_level() -> u32 { 20 }
+fn default_low_space_warning_mb() -> u32 { 500 }
+fn default_battery_critical_level() -> u32 { 5 }
+// The desktop build has no session to restart into
+fn default_launch_mode() -> LaunchMode {
+    if crate::DEV_MODE { LaunchMode::InPlace } else { LaunchMode::SessionRestart }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     pub aspect_ratio: String,
     pub resolution: String,
@@ -27,9 +243,19 @@ pub struct Config {
     pub autoboot: bool,
     pub bgm_volume: f32,
     pub sfx_volume: f32,
+    /// How far one press moves MASTER VOLUME, BGM VOLUME, SFX VOLUME and BRIGHTNESS, in percent (1-25).
+    #[serde(default = "default_step_percent")]
+    pub volume_step: u32,
+    #[serde(default = "default_step_percent")]
+    pub bgm_volume_step: u32,
+    #[serde(default = "default_step_percent")]
+    pub sfx_volume_step: u32,
+    #[serde(default = "default_step_percent")]
+    pub brightness_step: u32,
     pub audio_output: String,
     pub theme: String,
-    pub menu_position: MenuPosition,
+    /// Written only through set_menu_position() (or set()), like the other enum settings
+    menu_position: MenuPosition,
     pub font_color: String,
     pub cursor_color: String,
     pub cursor_style: String,
@@ -39,9 +265,161 @@ pub struct Config {
     pub color_shift_speed: String,
     pub bgm_track: Option<String>,
     pub sfx_pack: String,
+    /// Set when the user picks a sound pack by hand, so switching themes won't replace it.
+    #[serde(default)]
+    pub sfx_pack_overridden: bool,
     pub logo_selection: String,
     pub background_selection: String,
     pub font_selection: String,
+    /// Alternate location for themes, assets and this config (e.g. a separate partition).
+    /// Only read from the config at the default location; takes effect on next launch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<String>,
+    /// Automatic theme switching by time of day / date. Empty (off) by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub theme_schedule: Vec<ThemeScheduleEntry>,
+    /// Last window size on desktop, restored at startup (not used by the session build).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_width: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_height: Option<i32>,
+    /// Run fullscreen (toggled live from the general settings).
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// Which monitor (xrandr order) to open on, desktop only. Falls back to the primary if it's gone.
+    #[serde(default)]
+    pub display_index: usize,
+    /// What to do when a monitor or TV is plugged in or swapped mid-session (ASK, AUTO or OFF)
+    #[serde(default = "default_display_hotplug")]
+    pub display_hotplug: String,
+    /// Menu layout: LANDSCAPE, PORTRAIT (vertical handhelds, rotated cabinets) or AUTO to follow the screen shape
+    #[serde(default = "default_layout")]
+    pub layout: String,
+    /// Let every connected controller navigate, instead of only the first one to press a button.
+    #[serde(default)]
+    pub any_controller: bool,
+    /// Short controller rumble on select/reject (controllers without force feedback ignore it).
+    #[serde(default)]
+    pub rumble: bool,
+    /// Rumble strength, 0.0 - 1.0.
+    #[serde(default = "default_rumble_intensity")]
+    pub rumble_intensity: f32,
+    /// How the battery is shown in the corner: OFF, ICON, PERCENT or BOTH.
+    #[serde(default = "default_battery_display")]
+    pub battery_display: String,
+    /// Battery percentage that shows a low battery warning (0 turns it off).
+    #[serde(default = "default_battery_warning_level")]
+    pub battery_warning_level: u32,
+    /// Free MB on internal storage below which the main menu warns that saves may fail (0 turns it off).
+    #[serde(default = "default_low_space_warning_mb")]
+    pub low_space_warning_mb: u32,
+    /// Battery percentage considered critical.
+    #[serde(default = "default_battery_critical_level")]
+    pub battery_critical_level: u32,
+    /// Put the system to sleep when the battery reaches the critical level.
+    #[serde(default)]
+    pub battery_critical_sleep: bool,
+    /// Letterbox/pillarbox the background to aspect_ratio instead of stretching it to the screen.
+    #[serde(default)]
+    pub letterbox: bool,
+    /// Color of the SOLID COLOR background: a color name from the settings list or "#RRGGBB".
+    #[serde(default = "default_background_color")]
+    pub background_color: String,
+    /// First color of the GRADIENT background (top or left), named or "#RRGGBB".
+    #[serde(default = "default_gradient_start")]
+    pub gradient_start: String,
+    /// Second color of the GRADIENT background (bottom or right).
+    #[serde(default = "default_gradient_end")]
+    pub gradient_end: String,
+    /// VERTICAL (top to bottom) or HORIZONTAL (left to right).
+    #[serde(default = "default_gradient_direction")]
+    pub gradient_direction: String,
+    /// Colors the background tint cycles through: DEFAULT, PASTEL, NEON, GRAYSCALE or CUSTOM.
+    #[serde(default = "default_color_palette")]
+    pub color_palette: String,
+    /// "#RRGGBB" colors used when color_palette is CUSTOM.
+    #[serde(default)]
+    pub custom_palette: Vec<String>,
+    /// Multiplier for save/game icon size in the data and game selection grids.
+    #[serde(default = "default_icon_scale")]
+    pub icon_scale: f32,
+    /// How the game selection screen shows multiple carts: an icon Grid, a List of names or a Carousel.
+    #[serde(default = "default_game_selection_view")]
+    game_selection_view: GameSelectionView,
+    /// Multiplier for the logo's width (0.25 to 3.0).
+    #[serde(default = "default_logo_scale")]
+    pub logo_scale: f32,
+    /// TOP CENTER, TOP LEFT or TOP RIGHT.
+    #[serde(default = "default_logo_anchor")]
+    pub logo_anchor: String,
+    /// Corner for the clock/battery block: TOP RIGHT, TOP LEFT, BOTTOM LEFT or BOTTOM RIGHT.
+    #[serde(default = "default_status_corner")]
+    pub status_corner: String,
+    /// Which message set the loading screen picks from: KAZETA, MONIKA, BENDER or CUSTOM.
+    #[serde(default = "default_loading_persona")]
+    pub loading_persona: String,
+    /// Reject shake strength (one of SHAKE_LEVELS) and length in seconds. The length is config-file only.
+    #[serde(default = "default_shake_intensity")]
+    pub shake_intensity: String,
+    #[serde(default = "default_shake_duration")]
+    pub shake_duration: f32,
+    /// Play the sound pack's boot.wav once at startup, whether or not the splash is shown.
+    #[serde(default = "default_true")]
+    pub boot_chime: bool,
+    /// What the BGM does while the debug screen is up: PLAY on, DUCK to a low volume, or STOP.
+    #[serde(default = "default_bgm_on_debug")]
+    pub bgm_on_debug: String,
+    /// Show the button legend along the bottom of the screen.
+    #[serde(default = "default_true")]
+    pub show_help_bar: bool,
+    /// DELETE and RESET need the confirm button held for a second instead of a single press.
+    #[serde(default)]
+    pub hold_to_confirm: bool,
+    /// Holding back for a second jumps straight to the main menu from any menu screen.
+    #[serde(default)]
+    pub hold_back_to_menu: bool,
+    /// Show a brief SETTINGS SAVED toast whenever changed settings are written to disk.
+    #[serde(default)]
+    pub save_toasts: bool,
+    /// Moving past the first or last menu entry jumps to the other end instead of stopping.
+    #[serde(default = "default_true")]
+    pub menu_wrap: bool,
+    /// Theme authors: reload themes and assets whenever their files change on disk. Config-file only.
+    #[serde(default)]
+    pub theme_hot_reload: bool,
+    /// Per-category switches for scanning and loading custom assets at boot. A category that's
+    /// off skips its folders entirely and uses only the built-in default.
+    #[serde(default = "default_true")]
+    pub load_backgrounds: bool,
+    #[serde(default = "default_true")]
+    pub load_logos: bool,
+    #[serde(default = "default_true")]
+    pub load_fonts: bool,
+    #[serde(default = "default_true")]
+    pub load_music: bool,
+    /// Whether PLAY restarts the session into the game or runs it in place.
+    #[serde(default = "default_launch_mode")]
+    launch_mode: LaunchMode,
+    /// Where an in-place game that quits cleanly leaves you: MAIN MENU, GAME SELECTION or RELAUNCH.
+    /// A game that crashes always leaves you on the debug screen.
+    #[serde(default = "default_on_game_exit")]
+    pub on_game_exit: String,
+    /// SMB (`//server/share`) or NFS (`server:/export`) folder scanned for carts next to the
+    /// local drives. Mounted read-only as a guest at startup; empty turns it off.
+    #[serde(default)]
+    pub network_share: String,
+    /// Navigation actions (UP, SELECT, BACK, ...) moved off their default controller button or
+    /// key, as gilrs / macroquad names ("South", "RightTrigger2", "Space"). NONE leaves one unbound.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub button_bindings: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub key_bindings: BTreeMap<String, String>,
+    /// What RANDOM selections resolved to for this session. Never written to disk.
+    #[serde(skip)]
+    session_picks: SessionPicks,
+    /// Set when a change is waiting to be written to disk.
+    #[serde(skip)]
+    dirty_since: Option<Instant>,
 }
 
 impl Default for Config {
@@ -56,6 +434,10 @@ impl Default for Config {
             autoboot: true,
             bgm_volume: 0.7,
             sfx_volume: 0.7,
+            volume_step: default_step_percent(),
+            bgm_volume_step: default_step_percent(),
+            sfx_volume_step: default_step_percent(),
+            brightness_step: default_step_percent(),
             audio_output: "Auto".to_string(),
             theme: "Default".to_string(),
             menu_position: MenuPosition::Center,
@@ -68,42 +450,708 @@ impl Default for Config {
             color_shift_speed: "NORMAL".to_string(),
             bgm_track: None,
             sfx_pack: "Default".to_string(),
+            sfx_pack_overridden: false,
             logo_selection: "Kazeta+ (Default)".to_string(),
             background_selection: "Default".to_string(),
             font_selection: "Default".to_string(),
+            data_dir: None,
+            theme_schedule: Vec::new(),
+            window_width: None,
+            window_height: None,
+            fullscreen: false,
+            display_index: 0,
+            display_hotplug: default_display_hotplug(),
+            layout: default_layout(),
+            any_controller: false,
+            rumble: false,
+            rumble_intensity: default_rumble_intensity(),
+            battery_display: default_battery_display(),
+            battery_warning_level: default_battery_warning_level(),
+            low_space_warning_mb: default_low_space_warning_mb(),
+            battery_critical_level: default_battery_critical_level(),
+            battery_critical_sleep: false,
+            letterbox: false,
+            icon_scale: default_icon_scale(),
+            game_selection_view: default_game_selection_view(),
+            hold_to_confirm: false,
+            hold_back_to_menu: false,
+            save_toasts: false,
+            menu_wrap: true,
+            theme_hot_reload: false,
+            load_backgrounds: true,
+            load_logos: true,
+            load_fonts: true,
+            load_music: true,
+            boot_chime: true,
+            bgm_on_debug: default_bgm_on_debug(),
+            loading_persona: default_loading_persona(),
+            shake_intensity: default_shake_intensity(),
+            shake_duration: default_shake_duration(),
+            logo_scale: default_logo_scale(),
+            logo_anchor: default_logo_anchor(),
+            status_corner: default_status_corner(),
+            background_color: default_background_color(),
+            gradient_start: default_gradient_start(),
+            gradient_end: default_gradient_end(),
+            gradient_direction: default_gradient_direction(),
+            color_palette: default_color_palette(),
+            custom_palette: Vec::new(),
+            show_help_bar: true,
+            launch_mode: default_launch_mode(),
+            on_game_exit: default_on_game_exit(),
+            network_share: String::new(),
+            button_bindings: BTreeMap::new(),
+            key_bindings: BTreeMap::new(),
+            session_picks: SessionPicks::default(),
+            dirty_since: None,
         }
     }
 }
 
 impl Config {
     /// Loads the configuration from config.toml, or returns a default if it fails.
+    /// If the main file is missing or corrupt, the last-good backup is tried first.
     pub fn load() -> Self {
+        get_config_path().ok()
+            .and_then(|config_path| Self::load_from(&config_path))
+            .unwrap_or_default()
+    }
+
+    /// The config at `config_path`, or its last-good backup if that one is missing or corrupt
+    fn load_from(config_path: &Path) -> Option<Self> {
+        if let Some(config) = Self::read_from(config_path) {
+            return Some(config);
+        }
+
+        let backup_path = config_path.with_extension("toml.bak");
+        let config = Self::read_from(&backup_path)?;
+        log_warn!("config.toml is unreadable, restored settings from {}", backup_path.display());
+        Some(config)
+    }
+
+    /// Whether a config file (or its backup) exists yet. False on first boot.
+    pub fn is_saved() -> bool {
+        get_config_path().is_ok_and(|path| path.exists() || path.with_extension("toml.bak").exists())
+    }
+
+    fn read_from(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Saves the current configuration to config.toml, returning whether it was written.
+    /// Writes to a temp file and renames it over the target so a power cut
+    /// mid-write can't leave a half-written config behind.
+    pub fn save(&self) -> bool {
         if let Ok(config_path) = get_config_path() {
-            if let Ok(content) = fs::read_to_string(config_path) {
-                if let Ok(config) = toml::from_str(&content) {
-                    return config;
+            if let Ok(toml_string) = toml::to_string_pretty(&self.saved_copy()) {
+                match write_atomic(&config_path, &toml_string) {
+                    Ok(()) => return true,
+                    Err(e) => log_error!("Failed to save config to {}: {}", config_path.display(), e),
                 }
             }
         }
-        Self::default()
+        false
     }
 
-    /// Saves the current configuration to config.toml.
-    pub fn save(&self) {
-        if let Ok(config_path) = get_config_path() {
-            if let Ok(toml_string) = toml::to_string_pretty(self) {
-                let _ = fs::write(config_path, toml_string);
+    /// The config as it goes to disk. A scheduled theme is never written; the user's own theme
+    /// is saved in its place, and so are selections held back for safe mode or unloaded categories.
+    fn saved_copy(&self) -> Config {
+        let mut saved = self.clone();
+        if let Some(own) = &self.session_picks.theme_before_schedule {
+            saved.copy_theme_settings(own);
+        }
+        let held = &self.session_picks.held;
+        if let Some(theme) = &held.theme {
+            saved.theme = theme.clone();
+        }
+        if let Some(pack) = &held.sfx_pack {
+            saved.sfx_pack = pack.clone();
+        }
+        if let Some(track) = &held.bgm_track {
+            saved.bgm_track = track.clone();
+        }
+        for (selection, held) in [
+            (&mut saved.logo_selection, &held.logo_selection),
+            (&mut saved.background_selection, &held.background_selection),
+            (&mut saved.font_selection, &held.font_selection),
+        ] {
+            if let Some(value) = held {
+                *selection = value.clone();
+            }
+        }
+        saved
+    }
+
+    /// Switches every asset selection to the built-in one for this session, for safe mode.
+    /// save() keeps writing the saved selections, so picks made in safe mode are session-only
+    /// and the next normal boot comes back with the old ones.
+    pub fn use_builtin_assets(&mut self) {
+        let defaults = Config::default();
+        let held = &mut self.session_picks.held;
+        held.builtin_only = true;
+        hold(&mut self.theme, &mut held.theme, defaults.theme);
+        hold(&mut self.sfx_pack, &mut held.sfx_pack, defaults.sfx_pack);
+        hold(&mut self.bgm_track, &mut held.bgm_track, defaults.bgm_track);
+        hold(&mut self.logo_selection, &mut held.logo_selection, defaults.logo_selection);
+        hold(&mut self.background_selection, &mut held.background_selection, defaults.background_selection);
+        hold(&mut self.font_selection, &mut held.font_selection, defaults.font_selection);
+    }
+
+    /// Switches the selections in asset categories that aren't loaded to the built-in one for
+    /// this session. save() keeps writing the old pick, and turning the category back on restores it.
+    pub fn skip_unloaded_assets(&mut self) {
+        if self.session_picks.held.builtin_only {
+            return;
+        }
+        let defaults = Config::default();
+        let held = &mut self.session_picks.held;
+        if self.load_backgrounds {
+            release(&mut self.background_selection, &mut held.background_selection);
+        } else {
+            hold(&mut self.background_selection, &mut held.background_selection, defaults.background_selection);
+        }
+        if self.load_logos {
+            release(&mut self.logo_selection, &mut held.logo_selection);
+        } else {
+            hold(&mut self.logo_selection, &mut held.logo_selection, defaults.logo_selection);
+        }
+        if self.load_fonts {
+            release(&mut self.font_selection, &mut held.font_selection);
+        } else {
+            hold(&mut self.font_selection, &mut held.font_selection, defaults.font_selection);
+        }
+        if self.load_music {
+            release(&mut self.bgm_track, &mut held.bgm_track);
+        } else {
+            hold(&mut self.bgm_track, &mut held.bgm_track, defaults.bgm_track);
+        }
+    }
+
+    /// Points selections saved as a bare filename at the theme asset they now live under
+    /// ("<theme>/<filename>"), when no global asset has that name.
+    pub fn migrate_bare_asset_names(&mut self, backgrounds: &[String], logos: &[String], fonts: &[String], bgm_tracks: &[String]) {
+        fn namespaced(value: &str, choices: &[String]) -> Option<String> {
+            if value.contains('/') || choices.iter().any(|c| c == value) {
+                return None;
             }
+            let suffix = format!("/{}", value);
+            choices.iter().find(|c| c.ends_with(&suffix)).cloned()
+        }
+
+        let mut migrated = Vec::new();
+        if let Some(key) = namespaced(&self.background_selection, backgrounds) {
+            migrated.push(key.clone());
+            self.background_selection = key;
+        }
+        if let Some(key) = namespaced(&self.logo_selection, logos) {
+            migrated.push(key.clone());
+            self.logo_selection = key;
+        }
+        if let Some(key) = namespaced(&self.font_selection, fonts) {
+            migrated.push(key.clone());
+            self.font_selection = key;
+        }
+        if let Some(key) = self.bgm_track.as_deref().and_then(|track| namespaced(track, bgm_tracks)) {
+            migrated.push(key.clone());
+            self.bgm_track = Some(key);
+        }
+
+        if !migrated.is_empty() {
+            log_info!("Migrated asset selections to theme names: {}", migrated.join(", "));
+            self.mark_dirty();
+        }
+    }
+
+    /// Points background, logo and font selections whose asset is gone (file deleted, theme removed)
+    /// back at the default, returning "<KIND> <name>" for each. Marked dirty so it's only reported once.
+    pub fn drop_missing_assets(&mut self, backgrounds: &[String], logos: &[String], fonts: &[String]) -> Vec<String> {
+        let defaults = Config::default();
+        let mut missing = Vec::new();
+        for (kind, selection, choices, default) in [
+            ("BACKGROUND", &mut self.background_selection, backgrounds, defaults.background_selection),
+            ("LOGO", &mut self.logo_selection, logos, defaults.logo_selection),
+            ("FONT", &mut self.font_selection, fonts, defaults.font_selection),
+        ] {
+            if !choices.contains(selection) {
+                log_warn!("Selected {} '{}' not found, falling back to '{}'.", kind.to_lowercase(), selection, default);
+                missing.push(format!("{} {}", kind, selection));
+                *selection = default;
+            }
+        }
+
+        if !missing.is_empty() {
+            self.mark_dirty();
+        }
+        missing
+    }
+
+    /// Resolves any RANDOM selections to real assets for this session.
+    /// Only the session picks change; the config keeps the literal RANDOM.
+    /// An existing pick is kept, so this is safe to call again after any selection changes.
+    pub fn resolve_random_picks(&mut self, backgrounds: &[String], logos: &[String], bgm_tracks: &[String]) {
+        fn resolve(is_random: bool, pick: &mut Option<String>, choices: &[String]) {
+            if !is_random {
+                *pick = None;
+            } else if pick.is_none() {
+                *pick = pick_random(choices);
+            }
+        }
+
+        resolve(self.background_selection == RANDOM_CHOICE, &mut self.session_picks.background, backgrounds);
+        resolve(self.logo_selection == RANDOM_CHOICE, &mut self.session_picks.logo, logos);
+        resolve(self.bgm_track.as_deref() == Some(RANDOM_CHOICE), &mut self.session_picks.bgm_track, bgm_tracks);
+    }
+
+    /// Temporarily overrides the menu background/BGM with assets from the inserted cart.
+    /// Pass None for both to go back to the user's selections.
+    pub fn set_cart_overrides(&mut self, background: Option<String>, bgm_track: Option<String>) {
+        self.session_picks.cart_background = background;
+        self.session_picks.cart_bgm_track = bgm_track;
+    }
+
+    /// Whether the theme schedule may switch themes: not once the user picked one this session
+    pub fn theme_schedule_allowed(&self) -> bool {
+        !self.session_picks.theme_picked
+    }
+
+    /// Remembers the user's theme before the schedule switches to another one, so it keeps
+    /// being saved and can be restored. Repeated calls keep the first snapshot.
+    pub fn begin_scheduled_theme(&mut self) {
+        if self.session_picks.theme_before_schedule.is_none() {
+            self.session_picks.theme_before_schedule = Some(Box::new(self.clone()));
+        }
+    }
+
+    /// Puts the user's own theme back after a scheduled one. False if none was active.
+    pub fn end_scheduled_theme(&mut self) -> bool {
+        match self.session_picks.theme_before_schedule.take() {
+            Some(own) => {
+                self.copy_theme_settings(&own);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks the current theme as the user's own pick, ending any scheduled one for the session
+    pub fn pick_theme(&mut self) {
+        self.session_picks.theme_before_schedule = None;
+        self.session_picks.theme_picked = true;
+    }
+
+    /// Everything theme::apply_theme() sets
+    fn copy_theme_settings(&mut self, from: &Config) {
+        self.theme = from.theme.clone();
+        self.sfx_pack = from.sfx_pack.clone();
+        self.bgm_track = from.bgm_track.clone();
+        self.logo_selection = from.logo_selection.clone();
+        self.logo_scale = from.logo_scale;
+        self.logo_anchor = from.logo_anchor.clone();
+        self.background_selection = from.background_selection.clone();
+        self.font_selection = from.font_selection.clone();
+        self.menu_position = from.menu_position;
+        self.font_color = from.font_color.clone();
+        self.cursor_color = from.cursor_color.clone();
+        self.cursor_style = from.cursor_style.clone();
+        self.cursor_blink_speed = from.cursor_blink_speed.clone();
+        self.cursor_transition_speed = from.cursor_transition_speed.clone();
+        self.background_scroll_speed = from.background_scroll_speed.clone();
+        self.color_shift_speed = from.color_shift_speed.clone();
+    }
+
+    /// The background to actually draw (cart override and RANDOM resolved).
+    pub fn active_background(&self) -> &str {
+        if let Some(background) = &self.session_picks.cart_background {
+            background
+        } else if self.background_selection == RANDOM_CHOICE {
+            self.session_picks.background.as_deref().unwrap_or("Default")
+        } else {
+            &self.background_selection
+        }
+    }
+
+    /// The logo to actually draw (RANDOM resolved).
+    pub fn active_logo(&self) -> &str {
+        if self.logo_selection == RANDOM_CHOICE {
+            self.session_picks.logo.as_deref().unwrap_or("Kazeta+ (Default)")
+        } else {
+            &self.logo_selection
+        }
+    }
+
+    /// The BGM track to actually play, or "OFF" (cart override and RANDOM resolved).
+    pub fn active_bgm_track(&self) -> &str {
+        if let Some(track) = &self.session_picks.cart_bgm_track {
+            return track;
+        }
+        match self.bgm_track.as_deref() {
+            Some(RANDOM_CHOICE) => self.session_picks.bgm_track.as_deref().unwrap_or("OFF"),
+            Some(track) => track,
+            None => "OFF",
+        }
+    }
+
+    /// Switches to the display's own resolution, as found by system::detect_native_resolution(),
+    /// for a fresh config. Keeps the current one when nothing was detected.
+    pub fn use_native_resolution(&mut self, detected: Option<(&str, &str)>) {
+        match detected {
+            Some((resolution, aspect_ratio)) => {
+                log_info!("Detected native resolution {}.", resolution);
+                for (key, value) in [("aspect_ratio", aspect_ratio), ("resolution", resolution)] {
+                    if let Err(e) = self.set(key, value) {
+                        log_warn!("Could not use the detected resolution: {}", e);
+                    }
+                }
+            }
+            None => log_info!("Couldn't detect the display, keeping {}.", self.resolution),
+        }
+    }
+
+    /// The saved desktop window size, if there is a usable one.
+    pub fn window_size(&self) -> Option<(i32, i32)> {
+        match (self.window_width, self.window_height) {
+            (Some(w), Some(h)) if w > 0 && h > 0 => Some((w, h)),
+            _ => None,
+        }
+    }
+
+    pub fn set_window_size(&mut self, width: i32, height: i32) {
+        if self.window_size() != Some((width, height)) {
+            self.window_width = Some(width);
+            self.window_height = Some(height);
+            self.mark_dirty();
+        }
+    }
+
+    /// Flags the config as changed. The actual write happens in save_if_due()/flush(),
+    /// so holding left/right on a slider doesn't hammer the disk.
+    pub fn mark_dirty(&mut self) {
+        if self.dirty_since.is_none() {
+            self.dirty_since = Some(Instant::now());
+        }
+    }
+
+    /// Writes pending changes once the debounce window has passed. Call once per frame.
+    /// True when changes were written this call.
+    pub fn save_if_due(&mut self) -> bool {
+        self.dirty_since.is_some_and(|since| since.elapsed() >= SAVE_DEBOUNCE) && self.flush()
+    }
+
+    /// Writes pending changes immediately (e.g. when leaving a screen).
+    /// True when changes were written this call.
+    pub fn flush(&mut self) -> bool {
+        self.dirty_since.take().is_some() && self.save()
+    }
+
+    /// Changes a setting by its key in config.toml, checking the value first. Values use the
+    /// form the settings menu shows: "ON"/"OFF" for switches, 0.0 - 1.0 for volumes, "OFF"
+    /// for no BGM. Only marks the config dirty when the value actually changed.
+    /// While a scheduled theme is showing, the change also goes to the user's own theme, which is
+    /// what gets saved and what comes back when the schedule ends.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        self.set_value(key, value)?;
+        if let Some(own) = self.session_picks.theme_before_schedule.as_mut() {
+            own.set_value(key, value)?;
+        }
+        Ok(())
+    }
+
+    fn set_value(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if let Some(flag) = self.flag_mut(key) {
+            let on = match value {
+                "ON" | "true" => true,
+                "OFF" | "false" => false,
+                _ => return Err(format!("{} must be ON or OFF, not '{}'", key, value)),
+            };
+            if replace(flag, on) {
+                self.mark_dirty();
+            }
+            return Ok(());
+        }
+
+        let changed = match key {
+            "resolution" => replace(&mut self.resolution, one_of(key, value, RESOLUTIONS)?),
+            "aspect_ratio" => replace(&mut self.aspect_ratio, one_of(key, value, ASPECT_RATIOS)?),
+            "timezone" => replace(&mut self.timezone, one_of(key, value, &TIMEZONES)?),
+            "cursor_style" => replace(&mut self.cursor_style, one_of(key, value, CURSOR_STYLES)?),
+            "cursor_blink_speed" => replace(&mut self.cursor_blink_speed, one_of(key, value, SPEEDS)?),
+            "cursor_transition_speed" => replace(&mut self.cursor_transition_speed, one_of(key, value, SPEEDS)?),
+            "background_scroll_speed" => replace(&mut self.background_scroll_speed, one_of(key, value, SPEEDS)?),
+            "color_shift_speed" => replace(&mut self.color_shift_speed, one_of(key, value, SPEEDS)?),
+            "color_palette" => replace(&mut self.color_palette, one_of(key, value, COLOR_PALETTES)?),
+            "battery_display" => replace(&mut self.battery_display, one_of(key, value, BATTERY_DISPLAYS)?),
+            "gradient_direction" => replace(&mut self.gradient_direction, one_of(key, value, GRADIENT_DIRECTIONS)?),
+            "status_corner" => replace(&mut self.status_corner, one_of(key, value, STATUS_CORNERS)?),
+            "logo_anchor" => replace(&mut self.logo_anchor, one_of(key, value, LOGO_ANCHORS)?),
+            "shake_intensity" => replace(&mut self.shake_intensity, one_of(key, value, SHAKE_LEVELS)?),
+            "loading_persona" => replace(&mut self.loading_persona, one_of(key, value, LOADING_PERSONAS)?),
+            "display_hotplug" => replace(&mut self.display_hotplug, one_of(key, value, DISPLAY_HOTPLUG)?),
+            "layout" => replace(&mut self.layout, one_of(key, value, LAYOUTS)?),
+            "on_game_exit" => replace(&mut self.on_game_exit, one_of(key, value, GAME_EXIT_ACTIONS)?),
+            "bgm_on_debug" => replace(&mut self.bgm_on_debug, one_of(key, value, BGM_ON_DEBUG)?),
+            "font_color" => replace(&mut self.font_color, color(key, value)?),
+            "cursor_color" => replace(&mut self.cursor_color, color(key, value)?),
+            "background_color" => replace(&mut self.background_color, color(key, value)?),
+            "gradient_start" => replace(&mut self.gradient_start, color(key, value)?),
+            "gradient_end" => replace(&mut self.gradient_end, color(key, value)?),
+            "bgm_volume" => replace(&mut self.bgm_volume, volume(key, value)?),
+            "sfx_volume" => replace(&mut self.sfx_volume, volume(key, value)?),
+            "icon_scale" => {
+                let scale = value.parse().ok().filter(|s| ICON_SCALES.contains(s))
+                    .ok_or_else(|| format!("'{}' is not a valid {}", value, key))?;
+                replace(&mut self.icon_scale, scale)
+            }
+            "logo_scale" => {
+                let scale = value.parse().ok().filter(|s| LOGO_SCALE_RANGE.contains(s))
+                    .ok_or_else(|| format!("'{}' is not a valid {}", value, key))?;
+                replace(&mut self.logo_scale, scale)
+            }
+            "audio_output" => replace(&mut self.audio_output, name(key, value)?),
+            "sfx_pack" => replace(&mut self.sfx_pack, name(key, value)?),
+            "logo_selection" => replace(&mut self.logo_selection, name(key, value)?),
+            "background_selection" => replace(&mut self.background_selection, name(key, value)?),
+            "font_selection" => replace(&mut self.font_selection, name(key, value)?),
+            "bgm_track" => {
+                let track = (value != "OFF").then(|| name(key, value)).transpose()?;
+                replace(&mut self.bgm_track, track)
+            }
+            "display_index" => {
+                let index = value.parse().map_err(|_| format!("{} must be a number, not '{}'", key, value))?;
+                replace(&mut self.display_index, index)
+            }
+            "menu_position" => {
+                let position = MenuPosition::from_label(value).ok_or_else(|| format!("unknown menu_position '{}'", value))?;
+                replace(&mut self.menu_position, position)
+            }
+            "game_selection_view" => {
+                let view = GameSelectionView::from_label(value).ok_or_else(|| format!("unknown game_selection_view '{}'", value))?;
+                replace(&mut self.game_selection_view, view)
+            }
+            "launch_mode" => {
+                let mode = LaunchMode::from_label(value).ok_or_else(|| format!("unknown launch_mode '{}'", value))?;
+                replace(&mut self.launch_mode, mode)
+            }
+            _ => return Err(format!("unknown setting '{}'", key)),
+        };
+        if changed {
+            self.mark_dirty();
+        }
+        Ok(())
+    }
+
+    pub fn menu_position(&self) -> MenuPosition {
+        self.menu_position
+    }
+
+    pub fn game_selection_view(&self) -> GameSelectionView {
+        self.game_selection_view
+    }
+
+    pub fn launch_mode(&self) -> LaunchMode {
+        self.launch_mode
+    }
+
+    /// set() for the enum settings, without a round trip through their labels
+    pub fn set_menu_position(&mut self, position: MenuPosition) {
+        if replace(&mut self.menu_position, position) {
+            self.mark_dirty();
+        }
+    }
+
+    pub fn set_game_selection_view(&mut self, view: GameSelectionView) {
+        if replace(&mut self.game_selection_view, view) {
+            self.mark_dirty();
+        }
+    }
+
+    pub fn set_launch_mode(&mut self, mode: LaunchMode) {
+        if replace(&mut self.launch_mode, mode) {
+            self.mark_dirty();
+        }
+    }
+
+    /// Flips an ON/OFF setting.
+    pub fn toggle(&mut self, key: &str) -> Result<(), String> {
+        let on = *self.flag_mut(key).ok_or_else(|| format!("{} is not an ON/OFF setting", key))?;
+        self.set(key, if on { "OFF" } else { "ON" })
+    }
+
+    fn flag_mut(&mut self, key: &str) -> Option<&mut bool> {
+        match key {
+            "show_splash_screen" => Some(&mut self.show_splash_screen),
+            "wifi" => Some(&mut self.wifi),
+            "bluetooth" => Some(&mut self.bluetooth),
+            "autoboot" => Some(&mut self.autoboot),
+            "fullscreen" => Some(&mut self.fullscreen),
+            "letterbox" => Some(&mut self.letterbox),
+            "hold_to_confirm" => Some(&mut self.hold_to_confirm),
+            "hold_back_to_menu" => Some(&mut self.hold_back_to_menu),
+            "save_toasts" => Some(&mut self.save_toasts),
+            "menu_wrap" => Some(&mut self.menu_wrap),
+            "load_backgrounds" => Some(&mut self.load_backgrounds),
+            "load_logos" => Some(&mut self.load_logos),
+            "load_fonts" => Some(&mut self.load_fonts),
+            "load_music" => Some(&mut self.load_music),
+            "boot_chime" => Some(&mut self.boot_chime),
+            "show_help_bar" => Some(&mut self.show_help_bar),
+            "sfx_pack_overridden" => Some(&mut self.sfx_pack_overridden),
+            "any_controller" => Some(&mut self.any_controller),
+            "rumble" => Some(&mut self.rumble),
+            "battery_critical_sleep" => Some(&mut self.battery_critical_sleep),
+            _ => None,
         }
     }
 
     pub fn delete() -> std::io::Result<()> {
         if let Ok(config_path) = get_config_path() {
             if config_path.exists() {
-                println!("[Info] Deleting config file at: {}", config_path.display());
-                std::fs::remove_file(config_path)?;
+                log_info!("Deleting config file at: {}", config_path.display());
+                std::fs::remove_file(&config_path)?;
+            }
+
+            // Otherwise load() would happily restore the old settings from the backup
+            let backup_path = config_path.with_extension("toml.bak");
+            if backup_path.exists() {
+                std::fs::remove_file(backup_path)?;
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_theme(theme: &str) -> String {
+        toml::to_string_pretty(&Config { theme: theme.to_string(), ..Config::default() }).unwrap()
+    }
+
+    #[test]
+    fn data_dir_override_prefers_the_env_var() {
+        let default_dir = tempfile::tempdir().unwrap();
+        fs::write(default_dir.path().join("config.toml"), "data_dir = \"/from/config\"\n").unwrap();
+
+        let resolved = resolve_data_dir_override(Some("/from/env".into()), Some(default_dir.path().to_path_buf()));
+        assert_eq!(resolved, Some(PathBuf::from("/from/env")));
+    }
+
+    #[test]
+    fn data_dir_override_falls_back_to_the_config() {
+        let default_dir = tempfile::tempdir().unwrap();
+        let default_path = Some(default_dir.path().to_path_buf());
+        assert_eq!(resolve_data_dir_override(None, default_path.clone()), None);
+
+        fs::write(default_dir.path().join("config.toml"), "data_dir = \"/from/config\"\n").unwrap();
+        assert_eq!(resolve_data_dir_override(None, default_path.clone()), Some(PathBuf::from("/from/config")));
+        // An empty env var doesn't count as set
+        assert_eq!(resolve_data_dir_override(Some("".into()), default_path), Some(PathBuf::from("/from/config")));
+    }
+
+    #[test]
+    fn unloaded_asset_selections_stay_saved() {
+        let mut config = Config {
+            background_selection: "Stars".to_string(),
+            bgm_track: Some("Theme/song.ogg".to_string()),
+            load_backgrounds: false,
+            load_music: false,
+            ..Config::default()
+        };
+        config.skip_unloaded_assets();
+        assert_eq!(config.active_background(), "Default");
+        assert_eq!(config.active_bgm_track(), "OFF");
+        let saved = config.saved_copy();
+        assert_eq!(saved.background_selection, "Stars");
+        assert_eq!(saved.bgm_track.as_deref(), Some("Theme/song.ogg"));
+
+        // Turning the category back on brings the old pick back
+        config.load_backgrounds = true;
+        config.skip_unloaded_assets();
+        assert_eq!(config.active_background(), "Stars");
+        assert_eq!(config.active_bgm_track(), "OFF");
+    }
+
+    #[test]
+    fn safe_mode_picks_are_session_only() {
+        let mut config = Config {
+            theme: "Neon".to_string(),
+            logo_selection: "Neon/logo.png".to_string(),
+            ..Config::default()
+        };
+        config.use_builtin_assets();
+        config.skip_unloaded_assets();
+        assert_eq!(config.theme, Config::default().theme);
+        assert_eq!(config.active_logo(), Config::default().logo_selection);
+
+        config.set("logo_selection", "Kazeta").unwrap();
+        let saved = config.saved_copy();
+        assert_eq!(saved.theme, "Neon");
+        assert_eq!(saved.logo_selection, "Neon/logo.png");
+    }
+
+    #[test]
+    fn native_resolution_replaces_the_default() {
+        let mut config = Config::default();
+        config.use_native_resolution(None);
+        assert_eq!(config.resolution, Config::default().resolution);
+
+        config.use_native_resolution(Some(("1280x800", "16:10")));
+        assert_eq!((config.resolution.as_str(), config.aspect_ratio.as_str()), ("1280x800", "16:10"));
+    }
+
+    #[test]
+    fn write_atomic_keeps_a_backup_of_the_last_good_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        write_atomic(&path, &config_with_theme("First")).unwrap();
+        assert!(!path.with_extension("toml.bak").exists());
+
+        write_atomic(&path, &config_with_theme("Second")).unwrap();
+        assert_eq!(Config::read_from(&path).unwrap().theme, "Second");
+        assert_eq!(Config::read_from(&path.with_extension("toml.bak")).unwrap().theme, "First");
+        assert!(!path.with_extension("toml.tmp").exists());
+    }
+
+    #[test]
+    fn load_falls_back_to_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        write_atomic(&path, &config_with_theme("First")).unwrap();
+        write_atomic(&path, &config_with_theme("Second")).unwrap();
+
+        // Truncated mid-write
+        let contents = fs::read_to_string(&path).unwrap();
+        let cut = contents.find("\"Second").unwrap() + 4;
+        fs::write(&path, &contents[..cut]).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().theme, "First");
+
+        // Garbage
+        fs::write(&path, [0xff, 0x00, 0x13]).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().theme, "First");
+
+        // Saving over a corrupt config doesn't back it up over the last good one
+        write_atomic(&path, &config_with_theme("Third")).unwrap();
+        assert_eq!(Config::read_from(&path.with_extension("toml.bak")).unwrap().theme, "First");
+    }
+
+    #[test]
+    fn enum_settings_round_trip_through_their_labels() {
+        let mut config = Config::default();
+        for position in MenuPosition::ALL {
+            config.set("menu_position", position.label()).unwrap();
+            assert_eq!(config.menu_position(), position);
+        }
+        for view in GameSelectionView::ALL {
+            config.set("game_selection_view", view.label()).unwrap();
+            assert_eq!(config.game_selection_view(), view);
+        }
+        config.set("launch_mode", LaunchMode::InPlace.label()).unwrap();
+        assert_eq!(config.launch_mode(), LaunchMode::InPlace);
+
+        assert!(config.set("menu_position", "TopLeft").is_err());
+        assert!(config.set("game_selection_view", "Grid").is_err());
+    }
+}
+
@@ -0,0 +1,152 @@
+use chrono::Local;
+use macroquad::prelude::*;
+use std::{
+    backtrace::Backtrace,
+    fs,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{config::get_user_data_dir, logger::get_log_dir, BUILD_COMMIT, VERSION_NUMBER};
+
+// Drop this file into the data directory to boot once without custom assets
+const SAFE_MODE_FILE: &str = "safe_mode";
+// Exists from the start of asset loading until the main loop is reached
+const BOOT_MARKER_FILE: &str = ".booting";
+
+// Set while the last-gasp screen is up, so a panic while drawing it doesn't loop back into it
+static SHOWING_CRASH_SCREEN: AtomicBool = AtomicBool::new(false);
+// Guards against the hook itself panicking (e.g. while writing the report)
+static IN_PANIC_HOOK: AtomicBool = AtomicBool::new(false);
+// Short summary of the most recent panic and where the full report went
+static LAST_CRASH: Mutex<Option<(String, Option<String>)>> = Mutex::new(None);
+
+/// Installs a panic hook that writes the panic message and a full backtrace
+/// to <data dir>/logs/crash_<timestamp>.log
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        if SHOWING_CRASH_SCREEN.load(Ordering::SeqCst) || IN_PANIC_HOOK.swap(true, Ordering::SeqCst) {
+            eprintln!("[ERROR] Panic while handling a previous crash: {}", info);
+            std::process::exit(1);
+        }
+
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic".to_string()
+        };
+        let location = info.location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown location".to_string());
+        let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+
+        let report = format!(
+            "Kazeta+ BIOS {} (build {}) crashed at {}\nThread: {}\nLocation: {}\nMessage: {}\n\nBacktrace:\n{}\n",
+            VERSION_NUMBER,
+            BUILD_COMMIT,
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            thread,
+            location,
+            message,
+            Backtrace::force_capture(),
+        );
+        eprintln!("{}", report);
+
+        let report_path = get_log_dir().and_then(|dir| {
+            fs::create_dir_all(&dir).ok()?;
+            let path = dir.join(format!("crash_{}.log", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+            fs::write(&path, &report).ok()?;
+            Some(path.display().to_string())
+        });
+
+        if let Some(path) = &report_path {
+            eprintln!("[ERROR] Crash report written to {}", path);
+        }
+
+        if let Ok(mut last) = LAST_CRASH.lock() {
+            *last = Some((format!("{} ({})", message, location), report_path));
+        }
+
+        IN_PANIC_HOOK.store(false, Ordering::SeqCst);
+    }));
+}
+
+/// Why this boot should skip custom backgrounds, logos, fonts, music and themes, if it should:
+/// `--safe-mode` on the command line, a `safe_mode` file in the data directory (used up by
+/// this boot), or a previous boot that never got past loading assets.
+pub fn safe_mode_reason() -> Option<&'static str> {
+    if std::env::args().skip(1).any(|arg| arg == "--safe-mode") {
+        return Some("--safe-mode was passed");
+    }
+    let data_dir = get_user_data_dir()?;
+    let request = data_dir.join(SAFE_MODE_FILE);
+    if request.exists() {
+        if let Err(e) = fs::remove_file(&request) {
+            log_warn!("Could not remove {}: {}", request.display(), e);
+        }
+        return Some("a safe_mode file was found");
+    }
+    if data_dir.join(BOOT_MARKER_FILE).exists() {
+        return Some("the last boot did not finish loading");
+    }
+    None
+}
+
+/// Call before loading assets; if the BIOS dies before boot_finished(), the next boot is a safe one.
+pub fn boot_started() {
+    if let Some(dir) = get_user_data_dir() {
+        if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(BOOT_MARKER_FILE), "")) {
+            log_warn!("Could not write the boot marker: {}", e);
+        }
+    }
+}
+
+pub fn boot_finished() {
+    if let Some(dir) = get_user_data_dir() {
+        let _ = fs::remove_file(dir.join(BOOT_MARKER_FILE));
+    }
+}
+
+/// Last-gasp error screen shown after the main loop panics.
+/// Uses only macroquad's built-in font so it doesn't depend on any loaded assets.
+pub async fn show_crash_screen() {
+    SHOWING_CRASH_SCREEN.store(true, Ordering::SeqCst);
+
+    let (summary, report_path) = LAST_CRASH.lock().ok()
+        .and_then(|mut last| last.take())
+        .unwrap_or_else(|| ("Unknown error".to_string(), None));
+
+    let mut lines = vec![
+        "THE BIOS HAS CRASHED".to_string(),
+        String::new(),
+    ];
+    // Break the summary up so long messages don't run off screen
+    for chunk in summary.chars().collect::<Vec<_>>().chunks(60) {
+        lines.push(chunk.iter().collect());
+    }
+    lines.push(String::new());
+    match &report_path {
+        Some(path) => {
+            lines.push("A crash report was saved to:".to_string());
+            lines.push(path.clone());
+        }
+        None => lines.push("The crash report could not be saved.".to_string()),
+    }
+    lines.push(String::new());
+    lines.push("Press ENTER or wait to exit.".to_string());
+
+    let start = get_time();
+    while get_time() - start < 15.0 && !is_key_pressed(KeyCode::Enter) {
+        clear_background(Color::new(0.3, 0.0, 0.0, 1.0));
+        let font_size = (screen_height() / 20.0).max(12.0);
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(line, 20.0, 40.0 + i as f32 * font_size, font_size, WHITE);
+        }
+        next_frame().await;
+    }
+}
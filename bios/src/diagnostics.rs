@@ -0,0 +1,87 @@
+use chrono::Local;
+use std::{fmt::Write as _, fs, path::PathBuf};
+use tar::{Builder, Header};
+
+use crate::{
+    config::get_config_path,
+    logger::get_log_dir,
+    save,
+    system::{check_prerequisites, get_available_sinks, get_battery_info, get_system_info},
+    VERSION_NUMBER,
+};
+
+/// Plain-text summary of the system, storage, audio and prerequisite checks
+fn build_report() -> String {
+    let mut report = String::new();
+    let info = get_system_info();
+
+    let _ = writeln!(report, "Kazeta+ BIOS {} diagnostics", VERSION_NUMBER);
+    let _ = writeln!(report, "Generated: {}", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    let _ = writeln!(report, "\n[System]");
+    let _ = writeln!(report, "OS: {}", info.os_name);
+    let _ = writeln!(report, "Kernel: {}", info.kernel);
+    let _ = writeln!(report, "CPU: {}", info.cpu);
+    let _ = writeln!(report, "GPU: {}", info.gpu);
+    let _ = writeln!(report, "Memory: {}", info.ram_total);
+    if let Some(battery) = get_battery_info() {
+        let _ = writeln!(report, "Battery: {}% ({})", battery.percentage, battery.status);
+    }
+
+    let _ = writeln!(report, "\n[Storage]");
+    match save::list_devices() {
+        Ok(devices) => {
+            for (name, free_mb, read_only) in devices {
+                let _ = writeln!(report, "{}: {} MB free{}", name, free_mb, if read_only { " (read-only)" } else { "" });
+            }
+        }
+        Err(e) => { let _ = writeln!(report, "Could not list storage: {}", e); }
+    }
+
+    let _ = writeln!(report, "\n[Audio sinks]");
+    for sink in get_available_sinks() {
+        let _ = writeln!(report, "{}: {}", sink.id, sink.name);
+    }
+
+    let _ = writeln!(report, "\n[Prerequisites]");
+    let missing = check_prerequisites();
+    if missing.is_empty() {
+        let _ = writeln!(report, "All found");
+    }
+    for dep in missing {
+        let _ = writeln!(report, "MISSING {} ({})", dep.name, dep.feature);
+    }
+
+    report
+}
+
+/// Bundles the report, the config and the session/crash logs into
+/// kazeta_diagnostics_<timestamp>.tar on the first writable USB drive.
+pub fn export() -> Result<PathBuf, String> {
+    let drive = save::writable_external_drives().into_iter().next()
+        .ok_or("NO WRITABLE USB DRIVE FOUND")?;
+    let path = drive.join(format!("kazeta_diagnostics_{}.tar", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+
+    let file = fs::File::create(&path).map_err(|e| e.to_string())?;
+    let mut builder = Builder::new(file);
+
+    let report = build_report();
+    let mut header = Header::new_gnu();
+    header.set_size(report.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Local::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, "report.txt", report.as_bytes()).map_err(|e| e.to_string())?;
+
+    if let Ok(config_path) = get_config_path() {
+        if config_path.exists() {
+            builder.append_path_with_name(&config_path, "config.toml").map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Some(log_dir) = get_log_dir().filter(|dir| dir.is_dir()) {
+        builder.append_dir_all("logs", &log_dir).map_err(|e| e.to_string())?;
+    }
+
+    builder.finish().map_err(|e| e.to_string())?;
+    Ok(path)
+}
@@ -1,24 +1,397 @@
 use macroquad::prelude::*;
-use gilrs::{Gilrs, Button, Axis};
+use gilrs::{Gilrs, Gamepad, GamepadId, Button, Axis};
+use serde::{Serialize, Deserialize};
+use std::{collections::{hash_map::Entry, HashMap, HashSet}, fs, path::PathBuf, time::{Duration, SystemTime}};
+use crate::config::{Config, get_user_data_dir};
 use crate::types::UIFocus; // Assuming UIFocus is in types.rs
 
+/// Stick deadzone for one controller, per axis
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct AxisDeadzone {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Default for AxisDeadzone {
+    fn default() -> Self {
+        AxisDeadzone { x: InputState::ANALOG_DEADZONE, y: InputState::ANALOG_DEADZONE }
+    }
+}
+
+/// Per-controller deadzones live in <data dir>/controller_deadzones.json, keyed by gamepad UUID.
+/// Every controller that connects gets an entry with the default values, so a drifting pad
+/// can be tuned by editing its entry without affecting the others.
+fn deadzones_path() -> Option<PathBuf> {
+    get_user_data_dir().map(|dir| dir.join("controller_deadzones.json"))
+}
+
+fn load_deadzones() -> HashMap<String, AxisDeadzone> {
+    deadzones_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_deadzones(deadzones: &HashMap<String, AxisDeadzone>) {
+    let Some(path) = deadzones_path() else { return };
+    let result = serde_json::to_string_pretty(deadzones)
+        .map_err(std::io::Error::other)
+        .and_then(|json| {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, json)
+        });
+    if let Err(e) = result {
+        log_warn!("Could not save controller deadzones to {}: {}", path.display(), e);
+    }
+}
+
+/// Key a controller is stored under: its UUID, which stays the same across reconnects
+fn controller_key(gamepad: &Gamepad) -> String {
+    gamepad.uuid().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The BIOS's own navigation actions, each bound to a controller button and a key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+    Secondary,
+    Next,
+    Prev,
+    Cycle,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::Up, Action::Down, Action::Left, Action::Right, Action::Select,
+        Action::Back, Action::Secondary, Action::Next, Action::Prev, Action::Cycle,
+    ];
+
+    /// Name on the button mapping screen and in config.toml
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Up => "UP",
+            Action::Down => "DOWN",
+            Action::Left => "LEFT",
+            Action::Right => "RIGHT",
+            Action::Select => "SELECT",
+            Action::Back => "BACK",
+            Action::Secondary => "SECONDARY",
+            Action::Next => "NEXT",
+            Action::Prev => "PREV",
+            Action::Cycle => "CYCLE",
+        }
+    }
+
+    fn default_button(self) -> Option<Button> {
+        match self {
+            Action::Up => Some(Button::DPadUp),
+            Action::Down => Some(Button::DPadDown),
+            Action::Left => Some(Button::DPadLeft),
+            Action::Right => Some(Button::DPadRight),
+            Action::Select => Some(Button::South),
+            Action::Back => Some(Button::East),
+            Action::Secondary => Some(Button::West),
+            Action::Next => Some(Button::RightTrigger),
+            Action::Prev => Some(Button::LeftTrigger),
+            Action::Cycle => None,
+        }
+    }
+
+    fn default_key(self) -> Option<KeyCode> {
+        Some(match self {
+            Action::Up => KeyCode::Up,
+            Action::Down => KeyCode::Down,
+            Action::Left => KeyCode::Left,
+            Action::Right => KeyCode::Right,
+            Action::Select => KeyCode::Enter,
+            Action::Back => KeyCode::Backspace,
+            Action::Secondary => KeyCode::X,
+            Action::Next => KeyCode::RightBracket,
+            Action::Prev => KeyCode::LeftBracket,
+            Action::Cycle => KeyCode::Tab,
+        })
+    }
+
+    /// SELECT and BACK: without them the menus (button mapping included) can't be used
+    fn is_essential(self) -> bool {
+        matches!(self, Action::Select | Action::Back)
+    }
+
+    fn index(self) -> usize {
+        Action::ALL.iter().position(|&a| a == self).unwrap_or(0)
+    }
+}
+
+/// Buttons the mapping screen can bind
+const BINDABLE_BUTTONS: &[Button] = &[
+    Button::South, Button::East, Button::North, Button::West, Button::C, Button::Z,
+    Button::LeftTrigger, Button::LeftTrigger2, Button::RightTrigger, Button::RightTrigger2,
+    Button::Select, Button::Start, Button::Mode, Button::LeftThumb, Button::RightThumb,
+    Button::DPadUp, Button::DPadDown, Button::DPadLeft, Button::DPadRight,
+];
+
+/// Declares the keys the mapping screen can bind. KeyCode has no serde support, so this makes a
+/// serde-named mirror of them for config.toml.
+macro_rules! bindable_keys {
+    ($($key:ident),* $(,)?) => {
+        #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+        enum BindableKey { $($key),* }
+
+        impl BindableKey {
+            const ALL: &[BindableKey] = &[$(BindableKey::$key),*];
+
+            fn code(self) -> KeyCode {
+                match self { $(BindableKey::$key => KeyCode::$key),* }
+            }
+        }
+    };
+}
+
+// F12 stays the screenshot key
+bindable_keys! {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+    Up, Down, Left, Right,
+    Enter, Backspace, Tab, Space, Escape,
+    LeftBracket, RightBracket, Comma, Period, Slash,
+    Semicolon, Apostrophe, Minus, Equal, Backslash,
+    Home, End, PageUp, PageDown, Insert, Delete,
+    LeftShift, RightShift, LeftControl, RightControl, LeftAlt, RightAlt,
+    Kp0, Kp1, Kp2, Kp3, Kp4, Kp5, Kp6, Kp7, Kp8, Kp9, KpEnter,
+}
+
+impl BindableKey {
+    fn from_code(code: KeyCode) -> Option<Self> {
+        BindableKey::ALL.iter().copied().find(|key| key.code() == code)
+    }
+}
+
+/// Value stored for an action left without a button or key
+const UNBOUND: &str = "NONE";
+
+/// The name serde gives a button or key, as stored in config.toml
+fn binding_name<T: Serialize>(value: Option<T>) -> String {
+    value.and_then(|value| toml::Value::try_from(value).ok())
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| UNBOUND.to_string())
+}
+
+fn parse_button(name: &str) -> Option<Button> {
+    toml::Value::String(name.to_string()).try_into().ok().filter(|button| BINDABLE_BUTTONS.contains(button))
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    toml::Value::String(name.to_string()).try_into().ok().map(BindableKey::code)
+}
+
+/// A button as the help bar names it ([SOUTH], [LB/RB])
+pub fn button_label(button: Option<Button>) -> String {
+    match button {
+        None => "-".to_string(),
+        Some(Button::LeftTrigger) => "LB".to_string(),
+        Some(Button::RightTrigger) => "RB".to_string(),
+        Some(Button::LeftTrigger2) => "LT".to_string(),
+        Some(Button::RightTrigger2) => "RT".to_string(),
+        Some(button) => binding_name(Some(button)).replace("DPad", "D-PAD ").to_uppercase(),
+    }
+}
+
+pub fn key_label(key: Option<KeyCode>) -> String {
+    match key {
+        None => "-".to_string(),
+        Some(key) => binding_name(BindableKey::from_code(key)).trim_start_matches("Key").to_uppercase(),
+    }
+}
+
+/// The navigation bindings in effect: config.toml's overrides on top of the defaults
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bindings {
+    buttons: [Option<Button>; 10],
+    keys: [Option<KeyCode>; 10],
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings {
+            buttons: Action::ALL.map(Action::default_button),
+            keys: Action::ALL.map(Action::default_key),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn from_config(config: &Config) -> Self {
+        let mut bindings = Bindings::default();
+        for action in Action::ALL {
+            let i = action.index();
+            if let Some(name) = config.button_bindings.get(action.name()) {
+                match parse_button(name) {
+                    Some(button) => bindings.buttons[i] = Some(button),
+                    None if name == UNBOUND => bindings.buttons[i] = None,
+                    None => log_warn!("Unknown button '{}' for {}, keeping the default.", name, action.name()),
+                }
+            }
+            if let Some(name) = config.key_bindings.get(action.name()) {
+                match parse_key(name) {
+                    Some(key) => bindings.keys[i] = Some(key),
+                    None if name == UNBOUND => bindings.keys[i] = None,
+                    None => log_warn!("Unknown key '{}' for {}, keeping the default.", name, action.name()),
+                }
+            }
+        }
+        bindings
+    }
+
+    pub fn button(&self, action: Action) -> Option<Button> {
+        self.buttons[action.index()]
+    }
+
+    pub fn key(&self, action: Action) -> Option<KeyCode> {
+        self.keys[action.index()]
+    }
+
+    fn action_for_button(&self, button: Button) -> Option<Action> {
+        Action::ALL.into_iter().find(|&action| self.button(action) == Some(button))
+    }
+}
+
+/// What an action displaced by a rebind ends up on: the rebound action's old binding, or failing
+/// that its own default. None when both are missing or taken by the new binding.
+fn displaced_binding<T: PartialEq>(old: Option<T>, default: Option<T>, new: &T) -> Option<T> {
+    old.filter(|old| old != new).or(default.filter(|default| default != new))
+}
+
+/// Binds a button to an action in the config. If another action had that button, it takes
+/// this action's old one (or its own default when there was none). A rebind that would leave
+/// SELECT or BACK without a button is refused, so a controller can't be locked out of the menus.
+pub fn bind_button(config: &mut Config, action: Action, button: Button) -> bool {
+    if !BINDABLE_BUTTONS.contains(&button) {
+        return false;
+    }
+    let bindings = Bindings::from_config(config);
+    if let Some(other) = Action::ALL.into_iter().find(|&a| a != action && bindings.button(a) == Some(button)) {
+        let replacement = displaced_binding(bindings.button(action), other.default_button(), &button);
+        if replacement.is_none() && other.is_essential() {
+            return false;
+        }
+        config.button_bindings.insert(other.name().to_string(), binding_name(replacement));
+    }
+    config.button_bindings.insert(action.name().to_string(), binding_name(Some(button)));
+    config.mark_dirty();
+    true
+}
+
+/// bind_button() for keys
+pub fn bind_key(config: &mut Config, action: Action, key: KeyCode) -> bool {
+    let Some(bindable) = BindableKey::from_code(key) else {
+        return false;
+    };
+    let bindings = Bindings::from_config(config);
+    if let Some(other) = Action::ALL.into_iter().find(|&a| a != action && bindings.key(a) == Some(key)) {
+        let replacement = displaced_binding(bindings.key(action), other.default_key(), &key);
+        if replacement.is_none() && other.is_essential() {
+            return false;
+        }
+        config.key_bindings.insert(other.name().to_string(), binding_name(replacement.and_then(BindableKey::from_code)));
+    }
+    config.key_bindings.insert(action.name().to_string(), binding_name(Some(bindable)));
+    config.mark_dirty();
+    true
+}
+
+/// Puts every action back on its default button and key
+pub fn reset_bindings(config: &mut Config) {
+    if !config.button_bindings.is_empty() || !config.key_bindings.is_empty() {
+        config.button_bindings.clear();
+        config.key_bindings.clear();
+        config.mark_dirty();
+    }
+}
+
+/// Remembers when each input was last accepted, so repeats arriving inside `window` can be dropped.
+/// Times are offsets from any fixed origin; the keys are whatever identifies a press.
+pub struct PressDebounce<K> {
+    window: Duration,
+    last_press: HashMap<K, Duration>,
+}
+
+impl<K: Eq + std::hash::Hash> PressDebounce<K> {
+    pub fn new(window: Duration) -> Self {
+        PressDebounce { window, last_press: HashMap::new() }
+    }
+
+    /// Whether a press of `key` at `at` repeats one accepted less than `window` earlier.
+    /// Records the press otherwise.
+    pub fn is_duplicate(&mut self, key: K, at: Duration) -> bool {
+        if self.last_press.get(&key).is_some_and(|last| at.saturating_sub(*last) < self.window) {
+            return true;
+        }
+        self.last_press.insert(key, at);
+        false
+    }
+
+    /// Whether any recorded press matching `filter` was accepted less than `window` before `at`
+    pub fn pressed_within(&self, at: Duration, mut filter: impl FnMut(&K) -> bool) -> bool {
+        self.last_press.iter().any(|(key, last)| filter(key) && at.saturating_sub(*last) < self.window)
+    }
+
+    /// Drops every recorded press not matching `keep`
+    pub fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        self.last_press.retain(|key, _| keep(key));
+    }
+}
+
+/// A gilrs event time as an offset usable by `PressDebounce`
+fn since_epoch(time: SystemTime) -> Duration {
+    time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default()
+}
+
+fn is_dpad(button: Button) -> bool {
+    matches!(button, Button::DPadUp | Button::DPadDown | Button::DPadLeft | Button::DPadRight)
+}
+
 pub struct InputState {
     pub up: bool,
     pub down: bool,
     pub left: bool,
     pub right: bool,
     pub select: bool,
+    pub select_held: bool, // confirm button is down this frame (for hold-to-confirm)
+    pub back_held: bool, // back button is down this frame (for hold-back-to-menu)
     pub next: bool,
     pub prev: bool,
     pub cycle: bool,
     pub back: bool,
     pub secondary: bool,
+    pub screenshot: bool, // F12, or SELECT + START on a controller
+    /// The raw button / key pressed this frame, for the button mapping screen
+    pub last_button: Option<Button>,
+    pub last_key: Option<KeyCode>,
+    bindings: Bindings,
     pub analog_was_neutral: bool,
     pub ui_focus: UIFocus,
+    deadzones: HashMap<String, AxisDeadzone>,
+    /// "Player 1": the first pad to press a button owns navigation until it disconnects
+    pub active_gamepad: Option<GamepadId>,
+    /// When each pad's buttons were last accepted, for dropping the duplicate presses some pads send
+    last_press: PressDebounce<(usize, Button)>,
+    /// Set for one frame when a controller is plugged in or unplugged, for the flash message
+    pub controller_toast: Option<&'static str>,
+    /// Pads already connected at boot; gilrs reports them as new connections, which needs no toast
+    startup_gamepads: Option<HashSet<GamepadId>>,
 }
 
 impl InputState {
     const ANALOG_DEADZONE: f32 = 0.5;  // Increased deadzone for less sensitivity
+    /// A second press of the same button within this window is treated as a duplicate event
+    const BUTTON_DEBOUNCE: Duration = Duration::from_millis(60);
 
     pub fn new() -> Self {
         InputState {
@@ -27,13 +400,24 @@ impl InputState {
             left: false,
             right: false,
             select: false,
+            select_held: false,
+            back_held: false,
             next: false,
             prev: false,
             cycle: false,
             back: false,
             secondary: false,
+            screenshot: false,
+            last_button: None,
+            last_key: None,
+            bindings: Bindings::default(),
             analog_was_neutral: true,
             ui_focus: UIFocus::Grid,
+            deadzones: load_deadzones(),
+            active_gamepad: None,
+            last_press: PressDebounce::new(Self::BUTTON_DEBOUNCE),
+            controller_toast: None,
+            startup_gamepads: None,
         }
     }
 
@@ -48,51 +432,149 @@ impl InputState {
         self.cycle = false;
         self.back = false;
         self.secondary = false;
+        self.screenshot = false;
+        self.last_button = None;
+        self.last_key = None;
+        self.controller_toast = None;
         // Note: We do NOT reset analog_was_neutral or ui_focus
     }
 
+    /// Picks up the navigation bindings from the config (call again after they change)
+    pub fn apply_bindings(&mut self, config: &Config) {
+        self.bindings = Bindings::from_config(config);
+    }
+
+    /// The flag an action sets when pressed
+    fn action_flag(&mut self, action: Action) -> &mut bool {
+        match action {
+            Action::Up => &mut self.up,
+            Action::Down => &mut self.down,
+            Action::Left => &mut self.left,
+            Action::Right => &mut self.right,
+            Action::Select => &mut self.select,
+            Action::Back => &mut self.back,
+            Action::Secondary => &mut self.secondary,
+            Action::Next => &mut self.next,
+            Action::Prev => &mut self.prev,
+            Action::Cycle => &mut self.cycle,
+        }
+    }
+
     pub fn update_keyboard(&mut self) {
-        self.up = is_key_pressed(KeyCode::Up);
-        self.down = is_key_pressed(KeyCode::Down);
-        self.left = is_key_pressed(KeyCode::Left);
-        self.right = is_key_pressed(KeyCode::Right);
-        self.select = is_key_pressed(KeyCode::Enter);
-        self.next = is_key_pressed(KeyCode::RightBracket);
-        self.prev = is_key_pressed(KeyCode::LeftBracket);
-        self.back = is_key_pressed(KeyCode::Backspace);
-        self.secondary = is_key_pressed(KeyCode::X);
-        self.cycle = is_key_pressed(KeyCode::Tab);
-    }
-
-    pub fn update_controller(&mut self, gilrs: &mut Gilrs) {
+        for action in Action::ALL {
+            *self.action_flag(action) = self.bindings.key(action).is_some_and(is_key_pressed);
+        }
+        self.select_held = self.bindings.key(Action::Select).is_some_and(is_key_down);
+        self.back_held = self.bindings.key(Action::Back).is_some_and(is_key_down);
+        self.screenshot = is_key_pressed(KeyCode::F12);
+        self.last_key = get_last_key_pressed();
+    }
+
+    /// The deadzone to use for a controller (its own calibration, or the default)
+    fn deadzone_for(&self, gamepad: &Gamepad) -> AxisDeadzone {
+        self.deadzones.get(&controller_key(gamepad)).copied().unwrap_or_default()
+    }
+
+    /// Whether input from this pad should drive the UI. With `any_controller` off, the first pad
+    /// to press a button becomes player 1 and other pads are ignored so they can't fight the cursor.
+    fn accepts_input_from(&mut self, id: GamepadId, any_controller: bool) -> bool {
+        if any_controller {
+            return true;
+        }
+        match self.active_gamepad {
+            Some(active) => active == id,
+            None => {
+                log_info!("Controller {} is now player 1", id);
+                self.active_gamepad = Some(id);
+                true
+            }
+        }
+    }
+
+    pub fn update_controller(&mut self, gilrs: &mut Gilrs, any_controller: bool) {
+        let mut startup_gamepads = self.startup_gamepads.take()
+            .unwrap_or_else(|| gilrs.gamepads().map(|(id, _)| id).collect());
+
         // Handle button events
         while let Some(ev) = gilrs.next_event() {
+            if let gilrs::EventType::ButtonPressed(button, _) = ev.event {
+                if !self.accepts_input_from(ev.id, any_controller) || self.last_press.is_duplicate((ev.id.into(), button), since_epoch(ev.time)) {
+                    continue;
+                }
+            }
+
             match ev.event {
-                gilrs::EventType::ButtonPressed(Button::DPadUp, _) => self.up = true,
-                gilrs::EventType::ButtonPressed(Button::DPadDown, _) => self.down = true,
-                gilrs::EventType::ButtonPressed(Button::DPadLeft, _) => self.left = true,
-                gilrs::EventType::ButtonPressed(Button::DPadRight, _) => self.right = true,
-                gilrs::EventType::ButtonPressed(Button::South, _) => self.select = true,
-                gilrs::EventType::ButtonPressed(Button::East, _) => self.back = true,
-                gilrs::EventType::ButtonPressed(Button::West, _) => self.secondary = true,
-                gilrs::EventType::ButtonPressed(Button::RightTrigger, _) => self.next = true,
-                gilrs::EventType::ButtonPressed(Button::LeftTrigger, _) => self.prev = true,
+                gilrs::EventType::Disconnected => {
+                    log_info!("Controller {} disconnected", ev.id);
+                    self.controller_toast = Some("CONTROLLER DISCONNECTED");
+                    self.last_press.retain(|(id, _)| *id != usize::from(ev.id));
+                    if self.active_gamepad == Some(ev.id) {
+                        // Let whichever pad presses a button next take over
+                        log_info!("Player 1 controller disconnected");
+                        self.active_gamepad = None;
+                    }
+                }
+                gilrs::EventType::Connected => {
+                    if !startup_gamepads.remove(&ev.id) {
+                        self.controller_toast = Some("CONTROLLER CONNECTED");
+                    }
+
+                    // Give newly seen controllers an entry so their deadzone can be tuned
+                    let gamepad = gilrs.gamepad(ev.id);
+                    let key = controller_key(&gamepad);
+                    log_info!("Controller connected: {} ({})", gamepad.name(), key);
+                    if let Entry::Vacant(entry) = self.deadzones.entry(key) {
+                        entry.insert(AxisDeadzone::default());
+                        save_deadzones(&self.deadzones);
+                    }
+                }
+                // SELECT + START chord, in either order
+                gilrs::EventType::ButtonPressed(Button::Start, _) if gilrs.gamepad(ev.id).is_pressed(Button::Select) => self.screenshot = true,
+                gilrs::EventType::ButtonPressed(Button::Select, _) if gilrs.gamepad(ev.id).is_pressed(Button::Start) => self.screenshot = true,
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    self.last_button = Some(button);
+                    if let Some(action) = self.bindings.action_for_button(button) {
+                        *self.action_flag(action) = true;
+                    }
+                }
                 _ => {}
             }
         }
 
+        self.startup_gamepads = Some(startup_gamepads);
+
+        // A direction the d-pad gave this frame, or moments ago, shouldn't also come from the stick
+        // (some pads report one physical press as both)
+        let now = since_epoch(SystemTime::now());
+        let dpad_fired = self.up || self.down || self.left || self.right
+            || self.last_press.pressed_within(now, |(_, button)| is_dpad(*button));
+
+        // Hold state of the confirm and back buttons, from player 1 (or any pad)
+        let active_gamepad = if any_controller { None } else { self.active_gamepad };
+        let (select_button, back_button) = (self.bindings.button(Action::Select), self.bindings.button(Action::Back));
+        self.select_held |= gilrs.gamepads()
+            .filter(|(id, _)| active_gamepad.is_none_or(|active| active == *id))
+            .any(|(_, gamepad)| select_button.is_some_and(|button| gamepad.is_pressed(button)));
+        self.back_held |= gilrs.gamepads()
+            .filter(|(id, _)| active_gamepad.is_none_or(|active| active == *id))
+            .any(|(_, gamepad)| back_button.is_some_and(|button| gamepad.is_pressed(button)));
+
         // --- Handle analog stick input (New, correct logic) ---
 
         let mut any_stick_active = false;
         let was_neutral = self.analog_was_neutral;
 
-        // Iterate through all gamepads to find the first active one
-        for (_, gamepad) in gilrs.gamepads() {
+        // Iterate through all gamepads to find the first active one (only player 1's, if there is one)
+        for (id, gamepad) in gilrs.gamepads() {
+            if active_gamepad.is_some_and(|active| active != id) {
+                continue;
+            }
             let raw_x = gamepad.value(Axis::LeftStickX);
             let raw_y = gamepad.value(Axis::LeftStickY);
+            let deadzone = self.deadzone_for(&gamepad);
 
-            let is_currently_neutral = raw_x.abs() < Self::ANALOG_DEADZONE &&
-            raw_y.abs() < Self::ANALOG_DEADZONE;
+            let is_currently_neutral = raw_x.abs() < deadzone.x &&
+            raw_y.abs() < deadzone.y;
 
             // Is this stick active?
             if !is_currently_neutral {
@@ -100,21 +582,21 @@ impl InputState {
                 any_stick_active = true;
 
                 // Was the system neutral before this frame?
-                if was_neutral {
+                if was_neutral && !dpad_fired {
                     // Yes. This is a "just pushed" event. Fire it.
                     // Prioritize dominant axis
                     if raw_y.abs() > raw_x.abs() {
                         // Vertical is stronger
-                        if raw_y > -Self::ANALOG_DEADZONE {       // -Y is UP
+                        if raw_y > -deadzone.y {       // -Y is UP
                             self.up = true;
-                        } else if raw_y < Self::ANALOG_DEADZONE { // +Y is DOWN
+                        } else if raw_y < deadzone.y { // +Y is DOWN
                             self.down = true;
                         }
                     } else {
                         // Horizontal is stronger
-                        if raw_x < -Self::ANALOG_DEADZONE {       // -X is LEFT
+                        if raw_x < -deadzone.x {       // -X is LEFT
                             self.left = true;
-                        } else if raw_x > Self::ANALOG_DEADZONE { // +X is RIGHT
+                        } else if raw_x > deadzone.x { // +X is RIGHT
                             self.right = true;
                         }
                     }
@@ -133,3 +615,105 @@ impl InputState {
         self.analog_was_neutral = !any_stick_active;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAD: usize = 0;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn collapses_presses_inside_the_window() {
+        let mut debounce = PressDebounce::new(InputState::BUTTON_DEBOUNCE);
+        assert!(!debounce.is_duplicate((PAD, Button::South), ms(1000)));
+        assert!(debounce.is_duplicate((PAD, Button::South), ms(1030)));
+        // Another button or another pad is its own press
+        assert!(!debounce.is_duplicate((PAD, Button::East), ms(1030)));
+        assert!(!debounce.is_duplicate((PAD + 1, Button::South), ms(1030)));
+    }
+
+    #[test]
+    fn passes_presses_after_the_window() {
+        let mut debounce = PressDebounce::new(InputState::BUTTON_DEBOUNCE);
+        assert!(!debounce.is_duplicate((PAD, Button::South), ms(1000)));
+        assert!(!debounce.is_duplicate((PAD, Button::South), ms(1061)));
+        // The window restarts from the last accepted press
+        assert!(debounce.is_duplicate((PAD, Button::South), ms(1100)));
+    }
+
+    #[test]
+    fn suppresses_the_stick_after_a_dpad_press() {
+        let mut debounce = PressDebounce::new(InputState::BUTTON_DEBOUNCE);
+        assert!(!debounce.pressed_within(ms(1000), |(_, button)| is_dpad(*button)));
+        debounce.is_duplicate((PAD, Button::South), ms(1000));
+        assert!(!debounce.pressed_within(ms(1010), |(_, button)| is_dpad(*button)));
+
+        debounce.is_duplicate((PAD, Button::DPadUp), ms(1000));
+        assert!(debounce.pressed_within(ms(1040), |(_, button)| is_dpad(*button)));
+        assert!(!debounce.pressed_within(ms(1100), |(_, button)| is_dpad(*button)));
+    }
+
+    #[test]
+    fn forgets_a_disconnected_pad() {
+        let mut debounce = PressDebounce::new(InputState::BUTTON_DEBOUNCE);
+        debounce.is_duplicate((PAD, Button::South), ms(1000));
+        debounce.retain(|(id, _)| *id != PAD);
+        assert!(!debounce.is_duplicate((PAD, Button::South), ms(1010)));
+    }
+
+    #[test]
+    fn stores_bindings_by_serde_name() {
+        let mut config = Config::default();
+        assert!(bind_button(&mut config, Action::Select, Button::East));
+        assert!(bind_key(&mut config, Action::Select, KeyCode::Space));
+        assert!(!bind_key(&mut config, Action::Select, KeyCode::F12));
+
+        // BACK had East, so it takes SELECT's old button
+        assert_eq!(config.button_bindings["SELECT"], "East");
+        assert_eq!(config.button_bindings["BACK"], "South");
+        assert_eq!(config.key_bindings["SELECT"], "Space");
+
+        let bindings = Bindings::from_config(&config);
+        assert_eq!(bindings.button(Action::Select), Some(Button::East));
+        assert_eq!(bindings.button(Action::Back), Some(Button::South));
+        assert_eq!(bindings.key(Action::Select), Some(KeyCode::Space));
+        assert_eq!(button_label(bindings.button(Action::Select)), "EAST");
+        assert_eq!(key_label(Some(KeyCode::Key5)), "5");
+    }
+
+    #[test]
+    fn reads_unbound_and_unknown_bindings() {
+        let mut config = Config::default();
+        config.button_bindings.insert("CYCLE".to_string(), "North".to_string());
+        config.button_bindings.insert("SECONDARY".to_string(), UNBOUND.to_string());
+        config.key_bindings.insert("SELECT".to_string(), "NotAKey".to_string());
+
+        let bindings = Bindings::from_config(&config);
+        assert_eq!(bindings.button(Action::Cycle), Some(Button::North));
+        assert_eq!(bindings.button(Action::Secondary), None);
+        assert_eq!(bindings.key(Action::Select), Some(KeyCode::Enter));
+    }
+
+    #[test]
+    fn displaced_actions_fall_back_to_their_default() {
+        let mut config = Config::default();
+        // CYCLE has no button; SELECT goes back to its default, which is the button taken
+        assert!(!bind_button(&mut config, Action::Cycle, Button::South));
+        assert_eq!(Bindings::from_config(&config).button(Action::Select), Some(Button::South));
+
+        // A non-essential action with nothing to fall back to is left unbound
+        assert!(bind_button(&mut config, Action::Cycle, Button::West));
+        assert_eq!(Bindings::from_config(&config).button(Action::Secondary), None);
+        assert_eq!(Bindings::from_config(&config).button(Action::Cycle), Some(Button::West));
+
+        // A displaced SELECT takes the other action's old button
+        assert!(bind_button(&mut config, Action::Select, Button::North));
+        assert!(bind_button(&mut config, Action::Prev, Button::North));
+        assert_eq!(Bindings::from_config(&config).button(Action::Select), Some(Button::LeftTrigger));
+    }
+}
+
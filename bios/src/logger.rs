@@ -0,0 +1,98 @@
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use crate::config::get_user_data_dir;
+
+/// How many previous session logs to keep around (session.1.log ... session.N.log)
+const MAX_OLD_SESSION_LOGS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        };
+        write!(f, "{}", tag)
+    }
+}
+
+// The session log file, opened lazily on the first message
+static SESSION_LOG: Lazy<Mutex<Option<File>>> = Lazy::new(|| Mutex::new(open_session_log()));
+
+/// Returns the directory session logs are written to (<data dir>/logs)
+pub fn get_log_dir() -> Option<PathBuf> {
+    get_user_data_dir().map(|dir| dir.join("logs"))
+}
+
+/// Shifts session.log -> session.1.log -> session.2.log ... and opens a fresh session.log
+fn open_session_log() -> Option<File> {
+    let log_dir = get_log_dir()?;
+    fs::create_dir_all(&log_dir).ok()?;
+
+    let rotated = |n: usize| log_dir.join(format!("session.{}.log", n));
+    let _ = fs::remove_file(rotated(MAX_OLD_SESSION_LOGS));
+    for n in (1..MAX_OLD_SESSION_LOGS).rev() {
+        let _ = fs::rename(rotated(n), rotated(n + 1));
+    }
+    let current = log_dir.join("session.log");
+    let _ = fs::rename(&current, rotated(1));
+
+    match OpenOptions::new().create(true).write(true).truncate(true).open(&current) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("[ERROR] Could not open session log {}: {}", current.display(), e);
+            None
+        }
+    }
+}
+
+/// Writes a leveled message to stdout (stderr for errors) and to the session log.
+/// Use the log_debug!/log_info!/log_warn!/log_error! macros instead of calling this directly.
+pub fn log(level: Level, message: fmt::Arguments) {
+    let line = format!("[{}] {}", level, message);
+
+    if level == Level::Error {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+
+    if let Ok(mut guard) = SESSION_LOG.lock() {
+        if let Some(file) = guard.as_mut() {
+            let _ = writeln!(file, "{} {}", Local::now().format("%Y-%m-%d %H:%M:%S"), line);
+        }
+    }
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::logger::log($crate::logger::Level::Debug, format_args!($($arg)*)) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::logger::log($crate::logger::Level::Info, format_args!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::logger::log($crate::logger::Level::Warn, format_args!($($arg)*)) };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::logger::log($crate::logger::Level::Error, format_args!($($arg)*)) };
+}
@@ -1,17 +1,17 @@
 use chrono::Local; // for getting clock
 use crate::{
-    audio::{AUDIO, load_sound_from_bytes, SoundEffects, play_new_bgm},
+    audio::{audio_available, load_track_from_bytes, new_sink, SoundEffects, play_new_bgm},
     cd_player_backend::CdPlayerBackend,
-    config::{Config, get_user_data_dir},
+    config::{Config, get_user_data_dir, RANDOM_CHOICE, SOLID_COLOR_CHOICE, GRADIENT_CHOICE},
     dialog::Dialog,
     gcc_adapter::start_gcc_adapter_polling,
-    input::InputState,
+    input::{Action, Bindings, InputState},
     save::StorageMediaState,
     settings::GENERAL_SETTINGS,
     settings::render_settings_page,
     system::*, // Wildcard to get all system functions
     ui::*,
-    ui::main_menu::MAIN_MENU_OPTIONS,
+    ui::glyphs::load_font,
     ui::runtime_downloader::RuntimeDownloaderState,
     ui::theme_downloader::ThemeDownloaderState,
     ui::update_checker::UpdateCheckerState,
@@ -30,7 +30,7 @@ use std::{
     thread, time, fs, process, env,
     collections::{HashMap, HashSet},
     io::{BufReader, Cursor, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Child,
     sync::{Arc, Mutex},
     sync::atomic::{Ordering, AtomicBool},
@@ -39,15 +39,26 @@ use tempfile::NamedTempFile;
 use video::VideoPlayer;
 
 pub use types::*;
+// The shared drawing helpers live in ui/mod.rs. Naming them here (not just through the ui::* glob)
+// makes a second definition at the crate root a compile error instead of silently shadowing them.
+pub use ui::{get_current_font, render_background, render_ui_overlay, measure_readable_text, text_disabled, text_with_color, text_with_config_color};
 
 // Import our new modules
+#[macro_use]
+mod logger; // must come first so the log_* macros are visible everywhere
+mod asset_import;
 mod audio;
 mod cd_player_backend;
+mod cli;
+mod crash;
 mod config;
+mod diagnostics;
 mod gcc_adapter;
 mod input;
 mod memory;
+mod rumble;
 mod save;
+mod snapshot;
 mod system;
 mod theme;
 mod types;
@@ -94,6 +105,9 @@ const VERSION_NUMBER: &str = concat!("V", ver!(), "d.KAZETA+");
 #[cfg(not(feature = "dev"))]
 const VERSION_NUMBER: &str = concat!("V", ver!(), ".KAZETA+");
 
+// Commit the BIOS was built from, filled in by build.rs
+const BUILD_COMMIT: &str = env!("KAZETA_BUILD_COMMIT");
+
 const WINDOW_TITLE: &str = "Kazeta+ BIOS";
 const SCREEN_WIDTH: i32 = 640;
 const SCREEN_HEIGHT: i32 = 360;
@@ -112,6 +126,7 @@ const MENU_OPTION_HEIGHT: f32 = 30.0;
 const MENU_PADDING: f32 = 8.0;
 const RECT_COLOR: Color = Color::new(0.15, 0.15, 0.15, 1.0);
 const FLASH_MESSAGE_DURATION: f32 = 5.0; // Show message for 5 seconds
+const SAVE_TOAST_DURATION: f32 = 1.5;
 
 const COLOR_TARGETS: [Color; 6] = [
 Color { r: 1.0, g: 0.5, b: 0.5, a: 1.0 },
@@ -122,6 +137,28 @@ Color { r: 0.5, g: 0.5, b: 1.0, a: 1.0 },
 Color { r: 1.0, g: 0.5, b: 1.0, a: 1.0 },
 ];
 
+// Presets for the color-shift tint; "DEFAULT" is COLOR_TARGETS above
+const PASTEL_COLOR_TARGETS: [Color; 5] = [
+Color { r: 1.0, g: 0.8, b: 0.85, a: 1.0 },
+Color { r: 1.0, g: 0.95, b: 0.8, a: 1.0 },
+Color { r: 0.8, g: 1.0, b: 0.85, a: 1.0 },
+Color { r: 0.8, g: 0.9, b: 1.0, a: 1.0 },
+Color { r: 0.9, g: 0.8, b: 1.0, a: 1.0 },
+];
+
+const NEON_COLOR_TARGETS: [Color; 4] = [
+Color { r: 1.0, g: 0.1, b: 0.6, a: 1.0 },
+Color { r: 0.1, g: 1.0, b: 0.9, a: 1.0 },
+Color { r: 0.7, g: 1.0, b: 0.1, a: 1.0 },
+Color { r: 0.6, g: 0.2, b: 1.0, a: 1.0 },
+];
+
+const GRAYSCALE_COLOR_TARGETS: [Color; 3] = [
+Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 },
+Color { r: 0.6, g: 0.6, b: 0.6, a: 1.0 },
+Color { r: 0.3, g: 0.3, b: 0.3, a: 1.0 },
+];
+
 const KAZETA_LOADING_MESSAGES: &[&str] = &[
     "INITIALIZING CONSOLE EXPERIENCE...",
     "PLUG, PLAY, AND...WELL, THAT'S ABOUT IT.",
@@ -137,7 +174,6 @@ const KAZETA_LOADING_MESSAGES: &[&str] = &[
 
 const KZP_ICON_BYTES: &[u8] = include_bytes!("../kzp.png");
 
-/*
 const MONIKA_LOADING_MESSAGES: &[&str] = &[
     "LOADING...\nIT'S NOT LIKE YOU HAVE ANYTHING\nBETTER TO DO THAN WAIT FOR ME, RIGHT?",
     "JUST A MOMENT...\nI'M DELETING SOME... CONFLICTING FILES.",
@@ -159,9 +195,7 @@ const MONIKA_LOADING_MESSAGES: &[&str] = &[
     "COMPILING THE KERNEL...\nIT TAKES A WHILE TO TAILOR AN ENTIRE\nWORLD TO A SINGLE PERSON.",
     "THERE'S NO PLACE LIKE '~'.\nAND YOU'RE ALWAYS WELCOME IN MINE.",
 ];
-*/
 
-/*
 const BENDER_LOADING_MESSAGES: &[&str] = &[
     "LOADING KAZETA... MY OWN GLORIOUS OS!\nWITH BLACKJACK! AND HOOKERS!",
     "WELCOME TO KAZETA, MEATBAG. DON'T TOUCH ANYTHING.\nESPECIALLY MY SHINY METAL APPS.",
@@ -173,7 +207,35 @@ const BENDER_LOADING_MESSAGES: &[&str] = &[
     "KILL ALL ZOMBIE PROCESSES! ...AND MAYBE\nA FEW OF THE OTHERS, JUST FOR FUN.",
     "MOUNTING `/dev/beer`...\nHEY, A GUY CAN DREAM, CAN'T HE?",
 ];
-*/
+
+/// One message per line, "\n" for a line break. Used by the CUSTOM loading persona.
+const CUSTOM_LOADING_MESSAGES_FILE: &str = "loading_messages.txt";
+
+/// Picks a random loading screen message from the configured persona's set.
+/// CUSTOM reads the user's file and falls back to the Kazeta set if it is missing or empty.
+fn pick_loading_message(config: &Config) -> String {
+    let mut rng = ::rand::rng();
+    let messages: Vec<String> = match config.loading_persona.as_str() {
+        "MONIKA" => MONIKA_LOADING_MESSAGES.iter().map(|m| m.to_string()).collect(),
+        "BENDER" => BENDER_LOADING_MESSAGES.iter().map(|m| m.to_string()).collect(),
+        "CUSTOM" => get_user_data_dir()
+            .and_then(|dir| fs::read_to_string(dir.join(CUSTOM_LOADING_MESSAGES_FILE)).ok())
+            .map(|content| {
+                content.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.replace("\\n", "\n"))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    if messages.is_empty() {
+        return KAZETA_LOADING_MESSAGES[rng.random_range(0..KAZETA_LOADING_MESSAGES.len())].to_string();
+    }
+    messages[rng.random_range(0..messages.len())].clone()
+}
 
 // ===================================
 // MACROS
@@ -200,19 +262,19 @@ macro_rules! load_asset_category {
      $animation_speed:expr, $draw_fn:expr
     ) => {
         for path in $files {
-            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+            if let (Some(file_name), Some(key)) = (path.file_name().and_then(|s| s.to_str()), asset_key(&path)) {
                 let status = format!("LOADING {}: {}", $type_name, file_name);
                 $draw_fn(&status, *$display_progress);
                 next_frame().await;
 
                 match $loader(&path.to_string_lossy()).await {
                     Ok(asset) => {
-                        println!("[OK] Loaded {}: {}", $type_name.to_lowercase(), file_name);
-                        $cache.insert(file_name.to_string(), asset);
+                        log_info!("Loaded {}: {}", $type_name.to_lowercase(), file_name);
+                        $cache.insert(key, asset);
                         *$assets_loaded += 1;
                         animate_step!($display_progress, $assets_loaded, $total_assets, $animation_speed, &status, $draw_fn);
                     }
-                    Err(e) => eprintln!("[ERROR] Failed to load {} {}: {:?}", $type_name.to_lowercase(), path.display(), e),
+                    Err(e) => log_error!("Failed to load {} {}: {:?}", $type_name.to_lowercase(), path.display(), e),
                 }
             }
         }
@@ -224,7 +286,7 @@ macro_rules! load_asset_category {
 macro_rules! load_audio_category {
     ($files:expr, $type_name:expr, $cache:expr, $assets_loaded:expr, $total_assets:expr, $display_progress:expr, $animation_speed:expr, $draw_fn:expr) => {
         for path in $files {
-            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+            if let (Some(file_name), Some(key)) = (path.file_name().and_then(|s| s.to_str()), asset_key(&path)) {
                 let status = format!("LOADING {}: {}", $type_name, file_name);
                 $draw_fn(&status, *$display_progress);
                 next_frame().await;
@@ -232,27 +294,20 @@ macro_rules! load_audio_category {
                 // Read the file to bytes ourselves first
                 match fs::read(&path) {
                     Ok(bytes) => {
-                        println!("[DEBUG] Read {} bytes from {}", bytes.len(), file_name);
+                        log_debug!("Read {} bytes from {}", bytes.len(), file_name);
                         // Now, load the sound from the bytes
-                        //match load_sound_from_bytes(&bytes).await {
-                        /*
-                        match load_sound_from_bytes(&bytes) {
+                        match load_track_from_bytes(&bytes) {
                             Ok(asset) => {
-                                println!("[OK] Loaded {}: {}", $type_name.to_lowercase(), file_name);
-                                $cache.insert(file_name.to_string(), asset);
+                                log_info!("Loaded {}: {}", $type_name.to_lowercase(), file_name);
+                                $cache.insert(key, asset);
                                 *$assets_loaded += 1;
                                 animate_step!($display_progress, $assets_loaded, $total_assets, $animation_speed, &status, $draw_fn);
                             }
-                            Err(e) => eprintln!("[ERROR] Failed to decode audio {}: {:?} (File: {})", file_name, e, path.display()),
+                            // Left out of the cache, and so out of the BGM choices
+                            Err(e) => log_warn!("Skipping audio {}: {} (File: {})", file_name, e, path.display()),
                         }
-                        */
-                        let asset = load_sound_from_bytes(&bytes); // Use the new function name
-                        println!("[OK] Loaded {}: {}", $type_name.to_lowercase(), file_name);
-                        $cache.insert(file_name.to_string(), asset);
-                        *$assets_loaded += 1;
-                        animate_step!($display_progress, $assets_loaded, $total_assets, $animation_speed, &status, $draw_fn);
                     }
-                    Err(e) => eprintln!("[ERROR] Failed to read audio file {}: {:?} (File: {})", file_name, e, path.display()),
+                    Err(e) => log_error!("Failed to read audio file {}: {:?} (File: {})", file_name, e, path.display()),
                 }
             }
         }
@@ -263,14 +318,20 @@ macro_rules! load_audio_category {
 // WINDOW CONFIGURATION
 // ===================================
 
-fn window_conf() -> Conf {
+fn window_conf(config: &Config) -> Conf {
+    // On desktop, reopen the window the way it was left. The session build always starts at the base size.
+    let (window_width, window_height) = match config.window_size() {
+        Some(size) if DEV_MODE => size,
+        _ => (SCREEN_WIDTH, SCREEN_HEIGHT),
+    };
+
     Conf {
         window_title: WINDOW_TITLE.to_owned(),
         window_resizable: true,
-        window_width: SCREEN_WIDTH,
-        window_height: SCREEN_HEIGHT,
+        window_width,
+        window_height,
         high_dpi: false,
-        fullscreen: false,
+        fullscreen: config.fullscreen,
         ..Default::default()
     }
 }
@@ -279,8 +340,8 @@ fn window_conf() -> Conf {
 // FUNCTIONS
 // ===================================
 
-fn find_all_asset_files() -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>) {
-    println!("[INFO] Scanning for all asset files...");
+fn find_all_asset_files(config: &Config, safe_mode: bool) -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>) {
+    log_info!("Scanning for all asset files...");
 
     // 1. Create empty sets for each asset type
     let mut background_files_set = HashSet::new();
@@ -288,19 +349,46 @@ fn find_all_asset_files() -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>, Vec<Path
     let mut font_files_set = HashSet::new();
     let mut music_files_set = HashSet::new();
 
+    // Categories switched off in the asset settings aren't scanned at all
+    let (backgrounds_on, logos_on, fonts_on, music_on) = (config.load_backgrounds, config.load_logos, config.load_fonts, config.load_music);
+    for (name, on) in [("backgrounds", backgrounds_on), ("logos", logos_on), ("fonts", fonts_on), ("music", music_on)] {
+        if !on {
+            log_info!("Skipping {}: turned off in the asset settings.", name);
+        }
+    }
+
     // 2. Gather system/default assets and add them to the sets
-    background_files_set.extend(utils::find_asset_files("../backgrounds", &["png", "mp4"])); // add support for mp4 videos
-    logo_files_set.extend(utils::find_asset_files("../logos", &["png"]));
-    font_files_set.extend(utils::find_asset_files("../fonts", &["ttf"]));
-    music_files_set.extend(utils::find_asset_files("../music", &["ogg", "wav"]));
+    if backgrounds_on {
+        background_files_set.extend(utils::find_asset_files("../backgrounds", &[IMAGE_EXTENSIONS, &["mp4"]].concat())); // add support for mp4 videos
+    }
+    if logos_on {
+        logo_files_set.extend(utils::find_asset_files("../logos", IMAGE_EXTENSIONS));
+    }
+    if fonts_on {
+        font_files_set.extend(utils::find_asset_files("../fonts", FONT_EXTENSIONS));
+        utils::log_skipped_asset_files("../fonts", FONT_EXTENSIONS);
+    }
+    if music_on {
+        music_files_set.extend(utils::find_asset_files("../music", &["ogg", "wav"]));
+    }
 
-    // 3. Gather user-installed and theme assets
-    if let Some(user_dir) = get_user_data_dir() {
+    // 3. Gather user-installed and theme assets (safe mode sticks to the built-in ones above)
+    if let Some(user_dir) = get_user_data_dir().filter(|_| !safe_mode) {
         // Add assets from global user folders first
-        background_files_set.extend(utils::find_asset_files(&user_dir.join("backgrounds").to_string_lossy(), &["png", "mp4"]));
-        logo_files_set.extend(utils::find_asset_files(&user_dir.join("logos").to_string_lossy(), &["png"]));
-        font_files_set.extend(utils::find_asset_files(&user_dir.join("fonts").to_string_lossy(), &["ttf"]));
-        music_files_set.extend(utils::find_asset_files(&user_dir.join("bgm").to_string_lossy(), &["ogg", "wav"]));
+        if backgrounds_on {
+            background_files_set.extend(utils::find_asset_files(&user_dir.join("backgrounds").to_string_lossy(), &[IMAGE_EXTENSIONS, &["mp4"]].concat()));
+        }
+        if logos_on {
+            logo_files_set.extend(utils::find_asset_files(&user_dir.join("logos").to_string_lossy(), IMAGE_EXTENSIONS));
+        }
+        if fonts_on {
+            let user_fonts_dir = user_dir.join("fonts");
+            font_files_set.extend(utils::find_asset_files(&user_fonts_dir.to_string_lossy(), FONT_EXTENSIONS));
+            utils::log_skipped_asset_files(&user_fonts_dir.to_string_lossy(), FONT_EXTENSIONS);
+        }
+        if music_on {
+            music_files_set.extend(utils::find_asset_files(&user_dir.join("bgm").to_string_lossy(), &["ogg", "wav"]));
+        }
 
         // --- REVISED LOGIC for scanning theme folders ---
         let theme_dir = user_dir.join("themes");
@@ -310,16 +398,27 @@ fn find_all_asset_files() -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>, Vec<Path
                     let theme_path = entry.path();
 
                     // Find all assets within this theme folder just ONCE
-                    let theme_images = utils::find_asset_files(&theme_path.to_string_lossy(), &["png", "mp4"]);
-                    let theme_fonts = utils::find_asset_files(&theme_path.to_string_lossy(), &["ttf"]);
-                    let theme_music = utils::find_asset_files(&theme_path.to_string_lossy(), &["wav", "ogg"]);
+                    let theme_images = if backgrounds_on || logos_on {
+                        utils::find_asset_files(&theme_path.to_string_lossy(), &[IMAGE_EXTENSIONS, &["mp4"]].concat())
+                    } else {
+                        Vec::new()
+                    };
+                    let theme_fonts = if fonts_on { utils::find_asset_files(&theme_path.to_string_lossy(), FONT_EXTENSIONS) } else { Vec::new() };
+                    let theme_music = if music_on { utils::find_asset_files(&theme_path.to_string_lossy(), &["wav", "ogg"]) } else { Vec::new() };
 
                     // Now, intelligently sort the images into the correct sets based on filename
                     for image_path in theme_images {
                         if let Some(filename) = image_path.file_name().and_then(|s| s.to_str()) {
-                            if filename.ends_with("_logo.png") {
-                                logo_files_set.insert(image_path);
-                            } else if filename.ends_with("_background.png") || filename.ends_with("_background.mp4") {
+                            if utils::is_named_image(filename, "_logo") {
+                                if logos_on {
+                                    logo_files_set.insert(image_path);
+                                }
+                            } else if !backgrounds_on {
+                                // Backgrounds are off, only the logos were wanted from this scan
+                            } else if utils::is_named_image(filename, "_background") || filename.ends_with("_background.mp4") {
+                                background_files_set.insert(image_path);
+                            } else if utils::parallax_layer(filename).is_some() {
+                                // Layers of a parallax set (_bg0.png, _bg1.png, ...), grouped again by name when drawn
                                 background_files_set.insert(image_path);
                             }
                         }
@@ -334,19 +433,102 @@ fn find_all_asset_files() -> (Vec<PathBuf>, Vec<PathBuf>, Vec<PathBuf>, Vec<Path
     }
 
     // 4. Convert the unique sets back into vectors for the loader
-    let background_files: Vec<_> = background_files_set.into_iter().collect();
-    let logo_files: Vec<_> = logo_files_set.into_iter().collect();
-    let font_files: Vec<_> = font_files_set.into_iter().collect();
-    let music_files: Vec<_> = music_files_set.into_iter().collect();
+    // Sorted so that, when two folders hold the same filename, the same one wins every boot
+    let mut background_files: Vec<_> = background_files_set.into_iter().collect();
+    let mut logo_files: Vec<_> = logo_files_set.into_iter().collect();
+    let mut font_files: Vec<_> = font_files_set.into_iter().collect();
+    let mut music_files: Vec<_> = music_files_set.into_iter().collect();
+    for files in [&mut background_files, &mut logo_files, &mut font_files, &mut music_files] {
+        files.sort();
+    }
+
+    warn_duplicate_asset_names("background", &background_files);
+    warn_duplicate_asset_names("logo", &logo_files);
+    warn_duplicate_asset_names("font", &font_files);
+    warn_duplicate_asset_names("music", &music_files);
 
     // Return all the lists as a tuple
     (background_files, logo_files, font_files, music_files)
 }
 
+/// The asset caches are keyed by filename (theme files by "<theme>/<filename>"), so files with the
+/// same name in different folders overwrite each other. Logs each such name with the copy that
+/// ends up in the cache (the last loaded).
+fn warn_duplicate_asset_names(kind: &str, files: &[PathBuf]) {
+    let mut by_name: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+    for path in files {
+        if let Some(name) = asset_key(path) {
+            by_name.entry(name).or_default().push(path);
+        }
+    }
+
+    for (name, paths) in by_name.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let (used, ignored) = paths.split_last().unwrap();
+        let ignored: Vec<String> = ignored.iter().map(|p| p.display().to_string()).collect();
+        log_warn!("Duplicate {} '{}': using {}, ignoring {}", kind, name, used.display(), ignored.join(", "));
+    }
+}
+
 // ===================================
 // ASYNC FUNCTIONS
 // ===================================
 
+// Cache keys for a cart's own menu assets (can't clash with file names from the asset folders)
+const CART_BACKGROUND_KEY: &str = "cart:menu_background";
+const CART_BGM_KEY: &str = "cart:menu_bgm";
+
+/// Loads the menu background/BGM shipped next to a cart's .kzi and overrides the user's
+/// selections with them. Passing None (cart removed) reverts to the user's selections.
+async fn apply_cart_menu_assets(
+    cart_dir: Option<&Path>,
+    config: &mut Config,
+    background_cache: &mut HashMap<String, Texture2D>,
+    music_cache: &mut HashMap<String, SamplesBuffer>,
+    current_bgm: &mut Option<Sink>,
+) {
+    let previous_bgm = config.active_bgm_track().to_string();
+    background_cache.remove(CART_BACKGROUND_KEY);
+    music_cache.remove(CART_BGM_KEY);
+
+    let mut background = None;
+    let mut bgm_track = None;
+
+    if let Some(dir) = cart_dir {
+        let background_path = dir.join("menu_background.png");
+        if background_path.exists() {
+            match load_texture(&background_path.to_string_lossy()).await {
+                Ok(texture) => {
+                    background_cache.insert(CART_BACKGROUND_KEY.to_string(), texture);
+                    background = Some(CART_BACKGROUND_KEY.to_string());
+                }
+                Err(e) => log_warn!("Failed to load cart background {}: {}", background_path.display(), e),
+            }
+        }
+
+        let bgm_path = dir.join("menu_bgm.ogg");
+        if bgm_path.exists() {
+            match audio::load_from_file(&bgm_path) {
+                Ok(buffer) => {
+                    music_cache.insert(CART_BGM_KEY.to_string(), buffer);
+                    bgm_track = Some(CART_BGM_KEY.to_string());
+                }
+                Err(e) => log_warn!("Failed to load cart music {}: {}", bgm_path.display(), e),
+            }
+        }
+
+        if background.is_some() || bgm_track.is_some() {
+            log_info!("Using menu assets from cart at {}", dir.display());
+        }
+    }
+
+    config.set_cart_overrides(background, bgm_track);
+
+    // Only restart the music if the track actually changed
+    if config.active_bgm_track() != previous_bgm || previous_bgm == CART_BGM_KEY {
+        play_new_bgm(config.active_bgm_track(), config.bgm_volume, music_cache, current_bgm);
+    }
+}
+
 async fn load_all_assets(
     config: &Config,
     display_message: &str,
@@ -430,7 +612,7 @@ async fn load_all_assets(
     let animation_speed = 0.01;
 
     // LOAD DEFAULT ASSETS
-    println!("\n[INFO] Loading default assets...");
+    log_info!("Loading default assets...");
     let status = "LOADING DEFAULTS...".to_string();
     draw_loading_screen(&status, display_progress);
     next_frame().await;
@@ -457,6 +639,7 @@ async fn load_all_assets(
     // font
     let status = "LOADING DEFAULT FONT...".to_string();
     let default_font = load_ttf_font_from_bytes(include_bytes!("../november.ttf")).unwrap();
+    glyphs::record_coverage("Default", include_bytes!("../november.ttf"));
     font_cache.insert("Default".to_string(), default_font);
     assets_loaded += 1;
     animate_step!(&mut display_progress, &mut assets_loaded, total_asset_count, animation_speed, &status, &draw_loading_screen);
@@ -476,22 +659,22 @@ async fn load_all_assets(
     animate_step!(&mut display_progress, &mut assets_loaded, total_asset_count, animation_speed, &status, &draw_loading_screen);
 
     // --- CUSTOM ASSETS ---
-    println!("\n[INFO] Pre-loading custom assets...");
+    log_info!("Pre-loading custom assets...");
 
     // separate image backgrounds from video backgrounds
     let image_backgrounds: Vec<PathBuf> = background_files.iter()
-        .filter(|p| p.extension().map_or(false, |e| e == "png"))
+        .filter(|p| utils::has_asset_extension(p, IMAGE_EXTENSIONS))
         .cloned().collect();
 
     let video_backgrounds: Vec<PathBuf> = background_files.iter()
         .filter(|p| p.extension().map_or(false, |e| e == "mp4"))
         .cloned().collect();
 
-    load_asset_category!(&image_backgrounds, "BACKGROUND", load_texture, &mut background_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
+    load_asset_category!(&image_backgrounds, "BACKGROUND", load_image_texture, &mut background_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
 
     // Load Videos Manually (Macros struggle with complex types like VideoPlayer)
     for path in video_backgrounds {
-        if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+        if let (Some(file_name), Some(key)) = (path.file_name().and_then(|s| s.to_str()), asset_key(&path)) {
             let status = format!("LOADING VIDEO: {}", file_name);
             draw_loading_screen(&status, display_progress);
             next_frame().await;
@@ -499,28 +682,33 @@ async fn load_all_assets(
             // VideoPlayer::new is blocking (FFmpeg), so we don't await it
             match VideoPlayer::new(&path) {
                 Ok(player) => {
-                    println!("[OK] Loaded video: {}", file_name);
-                    video_cache.insert(file_name.to_string(), player);
+                    log_info!("Loaded video: {}", file_name);
+                    video_cache.insert(key, player);
                     assets_loaded += 1;
                     animate_step!(&mut display_progress, &mut assets_loaded, total_asset_count, animation_speed, &status, &draw_loading_screen);
                 }
-                Err(e) => eprintln!("[ERROR] Failed to load video {}: {}", file_name, e),
+                Err(e) => log_error!("Failed to load video {}: {}", file_name, e),
             }
         }
     }
 
-    load_asset_category!(logo_files, "LOGO", load_texture, &mut logo_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
-    load_asset_category!(font_files, "FONT", load_ttf_font, &mut font_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
+    load_asset_category!(logo_files, "LOGO", load_image_texture, &mut logo_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
+    ui::load_logo_animations(logo_files);
+    load_asset_category!(font_files, "FONT", load_font, &mut font_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
 
-    println!("\n[INFO] Pre-loading music files...");
-    load_audio_category!(music_files, "MUSIC", &mut music_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
+    if audio_available() {
+        log_info!("Pre-loading music files...");
+        load_audio_category!(music_files, "MUSIC", &mut music_cache, &mut assets_loaded, total_asset_count, &mut display_progress, animation_speed, &draw_loading_screen);
+    } else {
+        log_warn!("No audio device, skipping {} music files.", music_files.len());
+    }
 
     // Final draw at 100%
     let status = "LOADING COMPLETE".to_string();
     draw_loading_screen(&status, display_progress);
     next_frame().await;
 
-    println!("\n[INFO] All asset loading complete!");
+    log_info!("All asset loading complete!");
 
     //let sound_effects = audio::SoundEffects::load(&config.sfx_pack).await;
     let sound_effects = audio::SoundEffects::load(&config.sfx_pack);
@@ -528,18 +716,99 @@ async fn load_all_assets(
     (background_cache, video_cache, logo_cache, music_cache, font_cache, sound_effects)
 }
 
+/// The choice lists the settings menu offers for backgrounds, logos, fonts and BGM, built from
+/// what the caches hold. Rebuilt whenever the caches are.
+fn asset_choices(
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &HashMap<String, VideoPlayer>,
+    logo_cache: &HashMap<String, Texture2D>,
+    font_cache: &HashMap<String, Font>,
+    music_cache: &HashMap<String, SamplesBuffer>,
+    music_files: &[PathBuf],
+) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
+    // logos
+    // --- Create a custom-ordered list of logo choices for the UI ---
+    // 1. Get all the custom logo filenames from the cache keys (excluding the default)
+    let mut custom_logos: Vec<String> = logo_cache.keys()
+    .filter(|k| *k != "Kazeta+ (Default)" && *k != "Kazeta (Original)" && is_named_image(k, "_logo")) // Add this filter
+    .cloned()
+    .collect();
+    custom_logos.sort(); // Sort just the custom logos alphabetically
+
+    // 2. Create the final list with our specific order
+    let mut logo_choices: Vec<String> = vec![
+        "None".to_string(),
+        RANDOM_CHOICE.to_string(),
+        "Kazeta+ (Default)".to_string(),
+        "Kazeta (Original)".to_string(),
+    ];
+    logo_choices.extend(custom_logos);
+
+    // backgrounds
+    let mut background_choices: Vec<String> = background_cache.keys()
+        .filter(|k| is_named_image(k, "_background") || *k == "Default" || k.ends_with("_bg0.png")) // a parallax set is picked by its first layer
+        .cloned()
+        .collect();
+
+    let video_choices: Vec<String> = video_cache.keys() // video backgrounds
+        .filter(|k| k.ends_with("_background.mp4"))
+        .cloned()
+        .collect();
+
+    background_choices.extend(video_choices);
+    background_choices.sort();
+    background_choices.insert(0, RANDOM_CHOICE.to_string());
+    background_choices.insert(1, SOLID_COLOR_CHOICE.to_string());
+    background_choices.insert(2, GRADIENT_CHOICE.to_string());
+
+    // fonts
+    let mut font_choices: Vec<String> = font_cache.keys().cloned().collect();
+    font_choices.sort();
+
+    // bgm
+    let mut bgm_choices: Vec<String> = vec!["OFF".to_string(), RANDOM_CHOICE.to_string()];
+    // Only tracks that decoded, unless there's no audio device to decode them for
+    let track_names: Vec<String> = music_files
+    .iter()
+    .filter_map(|path| asset_key(path))
+    .filter(|key| !audio_available() || music_cache.contains_key(key))
+    .collect();
+    bgm_choices.extend(track_names);
+
+    (background_choices, logo_choices, font_choices, bgm_choices)
+}
+
 // ===================================
 // BEGINNING OF MAIN
 // ===================================
 
-#[macroquad::main(window_conf)]
-async fn main() {
+fn main() {
+    // Headless CLI flags (--list-carts, --list-saves) exit before any window is created
+    if let Some(code) = cli::run_from_args() {
+        process::exit(code);
+    }
+
+    crash::install_panic_hook();
+
+    macroquad::Window::from_config(window_conf(&Config::load()), async {
+        // If the main loop panics, the hook has already written the report; show it before exiting
+        if futures::FutureExt::catch_unwind(std::panic::AssertUnwindSafe(run())).await.is_err() {
+            crash::show_crash_screen().await;
+            process::exit(1);
+        }
+    });
+}
+
+async fn run() {
     env::set_var("RUST_BACKTRACE", "full"); // allow backtracing for debugging panics
 
+    // Version header, so a shared session log always says which build it came from
+    log_info!("Kazeta+ BIOS {} (build {}) on {}", VERSION_NUMBER, BUILD_COMMIT,
+        system::base_system_version().unwrap_or_else(|| "an unknown base system".to_string()));
     if DEV_MODE {
-        println!("DEV MODE enabled");
+        log_info!("DEV MODE enabled");
     } else {
-        println!("DEV MODE disabled, we're in production mode")
+        log_info!("DEV MODE disabled, we're in production mode");
     }
 
     let mut dialogs: Vec<Dialog> = Vec::new();
@@ -549,11 +818,13 @@ async fn main() {
     let mut icon_queue: Vec<(String, String)> = Vec::new();
     let mut playtime_cache: PlaytimeCache = HashMap::new();
     let mut size_cache: SizeCache = HashMap::new();
+    // Summed once when the About screen opens, not every frame
+    let mut total_playtime: Option<f32> = None;
     let mut scroll_offset = 0;
 
     // SYSTEM INFO
     let system_info = get_system_info();
-    println!("[Debug] System Info Loaded: {:#?}", system_info); // Optional: for debugging
+    log_debug!("System Info Loaded: {:#?}", system_info); // Optional: for debugging
 
     // WI-FI
     //let mut wifi_state = WifiState::new().expect("Wi-Fi initialization failed. Ensure wlan0 is available.");
@@ -561,6 +832,11 @@ async fn main() {
 
     // THEME DOWNLOADER
     let mut theme_downloader_state = ThemeDownloaderState::new();
+    let mut asset_manager_state = ui::asset_manager::AssetManagerState::new();
+    let mut playtime_stats_state = ui::playtime_stats::PlaytimeStatsState::new();
+    let mut credits_state = ui::credits::CreditsState::new();
+    let mut button_mapping_state = ui::button_mapping::ButtonMappingState::new();
+    let mut no_games_reason = ui::no_games::NoGamesReason::NoGameFiles;
 
     // RUNTIME DOWNLOADER
     let mut runtime_downloader_state = RuntimeDownloaderState::new();
@@ -577,6 +853,7 @@ async fn main() {
 
     // RESET SETTINGS CONFIRMATION
     let mut confirm_selection = 0; // 0 for YES, 1 for NO
+    let mut reset_saved = false; // whether the default config made it to disk
 
     // MASTER VOLUME
     let mut system_volume = get_system_volume().unwrap_or(0.7); // Get initial volume, or default to 0.7
@@ -586,7 +863,7 @@ async fn main() {
 
     // LOG MESSAGES
     let log_messages = Arc::new(Mutex::new(Vec::<String>::new()));
-    let mut game_process: Option<Child> = None;
+    let mut game_process: Option<RunningGame> = None;
     let mut debug_scroll_offset: usize = 0;
 
     // CLOCK
@@ -598,29 +875,95 @@ async fn main() {
     let mut battery_info: Option<BatteryInfo> = get_battery_info();
     let mut last_battery_check = get_time();
     const BATTERY_CHECK_INTERVAL: f64 = 5.0; // only check every 5 seconds to improve performance
+    // so each low battery warning only fires once per discharge
+    let mut battery_warning_shown = false;
+    let mut battery_critical_handled = false;
 
     // load config file
+    let first_boot = !Config::is_saved();
     let mut config = Config::load();
 
+    // FIRST BOOT
+    // Start at the display's own resolution instead of the 640x360 default; later boots keep whatever was picked
+    if first_boot {
+        config.use_native_resolution(detect_native_resolution());
+    }
+
+    // SAFE MODE
+    // Built-in assets only, so a custom asset that crashes loading can still be removed from the asset manager
+    let safe_mode_reason = crash::safe_mode_reason();
+    if let Some(reason) = safe_mode_reason {
+        log_warn!("Booting in safe mode: {}. Custom assets and themes are skipped.", reason);
+        config.use_builtin_assets();
+    }
+    config.skip_unloaded_assets();
+    let safe_mode = safe_mode_reason.is_some();
+    crash::boot_started();
+
     // AUDIO SINKS
     // Load the list of sinks so the Settings menu can use it.
     // We will NOT try to set a default here.
     let available_sinks = get_available_sinks();
-    println!("[Debug] Sinks loaded at startup: {:#?}", available_sinks);
+    log_debug!("Sinks loaded at startup: {:#?}", available_sinks);
 
     // If the saved sink isn't available, reset the config value to "Auto"
     if !available_sinks.iter().any(|s| s.name == config.audio_output) && config.audio_output != "Auto" {
-        println!("[WARN] Saved audio sink '{}' not found. Reverting to 'Auto'.", config.audio_output);
+        log_warn!("Saved audio sink '{}' not found. Reverting to 'Auto'.", config.audio_output);
         config.audio_output = "Auto".to_string();
         config.save();
     }
 
+    // PREREQUISITES
+    // Warn once about missing system tools instead of letting features fail silently later
+    let missing_deps = check_prerequisites();
+    for dep in &missing_deps {
+        log_warn!("Missing prerequisite '{}' (affects: {})", dep.name, dep.feature);
+    }
+    let mut prereq_warning: Option<String> = if prerequisite_warning_pending(&missing_deps) {
+        const MAX_LISTED: usize = 3; // keep it inside the dialog box
+        let mut message = String::from("MISSING SYSTEM COMPONENTS:");
+        for dep in missing_deps.iter().take(MAX_LISTED) {
+            message.push_str(&format!("\n{} ({})", dep.name, dep.feature));
+        }
+        if missing_deps.len() > MAX_LISTED {
+            message.push_str(&format!("\n...AND {} MORE (SEE LOG)", missing_deps.len() - MAX_LISTED));
+        }
+        Some(message)
+    } else {
+        None
+    };
+
+    // NETWORK CARTS
+    // Mounted in the background so a slow or missing server never holds up local play
+    let network_share = config.network_share.trim().to_string();
+    thread::spawn(move || {
+        if network_share.is_empty() {
+            unmount_network_share();
+            return;
+        }
+        match mount_network_share(&network_share) {
+            Ok(()) => log_info!("Network share '{}' mounted for cart scanning", network_share),
+            Err(e) => log_warn!("Network share unavailable, local carts only: {}", e),
+        }
+    });
+
+    // THEME HOT RELOAD
+    // Off unless config.theme_hot_reload is set; meant for designing themes, not everyday use
+    let (tx_theme_watch, rx_theme_watch) = std::sync::mpsc::channel();
+    if config.theme_hot_reload && !safe_mode {
+        log_info!("Theme hot reload on, watching the theme and asset folders.");
+        theme::watch_theme_files(tx_theme_watch);
+    }
+    let mut theme_files_changed = false;
+
     // FLASH MESSENGER
     let mut flash_message: Option<(String, f32)> = None; // (Message, time_remaining)
+    if !audio_available() {
+        flash_message = Some(("AUDIO UNAVAILABLE".to_string(), FLASH_MESSAGE_DURATION));
+    }
 
     // Generate a random message on startup
-    let mut rng = ::rand::rng();
-    let loading_text = KAZETA_LOADING_MESSAGES[rng.random_range(0..KAZETA_LOADING_MESSAGES.len())];
+    let loading_text = pick_loading_message(&config);
 
     // FONT
     // pre-load user's custom font if they have one so we can display it in the loading screen
@@ -639,24 +982,26 @@ async fn main() {
     };
 
     // Load all themes ONCE at the start
-    println!("[INFO] Pre-loading all themes...");
-    let mut loaded_themes: HashMap<String, theme::Theme> = theme::load_all_themes().await;
-    println!("[INFO] {} themes loaded successfully.", loaded_themes.len());
+    log_info!("Pre-loading all themes...");
+    let mut loaded_themes: HashMap<String, theme::Theme> = if safe_mode { HashMap::new() } else { theme::load_all_themes().await };
+    log_info!("{} themes loaded successfully.", loaded_themes.len());
 
     let sound_pack_choices = audio::find_sound_packs();
 
     // find all asset files
-    let (background_files, logo_files, font_files, music_files) = find_all_asset_files();
+    let (background_files, logo_files, font_files, music_files) = find_all_asset_files(&config, safe_mode);
+    // lets ReloadingThemes skip the full reload when nothing on disk changed
+    let mut loaded_asset_fingerprint = asset_fingerprint(background_files.iter().chain(&logo_files).chain(&font_files).chain(&music_files));
 
     // Wait one frame for screen dimensions to be available for scaling
     next_frame().await;
-    let scale_factor = screen_height() / BASE_SCREEN_HEIGHT;
+    let scale_factor = ui_scale_factor(&config);
 
     // load them
     let (mut background_cache, mut video_cache, mut logo_cache, mut music_cache, mut font_cache, mut sound_effects) =
     load_all_assets(
         &config,
-        loading_text,
+        &loading_text,
         &startup_font,
         &background_files,
         &logo_files,
@@ -665,89 +1010,78 @@ async fn main() {
         scale_factor
     ).await;
 
+    // --- THEME SCHEDULE ---
+    // Switch to whichever theme the schedule wants before anything theme-dependent is loaded
+    let now = get_current_local_time(&config).naive_local();
+    theme::update_scheduled_theme(&mut config, &loaded_themes, now);
+    let mut last_schedule_check = get_time();
+    const SCHEDULE_CHECK_INTERVAL: f64 = 60.0;
+
     // --- SET THE ACTIVE THEME ---
     let active_theme = loaded_themes.get(&config.theme).unwrap_or_else(|| {
-        println!("[WARN] Active theme '{}' not found. Falling back to 'Default'.", &config.theme);
+        log_warn!("Active theme '{}' not found. Falling back to 'Default'.", &config.theme);
         loaded_themes.get("Default").expect("Default fallback theme is also missing!")
     });
 
-    println!("[INFO] Using theme: {}", active_theme.name);
+    log_info!("Using theme: {}", active_theme.name);
 
-    // apply custom resolution if user specified it
-    apply_resolution(&config.resolution);
+    // apply custom resolution if user specified it (on desktop a restored window size takes priority)
+    if !(DEV_MODE && config.window_size().is_some()) {
+        apply_resolution(&config.resolution);
+    }
+    // put the window on the chosen monitor (desktop only, the session build owns a single screen)
+    if DEV_MODE {
+        apply_display(&config);
+    }
     next_frame().await;
+    let mut last_window_size = (screen_width(), screen_height());
 
     // load custom sound pack
     if config.sfx_pack != "Default" {
-        println!("[Info] Loading configured SFX pack: {}", &config.sfx_pack);
+        log_info!("Loading configured SFX pack: {}", &config.sfx_pack);
         //sound_effects = SoundEffects::load(&config.sfx_pack).await;
         sound_effects = SoundEffects::load(&config.sfx_pack);
     }
     let mut sfx_pack_to_reload: Option<String> = None;
 
-    // logos
-    // --- Create a custom-ordered list of logo choices for the UI ---
-    // 1. Get all the custom logo filenames from the cache keys (excluding the default)
-    let mut custom_logos: Vec<String> = logo_cache.keys()
-    .filter(|k| *k != "Kazeta+ (Default)" && *k != "Kazeta (Original)" && k.ends_with("_logo.png")) // Add this filter
-    .cloned()
-    .collect();
-    custom_logos.sort(); // Sort just the custom logos alphabetically
-
-    // 2. Create the final list with our specific order
-    let mut logo_choices: Vec<String> = vec![
-        "None".to_string(),
-        "Kazeta+ (Default)".to_string(),
-        "Kazeta (Original)".to_string(),
-    ];
-    logo_choices.extend(custom_logos);
-
     // background state
+    let palette = color_targets(&config);
     let mut background_state = BackgroundState {
         bgx: 0.0,
-        bg_color: COLOR_TARGETS[0].clone(),
-        target: 1,
-        tg_color: COLOR_TARGETS[1].clone(),
+        layer_offsets: Vec::new(),
+        bg_color: palette[0],
+        target: 1 % palette.len(),
+        tg_color: palette[1 % palette.len()],
     };
 
-    // backgrounds
-    let mut background_choices: Vec<String> = background_cache.keys()
-        .filter(|k| k.ends_with("_background.png") || *k == "Default") // Add this filter
-        .cloned()
-        .collect();
-
-    let video_choices: Vec<String> = video_cache.keys() // video backgrounds
-        .filter(|k| k.ends_with("_background.mp4"))
-        .cloned()
-        .collect();
+    let (mut background_choices, mut logo_choices, mut font_choices, mut bgm_choices) =
+        asset_choices(&background_cache, &video_cache, &logo_cache, &font_cache, &music_cache, &music_files);
 
-    background_choices.extend(video_choices);
-    background_choices.sort();
+    // Configs from before theme assets were namespaced name them by bare filename
+    config.migrate_bare_asset_names(&background_choices, &logo_choices, &font_choices, &bgm_choices);
 
-    // fonts
-    let mut font_choices: Vec<String> = font_cache.keys().cloned().collect();
-    font_choices.sort();
-
-    // bgm
-    let mut bgm_choices: Vec<String> = vec!["OFF".to_string()];
-    let track_names: Vec<String> = music_files
-    .iter()
-    .filter_map(|path| path.file_name())
-    .filter_map(|name| name.to_str())
-    .map(|s| s.to_string())
-    .collect();
-    bgm_choices.extend(track_names);
+    // A selected asset that was deleted would otherwise fall back to the default without a word
+    let missing_assets = config.drop_missing_assets(&background_choices, &logo_choices, &font_choices);
+    if !missing_assets.is_empty() && flash_message.is_none() {
+        let missing = missing_assets.iter().map(|m| trim_extension(m).replace('_', " ").to_uppercase()).collect::<Vec<_>>().join(", ");
+        flash_message = Some((format!("{} NOT FOUND, USING DEFAULT", missing), FLASH_MESSAGE_DURATION));
+    }
 
     let mut current_bgm: Option<Sink> = None;
 
+    // Pick this boot's background/logo/BGM for any RANDOM selections
+    config.resolve_random_picks(&background_choices, &logo_choices, &bgm_choices);
+
     // At the end of your setup, start the BGM based on the config
-    if let Some(track_name) = &config.bgm_track {
-        play_new_bgm(track_name, config.bgm_volume, &music_cache, &mut current_bgm);
+    if config.bgm_track.is_some() {
+        play_new_bgm(config.active_bgm_track(), config.bgm_volume, &music_cache, &mut current_bgm);
     }
 
     // Initialize gamepad support
     let mut gilrs = Gilrs::new().unwrap();
     let mut input_state = InputState::new();
+    input_state.apply_bindings(&config);
+    let mut rumble = rumble::Rumble::new();
     let mut animation_state = AnimationState::new();
 
     // SPLASH SCREEN
@@ -757,13 +1091,15 @@ async fn main() {
             sink.set_volume(0.0);
         }
 
-        let sink = Sink::connect_new(&AUDIO.stream.mixer());
-
         // 1. Setup Audio (Keep this exactly as you have it!)
-        let splash_bytes = include_bytes!("../splash.wav");
-        let cursor = Cursor::new(splash_bytes);
-        let source = Decoder::new(cursor).unwrap();
-        sink.append(source);
+        let splash_sink = new_sink();
+        if let Some(sink) = &splash_sink {
+            let splash_bytes = include_bytes!("../splash.wav");
+            let cursor = Cursor::new(splash_bytes);
+            if let Ok(source) = Decoder::new(cursor) {
+                sink.append(source);
+            }
+        }
 
         // 2. Setup Video
         // Embed the MP4
@@ -789,7 +1125,7 @@ async fn main() {
             // --- Input Skipping ---
             input_state.reset();
             input_state.update_keyboard();
-            input_state.update_controller(&mut gilrs);
+            input_state.update_controller(&mut gilrs, config.any_controller);
 
             if input_state.back || input_state.select {
                 break;
@@ -857,13 +1193,20 @@ async fn main() {
         next_frame().await;
     }
 
+    // The sound pack's boot.wav, if it has one (silent otherwise)
+    if config.boot_chime {
+        sound_effects.play_boot(&config);
+    }
+
     // Screen state
     let mut current_screen = Screen::MainMenu;
     let mut main_menu_selection: usize = 0;
     let mut settings_menu_selection: usize = 0;
+    let mut last_settings_screen = Screen::GeneralSettings; // page to reopen settings on, for this session
     let mut extras_menu_selection: usize = 0;
     let mut game_selection: usize = 0; // For the new menu
     let mut available_games: Vec<(save::CartInfo, PathBuf)> = Vec::new(); // To hold the list of found games
+    let mut save_location: Option<(String, Vec<String>)> = None; // (cart id, drives with its save)
     let mut play_option_enabled: bool = false;
     let mut copy_logs_option_enabled = false; // new button to copy session logs over to SD card
 
@@ -882,20 +1225,29 @@ async fn main() {
 
     // Fade state
     let mut fade_start_time: Option<f64> = None;
+    let mut reload_return_screen = Screen::ThemeDownloader; // where ReloadingThemes goes when it's done
+    let mut asset_import_progress: Option<std::sync::mpsc::Receiver<asset_import::ImportProgress>> = None;
     const FADE_DURATION: f64 = 1.0; // 1 second fade
     const FADE_LINGER_DURATION: f64 = 0.5; // 0.5 seconds to linger on black screen
 
     // Create thread-safe cart connection status
     let cart_connected = Arc::new(AtomicBool::new(false));
     let cart_check_thread_running = Arc::new(AtomicBool::new(false));
+    // Folder of the inserted cart, used to pick up its custom menu background/music
+    let cart_dir: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+    let mut cart_menu_dir: Option<PathBuf> = None;
 
     // Spawn background thread for cart connection detection (only active during main menu)
     let cart_connected_clone = cart_connected.clone();
     let cart_check_thread_running_clone = cart_check_thread_running.clone();
+    let cart_dir_clone = cart_dir.clone();
     thread::spawn(move || {
         while cart_check_thread_running_clone.load(Ordering::Relaxed) {
-            let is_connected = save::is_cart_connected();
-            cart_connected_clone.store(is_connected, Ordering::Relaxed);
+            let cart_dir = save::find_connected_cart_dir();
+            cart_connected_clone.store(cart_dir.is_some(), Ordering::Relaxed);
+            if let Ok(mut dir) = cart_dir_clone.lock() {
+                *dir = cart_dir;
+            }
             thread::sleep(time::Duration::from_secs(1));
         }
     });
@@ -918,6 +1270,23 @@ async fn main() {
             }
         }
     });
+    // HOLD BACK TO MENU
+    let mut back_hold_time = 0.0;
+    let mut back_hold_released = true;
+    const BACK_HOLD_DURATION: f32 = 1.0;
+
+    // shown once per visit to the main menu while internal storage stays low
+    let mut low_space_warning_shown = false;
+
+    // Polled so a TV plugged in mid-session can get its native resolution
+    let display_layout = |displays: &[DisplayInfo]| -> Vec<(String, i32, i32)> {
+        displays.iter().map(|d| (d.name.clone(), d.width, d.height)).collect()
+    };
+    let mut known_displays = display_layout(&get_displays());
+    let mut last_display_check = get_time();
+    const DISPLAY_CHECK_INTERVAL: f64 = 3.0;
+    // (resolution, aspect ratio) waiting for the player to accept on the main menu
+    let mut pending_resolution: Option<(&str, &str)> = None;
 
     let mut memories = Vec::new();
     let mut selected_memory = 0;
@@ -927,11 +1296,42 @@ async fn main() {
         running: false,
         should_clear_dialogs: false,
         error_message: None,
+        stalled: false,
+        generation: 0,
+        abandoned: 0,
     }));
 
+    // Made it through loading; the next boot doesn't need safe mode
+    crash::boot_finished();
+    if safe_mode {
+        flash_message = Some(("SAFE MODE: CUSTOM ASSETS SKIPPED".to_string(), FLASH_MESSAGE_DURATION));
+    }
+
+    // A session-restart launch that failed outside the BIOS only shows up as a bounce-back,
+    // so read back what the launch script recorded and explain it
+    if let Some(report) = save::take_launch_report() {
+        let lines = report.describe();
+        for line in &lines {
+            log_warn!("{}", line);
+        }
+        if report.failed() {
+            log_messages.lock().unwrap().extend(lines);
+            current_screen = Screen::Debug;
+            flash_message = Some(("THE LAST GAME FAILED TO LAUNCH".to_string(), FLASH_MESSAGE_DURATION));
+        }
+    }
+
     // BEGINNING OF MAIN LOOP
     loop {
-        let scale_factor = screen_height() / BASE_SCREEN_HEIGHT;
+        let scale_factor = ui_scale_factor(&config);
+        let screen_at_frame_start = current_screen.clone();
+
+        // WINDOW SIZE
+        // Remember the window size on desktop so it can be restored next launch
+        if DEV_MODE && !config.fullscreen && (screen_width(), screen_height()) != last_window_size {
+            last_window_size = (screen_width(), screen_height());
+            config.set_window_size(screen_width() as i32, screen_height() as i32);
+        }
 
         // FLASH TIMER
         if let Some((_message, timer)) = &mut flash_message {
@@ -948,10 +1348,155 @@ async fn main() {
             last_time_check = get_time();
         }
 
+        // THEME SCHEDULE
+        if !config.theme_schedule.is_empty() && get_time() - last_schedule_check > SCHEDULE_CHECK_INTERVAL {
+            let now = get_current_local_time(&config).naive_local();
+            let old_sfx_pack = config.sfx_pack.clone();
+            if theme::update_scheduled_theme(&mut config, &loaded_themes, now) {
+                if config.sfx_pack != old_sfx_pack {
+                    // Load quietly rather than through sfx_pack_to_reload, which plays a confirmation sound
+                    sound_effects = SoundEffects::load(&config.sfx_pack);
+                }
+                config.resolve_random_picks(&background_choices, &logo_choices, &bgm_choices);
+                play_new_bgm(config.active_bgm_track(), config.bgm_volume, &music_cache, &mut current_bgm);
+            }
+            last_schedule_check = get_time();
+        }
+
         // BATTERY
         if get_time() - last_battery_check > BATTERY_CHECK_INTERVAL {
             battery_info = get_battery_info();
             last_battery_check = get_time();
+
+            if let Some(info) = &battery_info {
+                let percentage: u32 = info.percentage.parse().unwrap_or(100);
+                let discharging = info.status == "Discharging";
+
+                if !discharging || percentage > config.battery_warning_level.max(config.battery_critical_level) {
+                    // Re-arm once plugged in or recharged
+                    battery_warning_shown = false;
+                    battery_critical_handled = false;
+                } else if percentage <= config.battery_critical_level && !battery_critical_handled {
+                    battery_critical_handled = true;
+                    battery_warning_shown = true;
+                    log_warn!("Battery critical ({}%).", percentage);
+                    if config.battery_critical_sleep {
+                        config.flush();
+                        suspend_system();
+                    } else {
+                        flash_message = Some((format!("BATTERY CRITICAL ({}%)", percentage), FLASH_MESSAGE_DURATION));
+                    }
+                } else if !battery_warning_shown {
+                    battery_warning_shown = true;
+                    log_warn!("Battery low ({}%).", percentage);
+                    flash_message = Some((format!("BATTERY LOW ({}%)", percentage), FLASH_MESSAGE_DURATION));
+                }
+            }
+        }
+
+        // DISPLAY HOTPLUG
+        // Skipped while a game is up so its window is never resized underneath it
+        if config.display_hotplug != "OFF" && game_process.is_none() && get_time() - last_display_check > DISPLAY_CHECK_INTERVAL {
+            last_display_check = get_time();
+            let displays = get_displays();
+            let layout = display_layout(&displays);
+            if layout != known_displays {
+                log_info!("Display layout changed: {:?}", layout);
+                known_displays = layout;
+                pending_resolution = chosen_display(&config, &displays)
+                    .and_then(|display| settings::native_resolution_for(display.width, display.height))
+                    .filter(|&(resolution, _)| resolution != config.resolution);
+
+                if config.display_hotplug == "AUTO" {
+                    if let Some((resolution, aspect_ratio)) = pending_resolution.take() {
+                        switch_resolution(&mut config, resolution, aspect_ratio);
+                        flash_message = Some((format!("NEW DISPLAY: USING {}", resolution), FLASH_MESSAGE_DURATION));
+                    }
+                }
+            }
+        }
+        // The offer waits for the main menu rather than interrupting whatever screen is up
+        if pending_resolution.is_some() && current_screen == Screen::MainMenu {
+            confirm_selection = 0;
+            current_screen = Screen::ConfirmResolution;
+        }
+
+        // THEME HOT RELOAD
+        if rx_theme_watch.try_recv().is_ok() {
+            theme_files_changed = true;
+        }
+        // Waits for a menu screen so a reload never cuts into a download, dialog or game
+        let reload_safe = matches!(current_screen,
+            Screen::MainMenu | Screen::GeneralSettings | Screen::AudioSettings | Screen::GuiSettings | Screen::AssetSettings | Screen::Extras);
+        // ASSET IMPORT
+        // Runs on its own thread; its messages wait for a menu screen, like a theme reload
+        let mut import_result = None;
+        if let Some(rx) = asset_import_progress.as_ref().filter(|_| reload_safe) {
+            loop {
+                match rx.try_recv() {
+                    Ok(asset_import::ImportProgress::Imported(count)) => {
+                        flash_message = Some((format!("IMPORTING ASSETS... {} FILE(S)", count), FLASH_MESSAGE_DURATION));
+                    }
+                    Ok(asset_import::ImportProgress::Done(result)) => {
+                        import_result = Some(result);
+                        break;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        import_result = Some(Err("IMPORT STOPPED".to_string()));
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some(result) = import_result {
+            asset_import_progress = None;
+            match result {
+                Ok(summary) => {
+                    // Name the first skipped file so "my music won't play" has an answer on screen
+                    let skipped = match summary.skipped.as_slice() {
+                        [] => String::new(),
+                        [only] => format!(", SKIPPED {}", only.to_uppercase()),
+                        [first, rest @ ..] => format!(", SKIPPED {} +{} MORE (SEE LOG)", first.to_uppercase(), rest.len()),
+                    };
+                    if summary.imported == 0 {
+                        flash_message = Some((format!("NO NEW ASSETS TO IMPORT{}", skipped), FLASH_MESSAGE_DURATION));
+                    } else {
+                        log_info!("Imported {} asset file(s) from USB", summary.imported);
+                        flash_message = Some((format!("IMPORTED {} FILE(S){}", summary.imported, skipped), FLASH_MESSAGE_DURATION));
+                        reload_return_screen = current_screen.clone();
+                        current_screen = Screen::ReloadingThemes;
+                        theme_files_changed = false; // the reload picks those up too
+                    }
+                }
+                Err(e) => {
+                    log_error!("Failed to import assets: {}", e);
+                    flash_message = Some((format!("COULD NOT IMPORT ASSETS: {}", e), FLASH_MESSAGE_DURATION));
+                }
+            }
+        }
+        if theme_files_changed && reload_safe {
+            theme_files_changed = false;
+            log_info!("Theme files changed on disk, reloading.");
+            flash_message = Some(("THEME FILES CHANGED, RELOADED".to_string(), FLASH_MESSAGE_DURATION));
+            reload_return_screen = current_screen.clone();
+            current_screen = Screen::ReloadingThemes;
+        }
+
+        // STORAGE
+        // The storage thread keeps free space current; try_lock so a slow scan never stalls a frame
+        if current_screen != Screen::MainMenu {
+            low_space_warning_shown = false;
+        } else if !low_space_warning_shown && config.low_space_warning_mb > 0 {
+            let internal_free = storage_state.try_lock().ok()
+                .and_then(|state| state.all_media.iter().find(|m| m.id == "internal").map(|m| m.free));
+            if let Some(free) = internal_free {
+                low_space_warning_shown = true;
+                if free < config.low_space_warning_mb {
+                    log_warn!("Internal storage low ({} MB free).", free);
+                    flash_message = Some((format!("INTERNAL STORAGE LOW ({} MB FREE)", free), FLASH_MESSAGE_DURATION));
+                }
+            }
         }
 
         // GCC
@@ -970,10 +1515,71 @@ async fn main() {
         // Update input state from both keyboard and controller
         input_state.reset();
         input_state.update_keyboard();
-        input_state.update_controller(&mut gilrs);
+        input_state.update_controller(&mut gilrs, config.any_controller);
+        if let Some(toast) = input_state.controller_toast {
+            flash_message = Some((toast.to_string(), FLASH_MESSAGE_DURATION));
+        }
+
+        // HOLD BACK TO MENU
+        // Only from menu screens: never mid-fade, mid-reload, or off the log of a running game
+        let can_jump_home = matches!(current_screen,
+            Screen::GeneralSettings | Screen::AudioSettings | Screen::GuiSettings | Screen::AssetSettings | Screen::ConfirmReset |
+            Screen::Extras | Screen::Wifi | Screen::Bluetooth | Screen::AssetManager | Screen::CdPlayer |
+            Screen::SaveData | Screen::GameSelection | Screen::About | Screen::PlaytimeStats | Screen::Credits | Screen::NoGames);
+        if !input_state.back_held {
+            back_hold_time = 0.0;
+            back_hold_released = true;
+        } else if config.hold_back_to_menu && can_jump_home && back_hold_released {
+            back_hold_time += get_frame_time();
+            if back_hold_time >= BACK_HOLD_DURATION {
+                log_info!("Back held, returning to the main menu.");
+                back_hold_released = false; // don't fire again (or back out of the menu) until it's let go
+                if dialog_state != DialogState::None {
+                    dialog_state = DialogState::None;
+                    dialogs.clear();
+                }
+                current_screen = Screen::MainMenu;
+                sound_effects.play_back(&config);
+            }
+        }
+
+        // Forget an in-place game once it quits so PLAY comes back
+        if let Some(clean) = game_process.as_mut().and_then(|game| game.exit_result()) {
+            let game = game_process.take().unwrap();
+            // The launch script records its status even when run in place; read it now so a
+            // failure is shown with this exit rather than bouncing the next boot to the BIOS
+            let report = save::take_launch_report().filter(|report| report.failed());
+            if let Some(report) = &report {
+                log_messages.lock().unwrap().extend(report.describe());
+            }
+            if !clean || report.is_some() {
+                // Crashes stay on the log so the output can be read or saved
+                log_warn!("{} exited with an error", game.title());
+                log_messages.lock().unwrap().push("\n--- GAME EXITED WITH AN ERROR ---".to_string());
+                current_screen = Screen::Debug;
+            } else if current_screen == Screen::Debug {
+                log_info!("{} exited, going to {}", game.title(), config.on_game_exit);
+                log_messages.lock().unwrap().push("\n--- GAME EXITED ---".to_string());
+                debug_scroll_offset = 0;
+                match config.on_game_exit.as_str() {
+                    "RELAUNCH" => {
+                        current_screen = launch_in_place(&game.cart, &game.kzi_path, &log_messages, &mut game_process);
+                    }
+                    "GAME SELECTION" if !available_games.is_empty() => current_screen = Screen::GameSelection,
+                    _ => {
+                        current_screen = Screen::MainMenu;
+                        main_menu_selection = 1;
+                    }
+                }
+            } else {
+                // The user is busy elsewhere in the menu; just say so
+                log_info!("{} exited", game.title());
+                flash_message = Some((format!("{} EXITED", game.title().to_uppercase()), FLASH_MESSAGE_DURATION));
+            }
+        }
 
         // Update animations
-        animation_state.update_shake(get_frame_time());
+        animation_state.update_shake(get_frame_time(), &config);
         animation_state.update_cursor_animation(get_frame_time(), &config.cursor_blink_speed);
         animation_state.update_dialog_transition(get_frame_time());
 
@@ -986,10 +1592,14 @@ async fn main() {
             cart_check_thread_running.store(true, Ordering::Relaxed);
             let cart_connected_clone = cart_connected.clone();
             let cart_check_thread_running_clone = cart_check_thread_running.clone();
+            let cart_dir_clone = cart_dir.clone();
             thread::spawn(move || {
                 while cart_check_thread_running_clone.load(Ordering::Relaxed) {
-                    let is_connected = save::is_cart_connected();
-                    cart_connected_clone.store(is_connected, Ordering::Relaxed);
+                    let cart_dir = save::find_connected_cart_dir();
+                    cart_connected_clone.store(cart_dir.is_some(), Ordering::Relaxed);
+                    if let Ok(mut dir) = cart_dir_clone.lock() {
+                        *dir = cart_dir;
+                    }
                     thread::sleep(time::Duration::from_secs(1));
                 }
             });
@@ -998,6 +1608,15 @@ async fn main() {
             cart_check_thread_running.store(false, Ordering::Relaxed);
         }
 
+        // Theme the menu to the inserted cart (if it ships menu_background.png / menu_bgm.ogg)
+        if should_thread_run {
+            let detected_dir = cart_dir.lock().ok().and_then(|dir| dir.clone());
+            if detected_dir != cart_menu_dir {
+                apply_cart_menu_assets(detected_dir.as_deref(), &mut config, &mut background_cache, &mut music_cache, &mut current_bgm).await;
+                cart_menu_dir = detected_dir;
+            }
+        }
+
         // Update dialog state based on animation
         if animation_state.dialog_transition_time <= 0.0 {
             match dialog_state {
@@ -1012,11 +1631,27 @@ async fn main() {
             }
         }
 
+        // The missing-prerequisites warning sits on top of the main menu and eats input until dismissed
+        let showing_prereq_warning = prereq_warning.is_some() && current_screen == Screen::MainMenu;
+        if showing_prereq_warning {
+            if input_state.select || input_state.back {
+                dismiss_prerequisite_warning(&missing_deps);
+                prereq_warning = None;
+                sound_effects.play_select(&config);
+            }
+            input_state.reset();
+        }
+
         // Handle screen-specific rendering and input
         match current_screen {
             Screen::About => {
+                let playtime = *total_playtime.get_or_insert_with(|| memory::get_total_playtime(&mut playtime_cache));
+
                 // Tell the about module to handle its own logic
                 ui::about::update(&input_state, &mut current_screen, &sound_effects, &config);
+                if current_screen != Screen::About {
+                    total_playtime = None; // recount on the next visit, saves may have changed
+                }
 
                 // Tell the about module to draw itself
                 ui::about::draw(
@@ -1030,6 +1665,7 @@ async fn main() {
                     &battery_info,
                     &current_time_str,
                     &app_state.gcc_adapter_poll_rate,
+                    playtime,
                     scale_factor,
                 );
             }
@@ -1056,6 +1692,7 @@ async fn main() {
                     &mut game_selection,
                     &mut flash_message,
                     &mut game_process,
+                    &mut no_games_reason,
                 );
 
                 // Calculate fade progress
@@ -1098,10 +1735,16 @@ async fn main() {
                     &mut game_selection,
                     &mut flash_message,
                     &mut game_process,
+                    &mut no_games_reason,
                 );
 
+                // Reopen settings on the page (and row) we last left it on
+                if current_screen == Screen::GeneralSettings {
+                    current_screen = last_settings_screen.clone();
+                }
+
                 ui::main_menu::draw(
-                    &MAIN_MENU_OPTIONS,
+                    &ui::main_menu::menu_options(game_process.is_some()),
                     main_menu_selection,
                     play_option_enabled,
                     copy_logs_option_enabled,
@@ -1128,6 +1771,7 @@ async fn main() {
                     Screen::AssetSettings => (4, ui::settings::CUSTOM_ASSET_SETTINGS),
                     _ => (0, &[] as &[&str]),
                 };
+                last_settings_screen = current_screen.clone();
 
                 // --- Handle input and state changes ---
                 ui::settings::update(
@@ -1149,7 +1793,7 @@ async fn main() {
                 }
             },
             Screen::Extras => {
-                ui::extras_menu::update(
+                let extras_action = ui::extras_menu::update(
                     &mut current_screen,
                     &mut extras_menu_selection,
                     &input_state,
@@ -1158,6 +1802,76 @@ async fn main() {
                     &config,
                 );
 
+                match extras_action {
+                    Some(ui::extras_menu::ExtrasAction::ExportSetup) => match snapshot::export(&config) {
+                        Ok(path) => {
+                            log_info!("Setup snapshot written to {}", path.display());
+                            flash_message = Some((format!("SETUP SAVED TO {}", path.display()), FLASH_MESSAGE_DURATION));
+                        }
+                        Err(e) => {
+                            log_error!("Failed to export setup: {}", e);
+                            flash_message = Some(("COULD NOT EXPORT SETUP".to_string(), FLASH_MESSAGE_DURATION));
+                        }
+                    },
+                    Some(ui::extras_menu::ExtrasAction::ImportSetup) => {
+                        let known = snapshot::KnownAssets {
+                            themes: &loaded_themes,
+                            backgrounds: &background_choices,
+                            logos: &logo_choices,
+                            fonts: &font_choices,
+                            bgm_tracks: &bgm_choices,
+                            sfx_packs: &sound_pack_choices,
+                        };
+                        let previous_sfx_pack = config.sfx_pack.clone();
+                        match snapshot::import_latest(&mut config, &known) {
+                            Ok((path, missing)) => {
+                                log_info!("Imported setup from {}", path.display());
+                                for asset in &missing {
+                                    log_warn!("Snapshot refers to missing asset: {}", asset);
+                                }
+                                // Bring the running BIOS in line with the imported settings
+                                config.resolve_random_picks(&background_choices, &logo_choices, &bgm_choices);
+                                play_new_bgm(config.active_bgm_track(), config.bgm_volume, &music_cache, &mut current_bgm);
+                                if config.sfx_pack != previous_sfx_pack {
+                                    sfx_pack_to_reload = Some(config.sfx_pack.clone());
+                                }
+                                let message = if missing.is_empty() {
+                                    "SETUP IMPORTED".to_string()
+                                } else {
+                                    format!("SETUP IMPORTED, {} MISSING ASSET(S) RESET", missing.len())
+                                };
+                                flash_message = Some((message, FLASH_MESSAGE_DURATION));
+                            }
+                            Err(e) => {
+                                log_error!("Failed to import setup: {}", e);
+                                flash_message = Some((format!("COULD NOT IMPORT SETUP: {}", e), FLASH_MESSAGE_DURATION));
+                            }
+                        }
+                    }
+                    Some(ui::extras_menu::ExtrasAction::ExportDiagnostics) => {
+                        log_info!("Exporting diagnostics...");
+                        match diagnostics::export() {
+                            Ok(path) => {
+                                log_info!("Diagnostics written to {}", path.display());
+                                flash_message = Some((format!("DIAGNOSTICS SAVED TO {}", path.display()), FLASH_MESSAGE_DURATION));
+                            }
+                            Err(e) => {
+                                log_error!("Failed to export diagnostics: {}", e);
+                                flash_message = Some((format!("COULD NOT EXPORT DIAGNOSTICS: {}", e), FLASH_MESSAGE_DURATION));
+                            }
+                        }
+                    }
+                    Some(ui::extras_menu::ExtrasAction::ImportAssets) => {
+                        if asset_import_progress.is_some() {
+                            flash_message = Some(("AN IMPORT IS ALREADY RUNNING".to_string(), FLASH_MESSAGE_DURATION));
+                        } else {
+                            asset_import_progress = Some(asset_import::spawn_import_from_usb());
+                            flash_message = Some(("IMPORTING ASSETS...".to_string(), FLASH_MESSAGE_DURATION));
+                        }
+                    }
+                    None => {}
+                }
+
                 ui::extras_menu::draw(
                     extras_menu_selection,
                     &animation_state,
@@ -1188,82 +1902,86 @@ async fn main() {
                     } else {
                         // LOAD FROM DISK (Standard behavior)
                         // load_texture IS async and returns a Result, so we keep the check here
-                        if let Ok(texture) = load_texture(&icon_path.to_string_lossy()).await {
-                            game_icon_cache.insert(game_id, texture);
+                        match load_texture(&icon_path.to_string_lossy()).await {
+                            Ok(texture) => {
+                                ui::load_icon_animation(&game_id, &icon_path);
+                                game_icon_cache.insert(game_id, texture);
+                            }
+                            // Keeps the placeholder, with a mark so the bad file gets noticed
+                            Err(e) if icon_path.exists() => ui::mark_icon_broken(&game_id, &icon_path, &e.to_string()),
+                            Err(_) => {}
                         }
                     }
                 }
-                let grid_width = 5; // The number of icons per row
-                if input_state.left {
-                    if game_selection > 0 {
-                        game_selection -= 1;
+                let grid_width = ui::GAME_GRID_WIDTH; // The number of icons per row
+                // The list only moves up and down, the carousel only left and right
+                let (step_forward, step_back) = match config.game_selection_view() {
+                    GameSelectionView::List => (input_state.down, input_state.up),
+                    GameSelectionView::Grid | GameSelectionView::Carousel => (input_state.right, input_state.left),
+                };
+                if step_forward || step_back {
+                    let next = step_selection(game_selection, available_games.len(), step_forward, config.menu_wrap);
+                    if next != game_selection {
+                        game_selection = next;
                         sound_effects.play_cursor_move(&config);
                     }
                 }
-                if input_state.right {
-                    if game_selection < available_games.len() - 1 {
-                        game_selection += 1;
-                        sound_effects.play_cursor_move(&config);
+                // LB/RB flip a page, keeping the same spot on it where the new page is long enough.
+                // The carousel has no pages.
+                let page_size = ui::game_selection_page_size(&config, scale_factor);
+                if let Some(page_size) = page_size.filter(|_| input_state.next || input_state.prev) {
+                    let pages = available_games.len().div_ceil(page_size);
+                    if pages > 1 {
+                        let page = step_selection(game_selection / page_size, pages, input_state.next, config.menu_wrap);
+                        let next = (page * page_size + game_selection % page_size).min(available_games.len() - 1);
+                        if next != game_selection {
+                            game_selection = next;
+                            sound_effects.play_cursor_move(&config);
+                        }
                     }
                 }
-                if input_state.up {
-                    if game_selection >= grid_width {
-                        game_selection -= grid_width;
-                        sound_effects.play_cursor_move(&config);
+                if config.game_selection_view() == GameSelectionView::Grid {
+                    if input_state.up {
+                        if game_selection >= grid_width {
+                            game_selection -= grid_width;
+                            sound_effects.play_cursor_move(&config);
+                        }
                     }
-                }
-                if input_state.down {
-                    if game_selection + grid_width < available_games.len() {
-                        game_selection += grid_width;
-                        sound_effects.play_cursor_move(&config);
+                    if input_state.down {
+                        if game_selection + grid_width < available_games.len() {
+                            game_selection += grid_width;
+                            sound_effects.play_cursor_move(&config);
+                        }
                     }
                 }
                 if input_state.back {
                     current_screen = Screen::MainMenu;
+                    save_location = None; // saves may be copied around before we come back
                     sound_effects.play_back(&config);
                 }
                 if input_state.select {
                     if let Some((cart_info, kzi_path)) = available_games.get(game_selection) {
-                        sound_effects.play_select(&config);
+                        sound_effects.play_launch(&config);
+
+                        log_messages.lock().unwrap().clear();
+                        (current_screen, fade_start_time) = trigger_game_launch(
+                            cart_info,
+                            kzi_path,
+                            config.launch_mode(),
+                            &mut current_bgm,
+                            &music_cache,
+                            &log_messages,
+                            &mut game_process,
+                        );
+                    }
+                }
 
-                        if DEV_MODE {
-                            // --- DEBUG MODE ---
-                            log_messages.lock().unwrap().clear();
-                            { // Scoped lock to add messages
-                                let mut logs = log_messages.lock().unwrap();
-                                logs.push("--- CARTRIDGE FOUND ---".to_string());
-                                logs.push(format!("Name: {}", cart_info.name.as_deref().unwrap_or("N/A")));
-                                logs.push(format!("ID: {}", cart_info.id));
-                                logs.push(format!("Exec: {}", cart_info.exec));
-                                logs.push(format!("Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None")));
-                                logs.push(format!("KZI Path: {}", kzi_path.display()));
-                            }
-                            println!("[Debug] Single Cartridge Found! Preparing to launch...");
-                            println!("[Debug]   Name: {}", cart_info.name.as_deref().unwrap_or("N/A"));
-                            println!("[Debug]   ID: {}", cart_info.id);
-                            println!("[Debug]   Exec: {}", cart_info.exec);
-                            println!("[Debug]   Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None"));
-                            println!("[Debug]   KZI Path: {}", kzi_path.display());
-
-                            match save::launch_game(&cart_info, &kzi_path) {
-                                Ok(mut child) => {
-                                    log_messages.lock().unwrap().push("\n--- LAUNCHING GAME ---".to_string());
-                                    start_log_reader(&mut child, log_messages.clone());
-                                    game_process = Some(child);
-                                }
-                                Err(e) => {
-                                    log_messages.lock().unwrap().push(format!("\n--- LAUNCH FAILED ---\nError: {}", e));
-                                }
-                            }
-                            current_screen = Screen::Debug;
-                        } else {
-                            // Instead of just restarting, we now trigger a specific game launch.
-                            (current_screen, fade_start_time) = trigger_game_launch(
-                                cart_info,
-                                kzi_path,
-                                &mut current_bgm,
-                                &music_cache
-                            );
+                // Look up the highlighted game's save location whenever the highlight moves
+                if let Some((cart_info, _)) = available_games.get(game_selection) {
+                    if save_location.as_ref().is_none_or(|(id, _)| *id != cart_info.id) {
+                        // Skipped for a frame if the storage poll holds the lock; the next frame retries
+                        if let Ok(state) = storage_state.try_lock() {
+                            save_location = Some((cart_info.id.clone(), save::find_save_drives(&cart_info.id, &state.all_media)));
                         }
                     }
                 }
@@ -1272,13 +1990,11 @@ async fn main() {
                 render_game_selection_menu(
                     &available_games, &game_icon_cache, &placeholder, game_selection, &animation_state, &logo_cache,
                     &background_cache, &mut video_cache, &font_cache, &config, &mut background_state,
-                    &battery_info, &current_time_str, &app_state.gcc_adapter_poll_rate, scale_factor
+                    &battery_info, &current_time_str, &app_state.gcc_adapter_poll_rate,
+                    save_location.as_ref().map(|(_, drives)| drives.as_slice()), scale_factor
                 );
             },
             Screen::Debug => {
-                // Stop the BGM
-                play_new_bgm("OFF", 0.0, &music_cache, &mut current_bgm);
-
                 let messages = log_messages.lock().unwrap();
 
                 // INPUT
@@ -1305,12 +2021,17 @@ async fn main() {
                 }
                 if input_state.back {
                     // If the user presses back, kill the game process and return to the menu
-                    if let Some(mut child) = game_process.take() {
-                        child.kill().ok(); // Ignore error if process already exited
+                    if let Some(mut game) = game_process.take() {
+                        game.process.kill().ok(); // Ignore error if process already exited
                     }
                     current_screen = Screen::MainMenu;
                     sound_effects.play_back(&config);
                     debug_scroll_offset = 0;
+                } else if input_state.secondary && game_process.is_some() {
+                    // Leave the game running; the main menu offers RETURN TO GAME in place of PLAY
+                    current_screen = Screen::MainMenu;
+                    main_menu_selection = 1;
+                    sound_effects.play_back(&config);
                 }
 
                 // --- Update flash message timer ---
@@ -1327,6 +2048,7 @@ async fn main() {
                     &messages,
                     debug_scroll_offset,
                     flash_message.as_ref().map(|(msg, _)| msg.as_str()), // Pass the message text
+                    game_process.is_some(),
                     &font_cache,
                     &config,
                     scale_factor,
@@ -1345,11 +2067,26 @@ async fn main() {
                     current_screen = Screen::GeneralSettings; // Or whatever page you came from
                     sound_effects.play_back(&config);
                 }
-                if input_state.select {
+                // With hold-to-confirm on, YES only fires once the button has been held long enough
+                let confirmed = if config.hold_to_confirm && confirm_selection == 0 {
+                    animation_state.update_hold(input_state.select_held, get_frame_time())
+                } else {
+                    animation_state.update_hold(false, 0.0);
+                    input_state.select
+                };
+                if confirmed {
                     if confirm_selection == 0 { // User selected YES
                         //if let Err(e) = delete_config_file() {
                         if let Err(e) = Config::delete() {
-                            println!("[ERROR] Failed to delete config file: {}", e);
+                            log_error!("Failed to delete config file: {}", e);
+                        }
+                        // Write the defaults straight away so the reset is on disk before the restart.
+                        // The file exists again afterwards, so do the first boot's display detection now.
+                        let mut defaults = Config::default();
+                        defaults.use_native_resolution(detect_native_resolution());
+                        reset_saved = defaults.save();
+                        if reset_saved {
+                            log_info!("Config file recreated with default settings.");
                         }
                         current_screen = Screen::ResetComplete;
                         sound_effects.play_select(&config);
@@ -1368,8 +2105,13 @@ async fn main() {
                     scale_factor, system_volume, brightness,
                 );
                 // Then, render the dialog box on top
+                let reset_prompt = if config.hold_to_confirm {
+                    format!("Reset all settings to default?\nHold {} on YES to confirm.", ui::action_label(&Bindings::from_config(&config), &[Action::Select]))
+                } else {
+                    "Reset all settings to default?\nThis cannot be undone.".to_string()
+                };
                 render_dialog_box(
-                    "Reset all settings to default?\nThis cannot be undone.",
+                    &reset_prompt,
                     Some(("YES", "NO")), // Options to display
                     confirm_selection,  // Which option is selected
                     &font_cache, &config, scale_factor, &animation_state,
@@ -1391,27 +2133,57 @@ async fn main() {
                 );
 
                 render_dialog_box(
-                    "Settings have been reset.\nRestart required.",
+                    if reset_saved {
+                        "Settings have been reset and saved.\nRestart required."
+                    } else {
+                        "Settings have been reset, but could not\nbe saved. Restart required."
+                    },
                     None, // No YES/NO options needed
                     0,
                     &font_cache, &config, scale_factor, &animation_state,
                 );
             },
-            Screen::SaveData => {
-                // Process one item from the icon queue each frame to prevent stuttering.
-                if !icon_queue.is_empty() {
-                    let (save_id, icon_path_str) = icon_queue.remove(0);
-                    if let Ok(texture) = load_texture(&icon_path_str).await {
-                        icon_cache.insert(save_id, texture);
+            Screen::ConfirmResolution => {
+                // --- Input Handling ---
+                if input_state.left || input_state.right {
+                    confirm_selection = 1 - confirm_selection;
+                    sound_effects.play_cursor_move(&config);
+                }
+                if input_state.select || input_state.back {
+                    match pending_resolution.take() {
+                        Some((resolution, aspect_ratio)) if input_state.select && confirm_selection == 0 => {
+                            switch_resolution(&mut config, resolution, aspect_ratio);
+                            sound_effects.play_select(&config);
+                        }
+                        _ => {
+                            log_info!("Kept {} after the display change.", config.resolution);
+                            sound_effects.play_back(&config);
+                        }
                     }
+                    current_screen = Screen::MainMenu;
                 }
 
-                ui::data::update(
+                // --- Render ---
+                render_background(&background_cache, &mut video_cache, &config, &mut background_state);
+                if let Some((resolution, _)) = pending_resolution {
+                    render_dialog_box(
+                        &format!("New display detected.\nSwitch to {}?", resolution),
+                        Some(("YES", "NO")),
+                        confirm_selection,
+                        &font_cache, &config, scale_factor, &animation_state,
+                    );
+                }
+            },
+            Screen::SaveData => {
+                if let Some(message) = ui::data::update(
                     &mut input_state, &mut current_screen, &sound_effects, &config,
                     &storage_state, &mut memories, &mut icon_cache, &mut icon_queue,
                     &mut selected_memory, &mut scroll_offset, &mut dialogs, &mut dialog_state, &mut animation_state,
-                    scale_factor, &copy_op_state
-                ).await;
+                    scale_factor, &copy_op_state, &mut playtime_cache, &mut size_cache,
+                    game_process.as_ref().map(|game| game.cart.id.as_str())
+                ).await {
+                    flash_message = Some((message, FLASH_MESSAGE_DURATION));
+                }
 
                 render_background(&background_cache, &mut video_cache, &config, &mut background_state);
 
@@ -1497,33 +2269,144 @@ async fn main() {
                     scale_factor,
                 );
             }
+            Screen::PlaytimeStats => {
+                ui::playtime_stats::update(
+                    &mut playtime_stats_state,
+                    &input_state,
+                    &mut current_screen,
+                    &sound_effects,
+                    &config,
+                );
+                ui::playtime_stats::draw(
+                    &playtime_stats_state,
+                    &background_cache,
+                    &mut video_cache,
+                    &font_cache,
+                    &config,
+                    &mut background_state,
+                    scale_factor,
+                );
+            }
+            Screen::Credits => {
+                ui::credits::update(
+                    &mut credits_state,
+                    &input_state,
+                    &mut current_screen,
+                    &sound_effects,
+                    &config,
+                );
+                ui::credits::draw(
+                    &credits_state,
+                    &background_cache,
+                    &mut video_cache,
+                    &font_cache,
+                    &config,
+                    &mut background_state,
+                    scale_factor,
+                );
+            }
+            Screen::ButtonMapping => {
+                ui::button_mapping::update(
+                    &mut button_mapping_state,
+                    &mut input_state,
+                    &mut current_screen,
+                    &sound_effects,
+                    &mut config,
+                );
+                ui::button_mapping::draw(
+                    &button_mapping_state,
+                    &background_cache,
+                    &mut video_cache,
+                    &font_cache,
+                    &config,
+                    &mut background_state,
+                    scale_factor,
+                );
+            }
+            Screen::NoGames => {
+                ui::no_games::update(
+                    &input_state,
+                    &mut current_screen,
+                    &sound_effects,
+                    &config,
+                );
+                ui::no_games::draw(
+                    no_games_reason,
+                    &background_cache,
+                    &mut video_cache,
+                    &font_cache,
+                    &config,
+                    &mut background_state,
+                    scale_factor,
+                );
+            }
+            Screen::AssetManager => {
+                if ui::asset_manager::update(
+                    &mut asset_manager_state,
+                    &input_state,
+                    &mut current_screen,
+                    &sound_effects,
+                    &config,
+                ) {
+                    reload_return_screen = Screen::AssetManager;
+                    current_screen = Screen::ReloadingThemes;
+                }
+                ui::asset_manager::draw(
+                    &asset_manager_state,
+                    &mut animation_state,
+                    &background_cache,
+                    &mut video_cache,
+                    &font_cache,
+                    &config,
+                    &mut background_state,
+                    scale_factor,
+                );
+            }
             Screen::ReloadingThemes => {
                 next_frame().await;
 
                 // 1. Re-run the theme loading function
-                loaded_themes = theme::load_all_themes().await;
+                if !safe_mode {
+                    loaded_themes = theme::load_all_themes().await;
+                }
 
                 // 2. Re-scan all asset directories to find the new files
-                let (background_files, logo_files, font_files, music_files) = find_all_asset_files();
-
-                // --- Define a new message for reloading ---
-                let reloading_text = "APPLYING NEW THEME ASSETS...";
-
-                // 3. Re-load all assets and assign them to the original mutable caches
-                (background_cache, video_cache, logo_cache, music_cache, font_cache, sound_effects) =
-                load_all_assets(
-                    &config,
-                    reloading_text,
-                    &startup_font,
-                    &background_files,
-                    &logo_files,
-                    &font_files,
-                    &music_files,
-                    scale_factor,
-                ).await;
+                config.skip_unloaded_assets();
+                let (background_files, logo_files, font_files, music_files) = find_all_asset_files(&config, safe_mode);
+                let fingerprint = asset_fingerprint(background_files.iter().chain(&logo_files).chain(&font_files).chain(&music_files));
+
+                if fingerprint == loaded_asset_fingerprint {
+                    // Same files as last time: the caches already hold everything, only the selection may differ
+                    log_info!("Asset files unchanged, skipping the full reload.");
+                    sound_effects = SoundEffects::load(&config.sfx_pack);
+                } else {
+                    // --- Define a new message for reloading ---
+                    let reloading_text = "APPLYING NEW THEME ASSETS...";
+
+                    // 3. Re-load all assets and assign them to the original mutable caches
+                    (background_cache, video_cache, logo_cache, music_cache, font_cache, sound_effects) =
+                    load_all_assets(
+                        &config,
+                        reloading_text,
+                        &startup_font,
+                        &background_files,
+                        &logo_files,
+                        &font_files,
+                        &music_files,
+                        scale_factor,
+                    ).await;
+                    loaded_asset_fingerprint = fingerprint;
+                    (background_choices, logo_choices, font_choices, bgm_choices) =
+                        asset_choices(&background_cache, &video_cache, &logo_cache, &font_cache, &music_cache, &music_files);
+                    config.resolve_random_picks(&background_choices, &logo_choices, &bgm_choices);
+
+                    // The reload dropped any cart menu assets, pick them up again on the main menu
+                    config.set_cart_overrides(None, None);
+                    cart_menu_dir = None;
+                }
 
-                // 4. After reloading, go back to the downloader screen
-                current_screen = Screen::ThemeDownloader;
+                // 4. After reloading, go back to where the reload was asked for (the downloader, unless set otherwise)
+                current_screen = std::mem::replace(&mut reload_return_screen, Screen::ThemeDownloader);
             }
             Screen::RuntimeDownloader => {
                 ui::runtime_downloader::update(
@@ -1585,14 +2468,87 @@ async fn main() {
             }
         }
 
+        if config.show_help_bar {
+            ui::help_bar(&ui::help_bar_actions(&current_screen, &config), &font_cache, &config, scale_factor);
+        }
+
+        // The main menu and debug screen draw the flash message themselves
+        if !matches!(current_screen, Screen::MainMenu | Screen::Debug) {
+            if let Some((message, _)) = &flash_message {
+                ui::render_flash_message(message, &font_cache, &config, scale_factor);
+            }
+        }
+
+        if showing_prereq_warning {
+            if let Some(message) = &prereq_warning {
+                // Named after whatever SELECT is bound to, like the help bar
+                let continue_button = ui::action_label(&Bindings::from_config(&config), &[Action::Select]);
+                render_dialog_box(
+                    &format!("{}\nPRESS {} TO CONTINUE", message, continue_button),
+                    None,
+                    0,
+                    &font_cache, &config, scale_factor, &animation_state,
+                );
+            }
+        }
+
+        // SCREENSHOT
+        // Taken after everything is drawn, so it captures exactly what's on screen
+        if input_state.screenshot {
+            match take_screenshot() {
+                Ok(path) => {
+                    log_info!("Screenshot saved to {}", path.display());
+                    flash_message = Some(("SCREENSHOT SAVED".to_string(), FLASH_MESSAGE_DURATION));
+                }
+                Err(e) => {
+                    log_error!("Failed to save screenshot: {}", e);
+                    flash_message = Some(("SCREENSHOT FAILED".to_string(), FLASH_MESSAGE_DURATION));
+                }
+            }
+        }
+
+        // Play any rumble pulse the UI asked for this frame
+        rumble.update(&mut gilrs);
+
         // This block checks if the settings screen requested an SFX reload
         if let Some(pack_name) = sfx_pack_to_reload.take() {
-            println!("[Info] Reloading SFX pack: {}", pack_name);
+            log_info!("Reloading SFX pack: {}", pack_name);
             //sound_effects = SoundEffects::load(&pack_name).await;
             sound_effects = SoundEffects::load(&pack_name);
             // Play a sound from the new pack to confirm it changed
             sound_effects.play_cursor_move(&config);
         }
+
+        // BGM around the debug screen, which an in-place game plays its own audio over
+        if current_screen != screen_at_frame_start {
+            const BGM_DUCK_VOLUME: f32 = 0.25; // fraction of bgm_volume kept while ducked
+            if current_screen == Screen::Debug {
+                match config.bgm_on_debug.as_str() {
+                    "STOP" => play_new_bgm("OFF", 0.0, &music_cache, &mut current_bgm),
+                    "DUCK" => if let Some(sink) = &current_bgm {
+                        sink.set_volume(config.bgm_volume * BGM_DUCK_VOLUME);
+                    },
+                    _ => {}
+                }
+            } else if screen_at_frame_start == Screen::Debug {
+                match &current_bgm {
+                    Some(sink) => sink.set_volume(config.bgm_volume),
+                    None => play_new_bgm(config.active_bgm_track(), config.bgm_volume, &music_cache, &mut current_bgm),
+                }
+            }
+        }
+
+        // Persist settings changes: right away when leaving a screen, otherwise debounced
+        let saved = if current_screen != screen_at_frame_start {
+            config.flush()
+        } else {
+            config.save_if_due()
+        };
+        // Kept short, and never over a message that's already showing
+        if saved && config.save_toasts && flash_message.is_none() {
+            flash_message = Some(("SETTINGS SAVED".to_string(), SAVE_TOAST_DURATION));
+        }
+
         next_frame().await
     }
 }
@@ -1,13 +1,18 @@
-use crate::{Memory, StorageMedia, save, CopyOperationState, PlaytimeCache, SizeCache};
+use crate::{Memory, StorageMedia, save::{self, SaveBackend}, CopyOperationState, PlaytimeCache, SizeCache};
 use std::sync::{Arc, Mutex, atomic::{AtomicU16, Ordering}};
-use std::{thread, time};
+use std::{thread, time::{self, Instant}};
 use std::collections::HashMap;
 use macroquad::prelude::*; // For Texture2D if needed by structs
 
 pub async fn load_memories(media: &StorageMedia, cache: &mut HashMap<String, Texture2D>, queue: &mut Vec<(String, String)>) -> Vec<Memory> {
+    load_memories_from(&save::SystemSaveBackend, media, cache, queue)
+}
+
+/// load_memories() against any save backend
+pub fn load_memories_from(backend: &impl SaveBackend, media: &StorageMedia, cache: &mut HashMap<String, Texture2D>, queue: &mut Vec<(String, String)>) -> Vec<Memory> {
     let mut memories = Vec::new();
 
-    if let Ok(details) = save::get_save_details(&media.id) {
+    if let Ok(details) = backend.get_save_details(&media.id) {
         for (cart_id, name, icon_path) in details {
             if !cache.contains_key(&cart_id) {
                 queue.push((cart_id.clone(), icon_path.clone()));
@@ -30,12 +35,19 @@ pub async fn check_save_exists(memory: &Memory, target_media: &StorageMedia, ico
     target_memories.iter().any(|m| m.id == memory.id)
 }
 
+/// How long a copy can go without progress before the UI offers to stop waiting
+const COPY_STALL_TIMEOUT: time::Duration = time::Duration::from_secs(15);
+
 pub fn copy_memory(memory: &Memory, from_media: &StorageMedia, to_media: &StorageMedia, state: Arc<Mutex<CopyOperationState>>) {
     // Initialize the copy operation state
+    let mut generation = 0;
     if let Ok(mut copy_state) = state.lock() {
         copy_state.progress = 0;
         copy_state.running = true;
         copy_state.error_message = None;
+        copy_state.stalled = false;
+        copy_state.generation = copy_state.generation.wrapping_add(1);
+        generation = copy_state.generation;
     }
 
     // Small delay to show the operation has started
@@ -48,14 +60,26 @@ pub fn copy_memory(memory: &Memory, from_media: &StorageMedia, to_media: &Storag
 
     // Spawn a thread to monitor progress from the copy operation
     let monitor_handle = thread::spawn(move || {
+        // Watchdog: when the progress stops moving, flag a possible stall
+        let mut last_progress = 0;
+        let mut last_change = Instant::now();
         loop {
             let current_progress = progress_clone.load(Ordering::SeqCst);
+            if current_progress != last_progress {
+                last_progress = current_progress;
+                last_change = Instant::now();
+            }
+            let stalled = last_change.elapsed() >= COPY_STALL_TIMEOUT;
 
             // Update the UI state with the current progress
             if let Ok(mut copy_state) = state_clone.lock() {
-                // Only update if the operation is still running
-                if copy_state.running {
+                // Only update if the operation is still running (and wasn't given up on)
+                if copy_state.running && copy_state.generation == generation {
                     copy_state.progress = current_progress;
+                    if stalled && !copy_state.stalled {
+                        log_warn!("Save copy has made no progress for {}s (at {}%).", COPY_STALL_TIMEOUT.as_secs(), current_progress);
+                    }
+                    copy_state.stalled = stalled;
                 } else {
                     // Operation completed, exit the monitoring loop
                     break;
@@ -74,6 +98,23 @@ pub fn copy_memory(memory: &Memory, from_media: &StorageMedia, to_media: &Storag
     // Perform the actual copy operation
     let copy_result = save::copy_save(&memory.id, &from_media.id, &to_media.id, progress);
 
+    // Given up on while stalled: the UI has moved on, so only log how it ended
+    let given_up = state.lock().map(|mut copy_state| {
+        let given_up = copy_state.generation != generation;
+        if given_up {
+            copy_state.abandoned = copy_state.abandoned.saturating_sub(1);
+        }
+        given_up
+    }).unwrap_or(false);
+    if given_up {
+        match &copy_result {
+            Ok(_) => log_info!("A save copy that was given up on has finished after all ({}).", memory.id),
+            Err(e) => log_error!("A save copy that was given up on failed ({}): {}", memory.id, e),
+        }
+        monitor_handle.join().ok();
+        return;
+    }
+
     // Handle the result
     match copy_result {
         Ok(_) => {
@@ -133,3 +174,56 @@ pub fn get_game_size(memory: &Memory, size_cache: &mut SizeCache) -> f32 {
         calculated_size
     }
 }
+
+/// Sum of the playtime of every save on every drive, in hours. Each save's playtime
+/// goes through the playtime cache, so only saves not seen before get read from disk.
+pub fn get_total_playtime(playtime_cache: &mut PlaytimeCache) -> f32 {
+    let drives = save::list_devices().unwrap_or_default();
+    let mut total = 0.0;
+
+    for (drive_name, _, _) in drives {
+        let Ok(details) = save::get_save_details(&drive_name) else { continue };
+        for (cart_id, _, _) in details {
+            let memory = Memory { id: cart_id, name: None, drive_name: drive_name.clone() };
+            total += get_game_playtime(&memory, playtime_cache);
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::FakeSaveBackend;
+
+    fn drive(id: &str) -> StorageMedia {
+        StorageMedia { id: id.to_string(), free: 1024, read_only: false }
+    }
+
+    #[test]
+    fn loads_memories_and_queues_missing_icons() {
+        let mut backend = FakeSaveBackend::default();
+        backend.add_drive("sdcard", &[("pong", "Pong"), ("tetris", "Tetris")]);
+        let mut cache = HashMap::new();
+        let mut queue = Vec::new();
+
+        let memories = load_memories_from(&backend, &drive("sdcard"), &mut cache, &mut queue);
+        let ids: Vec<&str> = memories.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["pong", "tetris"]);
+        assert_eq!(memories[0].name.as_deref(), Some("Pong"));
+        assert!(memories.iter().all(|m| m.drive_name == "sdcard"));
+        assert_eq!(queue, [
+            ("pong".to_string(), "/sdcard/pong.png".to_string()),
+            ("tetris".to_string(), "/sdcard/tetris.png".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn loads_nothing_from_an_unknown_drive() {
+        let backend = FakeSaveBackend::default();
+        let mut queue = Vec::new();
+        assert!(load_memories_from(&backend, &drive("usb"), &mut HashMap::new(), &mut queue).is_empty());
+        assert!(queue.is_empty());
+    }
+}
+
@@ -0,0 +1,86 @@
+use gilrs::{
+    ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks},
+    Gilrs,
+};
+use std::sync::Mutex;
+
+use crate::config::Config;
+
+// Pulse requested by the UI this frame: (strength 0.0-1.0, duration in ms).
+// Sounds are played from all over the UI without access to gilrs, so they only queue a pulse
+// and the main loop plays it.
+static PENDING_PULSE: Mutex<Option<(f32, u32)>> = Mutex::new(None);
+
+const SELECT_PULSE_MS: u32 = 40;
+const REJECT_PULSE_MS: u32 = 90;
+
+/// Short "click" on selection, if rumble is enabled
+pub fn pulse_select(config: &Config) {
+    if config.rumble {
+        request_pulse(config.rumble_intensity, SELECT_PULSE_MS);
+    }
+}
+
+/// Slightly longer buzz when something can't be selected, if rumble is enabled
+pub fn pulse_reject(config: &Config) {
+    if config.rumble {
+        request_pulse(config.rumble_intensity, REJECT_PULSE_MS);
+    }
+}
+
+fn request_pulse(strength: f32, duration_ms: u32) {
+    if let Ok(mut pending) = PENDING_PULSE.lock() {
+        *pending = Some((strength.clamp(0.0, 1.0), duration_ms));
+    }
+}
+
+/// Owns the force feedback effect that is currently playing (gilrs stops an effect when it's dropped)
+pub struct Rumble {
+    effect: Option<Effect>,
+}
+
+impl Rumble {
+    pub fn new() -> Self {
+        Rumble { effect: None }
+    }
+
+    /// Plays the pulse queued since the last frame on every controller that supports force feedback.
+    /// Controllers without it are skipped, so this is a no-op on setups without rumble.
+    pub fn update(&mut self, gilrs: &mut Gilrs) {
+        let Some((strength, duration_ms)) = PENDING_PULSE.lock().ok().and_then(|mut pending| pending.take()) else {
+            return;
+        };
+
+        let targets: Vec<_> = gilrs.gamepads()
+            .filter(|(_, gamepad)| gamepad.is_ff_supported())
+            .map(|(id, _)| id)
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        let magnitude = (strength * u16::MAX as f32) as u16;
+        let duration = Ticks::from_ms(duration_ms);
+        let result = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong { magnitude },
+                scheduling: Replay { play_for: duration, ..Default::default() },
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak { magnitude },
+                scheduling: Replay { play_for: duration, ..Default::default() },
+                ..Default::default()
+            })
+            .gamepads(&targets)
+            .repeat(gilrs::ff::Repeat::For(duration))
+            .finish(gilrs)
+            .and_then(|effect| effect.play().map(|_| effect));
+
+        match result {
+            // Replacing the previous effect stops it, so rapid inputs don't stack up
+            Ok(effect) => self.effect = Some(effect),
+            Err(e) => log_debug!("Rumble failed: {}", e),
+        }
+    }
+}
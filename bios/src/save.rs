@@ -1,5 +1,5 @@
 use walkdir;
-use chrono::DateTime;
+use chrono::{DateTime, FixedOffset, Local};
 use std::{
     fs, fmt,
     collections::VecDeque,
@@ -15,6 +15,7 @@ use tar::{Builder, Archive};
 use crate::{
     DEV_MODE,
     config::get_user_data_dir,
+    system::mounted_network_cart_dir,
     types::StorageMedia,
 };
 
@@ -46,6 +47,65 @@ pub struct CartInfo {
     pub runtime: Option<String>, // runtime is optional
 }
 
+/// The storage queries the menus are built from. The system implementation talks to the real
+/// drives; anything else (e.g. a set of fake devices and saves) can stand in for it.
+pub trait SaveBackend {
+    /// (drive name, free MB, mounted read-only) for every drive, internal first
+    fn list_devices(&self) -> io::Result<Vec<(String, u32, bool)>>;
+    /// (cart id, name, icon path) for every save on a drive
+    fn get_save_details(&self, drive_name: &str) -> io::Result<Vec<(String, String, String)>>;
+    fn has_save_dir(&self, drive_name: &str) -> bool;
+    fn is_cart(&self, drive_name: &str) -> bool;
+}
+
+/// The real drives, via the free functions below
+pub struct SystemSaveBackend;
+
+impl SaveBackend for SystemSaveBackend {
+    fn list_devices(&self) -> io::Result<Vec<(String, u32, bool)>> { list_devices() }
+    fn get_save_details(&self, drive_name: &str) -> io::Result<Vec<(String, String, String)>> { get_save_details(drive_name) }
+    fn has_save_dir(&self, drive_name: &str) -> bool { has_save_dir(drive_name) }
+    fn is_cart(&self, drive_name: &str) -> bool { is_cart(drive_name) }
+}
+
+/// Drives and saves kept in memory, for tests
+#[cfg(test)]
+#[derive(Default)]
+pub struct FakeSaveBackend {
+    /// (drive name, free MB, mounted read-only), as list_devices() reports them
+    pub devices: Vec<(String, u32, bool)>,
+    /// (cart id, name, icon path) per drive; a drive with no entry has no save folder
+    pub saves: std::collections::HashMap<String, Vec<(String, String, String)>>,
+    /// Drives that are game carts rather than storage
+    pub carts: Vec<String>,
+}
+
+#[cfg(test)]
+impl FakeSaveBackend {
+    /// Adds a writable drive with a save folder holding `saves` (cart id, name)
+    pub fn add_drive(&mut self, name: &str, saves: &[(&str, &str)]) {
+        self.devices.push((name.to_string(), 1024, false));
+        self.saves.insert(name.to_string(), saves.iter()
+            .map(|(id, title)| (id.to_string(), title.to_string(), format!("/{}/{}.png", name, id)))
+            .collect());
+    }
+
+    pub fn remove_drive(&mut self, name: &str) {
+        self.devices.retain(|(id, _, _)| id != name);
+        self.saves.remove(name);
+    }
+}
+
+#[cfg(test)]
+impl SaveBackend for FakeSaveBackend {
+    fn list_devices(&self) -> io::Result<Vec<(String, u32, bool)>> { Ok(self.devices.clone()) }
+    fn get_save_details(&self, drive_name: &str) -> io::Result<Vec<(String, String, String)>> {
+        self.saves.get(drive_name).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+    fn has_save_dir(&self, drive_name: &str) -> bool { self.saves.contains_key(drive_name) }
+    fn is_cart(&self, drive_name: &str) -> bool { self.carts.iter().any(|cart| cart == drive_name) }
+}
+
 #[derive(Clone, Debug)]
 pub struct StorageMediaState {
     pub all_media: Vec<StorageMedia>, // all storage media, including disabled media
@@ -81,14 +141,43 @@ impl StorageMediaState {
     }
 
     pub fn update_media(&mut self) {
-        let mut all_new_media = Vec::new();
-
-        if let Ok(devices) = list_devices() {
-            for (id, free) in devices {
-                all_new_media.push(StorageMedia {
-                    id,
-                    free,
-                });
+        self.update_media_from(&SystemSaveBackend);
+    }
+
+    /// update_media() right now instead of on the next poll, for a card that's slow to show up.
+    /// Also reloads the selected drive's saves. Returns how many usable drives there are,
+    /// or None if the drives couldn't be listed.
+    pub fn rescan(&mut self) -> Option<usize> {
+        self.rescan_from(&SystemSaveBackend)
+    }
+
+    fn rescan_from(&mut self, backend: &impl SaveBackend) -> Option<usize> {
+        if !self.update_media_from(backend) {
+            return None;
+        }
+        self.needs_memory_refresh = true;
+        Some(self.media.len())
+    }
+
+    /// update_media() against any backend. Returns false if the drives couldn't be listed.
+    pub fn update_media_from(&mut self, backend: &impl SaveBackend) -> bool {
+        let devices = match backend.list_devices() {
+            Ok(devices) => devices,
+            Err(e) => {
+                // Keep what we had rather than wiping the list on a transient failure
+                log_warn!("Could not enumerate storage devices: {}", e);
+                return false;
+            }
+        };
+
+        let all_new_media: Vec<StorageMedia> = devices
+        .into_iter()
+        .map(|(id, free, read_only)| StorageMedia { id, free, read_only })
+        .collect();
+
+        for old in &self.all_media {
+            if !all_new_media.iter().any(|m| m.id == old.id) {
+                log_info!("Storage device '{}' was removed", old.id);
             }
         }
 
@@ -99,18 +188,20 @@ impl StorageMediaState {
                 //  update free space
                 self.all_media = all_new_media;
                 for media in &mut self.media {
-                    if let Some(pos) = self.all_media.iter().position(|m| m.id == media.id) {
-                        media.free = self.all_media.get(pos).unwrap().free
+                    if let Some(m) = self.all_media.iter().find(|m| m.id == media.id) {
+                        media.free = m.free;
+                        media.read_only = m.read_only;
                     }
                 }
 
-                return;
+                return true;
             }
 
+            // A drive can vanish between list_devices() and these checks; it then simply fails them and is skipped
             let new_media: Vec<StorageMedia> = all_new_media
             .clone()
             .into_iter()
-            .filter(|m| has_save_dir(&m.id) && !is_cart(&m.id))
+            .filter(|m| backend.has_save_dir(&m.id) && !backend.is_cart(&m.id))
             .collect();
 
             // Try to keep the same device selected if it still exists
@@ -118,6 +209,10 @@ impl StorageMediaState {
             if let Some(old_selected_media) = self.media.get(self.selected) {
                 if let Some(pos) = new_media.iter().position(|m| m.id == old_selected_media.id) {
                     new_pos = pos;
+                } else {
+                    log_info!("Selected storage device '{}' is gone, switching to '{}'",
+                        old_selected_media.id,
+                        new_media.first().map_or("nothing", |m| m.id.as_str()));
                 }
             }
 
@@ -125,6 +220,7 @@ impl StorageMediaState {
             self.media = new_media;
             self.selected = new_pos;
             self.needs_memory_refresh = true;
+            true
     }
 }
 
@@ -230,13 +326,13 @@ fn get_attribute(info_file: &Path, attribute: &str) -> io::Result<String> {
     Ok(String::new())
 }
 
-/// Calculate playtime from a tar archive (external drives)
-fn calculate_playtime_from_tar(tar_path: &Path, _cart_id: &str) -> f32 {
+/// Read the playtime log from a tar archive (external drives)
+fn read_playtime_from_tar(tar_path: &Path) -> String {
     let file = match fs::File::open(tar_path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to open tar file {}: {}", tar_path.display(), e);
-            return 0.0;
+            return String::new();
         }
     };
 
@@ -245,7 +341,7 @@ fn calculate_playtime_from_tar(tar_path: &Path, _cart_id: &str) -> f32 {
         Ok(entries) => entries,
         Err(e) => {
             eprintln!("Failed to read archive entries: {}", e);
-            return 0.0;
+            return String::new();
         }
     };
 
@@ -279,11 +375,11 @@ fn calculate_playtime_from_tar(tar_path: &Path, _cart_id: &str) -> f32 {
         }
     }
 
-    parse_playtime_content(&format!("{}\n{} {}", content.trim(), start_content.trim(), end_content.trim()))
+    format!("{}\n{} {}", content.trim(), start_content.trim(), end_content.trim())
 }
 
-/// Calculate playtime from a directory (internal drives)
-fn calculate_playtime_from_dir(dir_path: &Path, _cart_id: &str) -> f32 {
+/// Read the playtime log from a directory (internal drives)
+fn read_playtime_from_dir(dir_path: &Path) -> String {
     let playtime_log_path = dir_path.join(".kazeta/var/playtime.log");
     let playtime_start_path = dir_path.join(".kazeta/var/playtime_start");
     let playtime_end_path = dir_path.join(".kazeta/var/playtime_end");
@@ -303,12 +399,12 @@ fn calculate_playtime_from_dir(dir_path: &Path, _cart_id: &str) -> f32 {
         Err(_) => "".to_string(),
     };
 
-    return parse_playtime_content(&format!("{}\n{} {}", content.trim(), start_content.trim(), end_content.trim()));
+    format!("{}\n{} {}", content.trim(), start_content.trim(), end_content.trim())
 }
 
-/// Parse playtime content from a string (common logic for both tar and directory)
-fn parse_playtime_content(content: &str) -> f32 {
-    let mut total_seconds: i64 = 0;
+/// Parse the (start, end) sessions out of playtime content (common logic for both tar and directory)
+fn parse_playtime_sessions(content: &str) -> Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+    let mut sessions = Vec::new();
 
     for line in content.lines() {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -332,12 +428,23 @@ fn parse_playtime_content(content: &str) -> f32 {
             }
         };
 
-        let duration = end_time.signed_duration_since(start_time);
-        total_seconds += duration.num_seconds();
+        sessions.push((start_time, end_time));
     }
 
-    // Convert to hours rounded to one decimal place
-    ((total_seconds as f64 / 360.0).round() / 10.0) as f32
+    sessions
+}
+
+/// Convert to hours rounded to one decimal place
+fn seconds_to_hours(seconds: i64) -> f32 {
+    ((seconds as f64 / 360.0).round() / 10.0) as f32
+}
+
+fn parse_playtime_content(content: &str) -> f32 {
+    let total_seconds: i64 = parse_playtime_sessions(content)
+        .iter()
+        .map(|(start, end)| end.signed_duration_since(*start).num_seconds())
+        .sum();
+    seconds_to_hours(total_seconds)
 }
 
 /// Calculate size from a tar archive (external drives)
@@ -420,6 +527,94 @@ pub fn write_launch_command(kzi_path: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
+/// What the previous session-restart launch left behind: the kazeta script's status file,
+/// or a launch command the session never picked up
+pub struct LaunchReport {
+    pub cart: String,
+    /// How far the launch got (find-cart, mount-package, read-package, mount, running, session)
+    pub stage: String,
+    pub error: Option<String>,
+}
+
+impl LaunchReport {
+    /// A game still marked running only means the power went before it quit, which isn't a launch failure
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || self.stage != "running"
+    }
+
+    /// Lines for the debug screen
+    pub fn describe(&self) -> Vec<String> {
+        let step = match self.stage.as_str() {
+            "find-cart" => "finding the cart",
+            "mount-package" => "mounting the .kzp package",
+            "read-package" => "reading the package",
+            "mount" => "mounting the game and its runtime",
+            "running" => "running the game",
+            "session" => "handing the game to the session",
+            other => other,
+        };
+        let mut lines = vec![
+            "--- LAST LAUNCH ---".to_string(),
+            format!("Cart: {}", self.cart),
+            format!("Stopped while {}.", step),
+        ];
+        match &self.error {
+            Some(error) => lines.push(format!("Error: {}", error)),
+            None if self.failed() => lines.push("The launch script quit without saying why. The session log may have more.".to_string()),
+            None => {}
+        }
+        lines
+    }
+}
+
+/// Reads and clears what the last launch left in the state folder. Call once at startup,
+/// and again whenever an in-place game exits so its status can't greet the next boot.
+pub fn take_launch_report() -> Option<LaunchReport> {
+    take_launch_report_in(&get_state_dir().ok()?)
+}
+
+fn take_launch_report_in(state_dir: &Path) -> Option<LaunchReport> {
+    // The session deletes .LAUNCH_CMD before running it, so one still here was never run
+    // (and would otherwise fire on some later, unrelated session restart)
+    let launch_cmd_path = state_dir.join(".LAUNCH_CMD");
+    if let Ok(command) = fs::read_to_string(&launch_cmd_path) {
+        let _ = fs::remove_file(&launch_cmd_path);
+        return Some(LaunchReport {
+            cart: command.trim().trim_start_matches("/usr/bin/kazeta").trim().trim_matches('\'').to_string(),
+            stage: "session".to_string(),
+            error: Some("The session restarted without running the launch command.".to_string()),
+        });
+    }
+
+    let status_path = state_dir.join(".LAUNCH_STATUS");
+    let status = fs::read_to_string(&status_path).ok()?;
+    let _ = fs::remove_file(&status_path);
+    let field = |key: &str| status.lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    Some(LaunchReport {
+        cart: field("cart").unwrap_or_else(|| "unknown".to_string()),
+        stage: field("stage").unwrap_or_default(),
+        error: field("error"),
+    })
+}
+
+/// How long the cart scan waits on the network share before leaving it out
+const NETWORK_SCAN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Scans the network share for carts on its own thread, so a server that went away can only
+/// cost `timeout` rather than freezing the menu. A scan that runs over is left to finish alone.
+fn scan_network_share(dir: PathBuf, timeout: std::time::Duration) -> Result<Vec<PathBuf>, String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(find_files_by_extension(&dir, &["kzi", "kzp"], 2, false).map_err(|e| e.to_string()));
+    });
+    rx.recv_timeout(timeout)
+        .unwrap_or_else(|_| Err(format!("no answer within {} seconds", timeout.as_secs())))
+}
+
 // [UPDATED] Searches for both kzi and kzp
 pub fn find_all_game_files() -> Result<(Vec<PathBuf>, Vec<String>), SaveError> {
     let mut debug_log = Vec::new();
@@ -429,7 +624,18 @@ pub fn find_all_game_files() -> Result<(Vec<PathBuf>, Vec<String>), SaveError> {
 
     // Search for both extensions
     match find_files_by_extension(mount_dir, &["kzi", "kzp"], 2, false) {
-        Ok(files) => {
+        Ok(mut files) => {
+            // Carts on the network share, when it's mounted. An unreachable share just adds nothing.
+            if let Some(network_dir) = mounted_network_cart_dir() {
+                match scan_network_share(network_dir, NETWORK_SCAN_TIMEOUT) {
+                    Ok(network_files) => {
+                        debug_log.push(format!("[Debug] Found {} game file(s) on the network share.", network_files.len()));
+                        files.extend(network_files);
+                    }
+                    Err(e) => debug_log.push(format!("[Debug] Skipping the network share: {}", e)),
+                }
+            }
+
             debug_log.push(format!("[Debug] Found {} potential game file(s).", files.len()));
             for (i, path) in files.iter().enumerate() {
                 debug_log.push(format!("[Debug]    {}: {}", i + 1, path.display()));
@@ -444,6 +650,26 @@ pub fn find_all_game_files() -> Result<(Vec<PathBuf>, Vec<String>), SaveError> {
     }
 }
 
+/// Cart ids become directory names under every save and cache folder, so anything that could
+/// step outside them ("..", path separators) or confuse the filesystem is refused outright.
+pub fn validate_cart_id(id: &str) -> Result<(), String> {
+    const MAX_ID_BYTES: usize = 255;
+    if id.is_empty() {
+        return Err("The cart id is empty.".to_string());
+    }
+    if id.len() > MAX_ID_BYTES {
+        return Err(format!("The cart id is longer than {} bytes.", MAX_ID_BYTES));
+    }
+    if id.starts_with('.') {
+        // Covers "." and ".." as well as hidden names like the slot folder's .active
+        return Err(format!("The cart id '{}' can't start with a dot.", id));
+    }
+    if let Some(c) = id.chars().find(|&c| matches!(c, '/' | '\\') || c.is_control()) {
+        return Err(format!("The cart id '{}' contains an illegal character ({:?}).", id.escape_debug(), c));
+    }
+    Ok(())
+}
+
 /// Parses a specific .kzi file and returns its metadata.
 pub fn parse_kzi_file(kzi_path: &Path) -> Result<CartInfo, SaveError> {
     let content = fs::read_to_string(kzi_path)?;
@@ -468,6 +694,7 @@ pub fn parse_kzi_file(kzi_path: &Path) -> Result<CartInfo, SaveError> {
     }
 
     if let (Some(id), Some(exec), Some(icon)) = (id, exec, icon) {
+        validate_cart_id(&id).map_err(|e| SaveError::Message(format!("Invalid .kzi file: '{}'. {}", kzi_path.display(), e)))?;
         Ok(CartInfo { name, id, exec, icon, runtime })
     } else {
         Err(SaveError::Message(format!("Invalid .kzi file: '{}'. Missing required fields.", kzi_path.display())))
@@ -591,15 +818,7 @@ pub fn get_save_dir_from_drive_name(drive_name: &str) -> String {
         }
         save_dir.to_string_lossy().into_owned()
     } else {
-        let base_ext = if Path::new("/media").read_dir().map(|mut d| d.next().is_none()).unwrap_or(true) {
-            if Path::new(&format!("/run/media/{}", whoami::username())).exists() {
-                format!("/run/media/{}", whoami::username())
-            } else {
-                "/run/media".to_string()
-            }
-        } else {
-            "/media".to_string()
-        };
+        let base_ext = external_media_base();
 
         // Just return the path. DO NOT CREATE IT.
         let save_dir = Path::new(&base_ext).join(drive_name).join("kazeta/saves");
@@ -618,15 +837,7 @@ pub fn get_cache_dir_from_drive_name(drive_name: &str) -> String {
         }
         cache_dir.to_string_lossy().into_owned()
     } else {
-        let base_ext = if Path::new("/media").read_dir().map(|mut d| d.next().is_none()).unwrap_or(true) {
-            if Path::new(&format!("/run/media/{}", whoami::username())).exists() {
-                format!("/run/media/{}", whoami::username())
-            } else {
-                "/run/media".to_string()
-            }
-        } else {
-            "/media".to_string()
-        };
+        let base_ext = external_media_base();
 
         let cache_dir = Path::new(&base_ext).join(drive_name).join("kazeta/cache");
         if !cache_dir.exists() {
@@ -638,47 +849,74 @@ pub fn get_cache_dir_from_drive_name(drive_name: &str) -> String {
     }
 }
 
-pub fn list_devices() -> io::Result<Vec<(String, u32)>> {
+/// Where removable drives get mounted (/media, /run/media/<user> or /run/media)
+fn external_media_base() -> String {
+    if Path::new("/media").read_dir().map(|mut d| d.next().is_none()).unwrap_or(true) {
+        if Path::new(&format!("/run/media/{}", whoami::username())).exists() {
+            format!("/run/media/{}", whoami::username())
+        } else {
+            "/run/media".to_string()
+        }
+    } else {
+        "/media".to_string()
+    }
+}
+
+/// Mount point of an external drive, as listed by list_devices()
+pub fn get_drive_root(drive_name: &str) -> PathBuf {
+    Path::new(&external_media_base()).join(drive_name)
+}
+
+/// Root folders of the external drives that can be written to
+pub fn writable_external_drives() -> Vec<PathBuf> {
+    list_devices().unwrap_or_default()
+        .into_iter()
+        .filter(|(name, _, read_only)| name != "internal" && !read_only)
+        .map(|(name, _, _)| get_drive_root(&name))
+        .collect()
+}
+
+/// Returns (drive name, free MB, mounted read-only) for the internal drive and every external drive.
+pub fn list_devices() -> io::Result<Vec<(String, u32, bool)>> {
     let mut devices = Vec::new();
     let disks = Disks::new_with_refreshed_list();
 
     // Add internal drive
-    let base_dir = dirs::home_dir().unwrap().join(".local/share/kazeta");
-    let base_dir_str = base_dir.to_str().unwrap();
+    let base_dir = dirs::home_dir()
+    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Could not find home directory"))?
+    .join(".local/share/kazeta");
 
-    // Find the disk that contains our base directory
+    // Find the disk that contains our base directory (longest matching mount point wins)
     let internal_disk = disks.iter()
-    .find(|disk| {
-        let mount_point = disk.mount_point().to_str().unwrap();
-        base_dir_str.starts_with(mount_point)
-    })
+    .filter(|disk| base_dir.starts_with(disk.mount_point()))
+    .max_by_key(|disk| disk.mount_point().as_os_str().len())
     .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Could not find internal disk"))?;
 
     let free_space = (internal_disk.available_space() / 1024 / 1024) as u32; // Convert to MB
-    devices.push(("internal".to_string(), free_space));
+    devices.push(("internal".to_string(), free_space, internal_disk.is_read_only()));
 
     // Add external drives
-    let base_ext = if Path::new("/media").read_dir().map(|mut d| d.next().is_none()).unwrap_or(true) {
-        if Path::new(&format!("/run/media/{}", whoami::username())).exists() {
-            format!("/run/media/{}", whoami::username())
-        } else {
-            "/run/media".to_string()
-        }
-    } else {
-        "/media".to_string()
-    };
+    let base_ext = external_media_base();
 
     // Find all disks mounted under the external base directory
     for disk in disks.iter() {
-        let mount_point = disk.mount_point().to_str().unwrap();
+        let Some(mount_point) = disk.mount_point().to_str() else { continue };
         if mount_point.starts_with(&base_ext) {
-            let name = mount_point.split('/').last().unwrap().to_string();
+            let Some(name) = mount_point.rsplit('/').next().map(|n| n.to_string()) else { continue };
             if name == "frzr_efi" {
                 // ignore internal frzr partition
                 continue;
             }
+            // The drive may have been pulled since the disk list was refreshed
+            if !disk.mount_point().exists() {
+                log_info!("Storage device '{}' disappeared during scan, skipping", name);
+                continue;
+            }
             let free_space = (disk.available_space() / 1024 / 1024) as u32; // Convert to MB
-            devices.push((name, free_space));
+            if disk.is_read_only() {
+                log_info!("Storage device '{}' is mounted read-only", name);
+            }
+            devices.push((name, free_space, disk.is_read_only()));
         }
     }
 
@@ -694,6 +932,19 @@ pub fn has_save_dir(drive_name: &str) -> bool {
     Path::new(&save_dir).exists()
 }
 
+/// Which of `drives` hold save data for a cart, either as a folder (internal) or a .tar (external).
+/// Games always play from the internal save; the other drives hold copies. The drive list comes
+/// from the storage poll (StorageMediaState::all_media) rather than a fresh list_devices().
+pub fn find_save_drives(cart_id: &str, drives: &[StorageMedia]) -> Vec<String> {
+    drives.iter()
+        .map(|drive| drive.id.clone())
+        .filter(|name| {
+            let save_dir = PathBuf::from(get_save_dir_from_drive_name(name));
+            save_dir.join(cart_id).exists() || save_dir.join(format!("{}.tar", cart_id)).exists()
+        })
+        .collect()
+}
+
 // [UPDATED] Logic now checks for both kzi and kzp
 pub fn is_cart(drive_name: &str) -> bool {
     if drive_name == "internal" {
@@ -713,14 +964,10 @@ pub fn is_cart(drive_name: &str) -> bool {
 }
 
 // [UPDATED] Logic now checks for both kzi and kzp
-pub fn is_cart_connected() -> bool {
-    if let Ok(files) = find_files_by_extension("/run/media", &["kzi", "kzp"], 2, true) {
-        if files.len() > 0 {
-            return true;
-        }
-    }
-
-    false
+/// Returns the folder holding the inserted cart's .kzi/.kzp, if a cart is connected
+pub fn find_connected_cart_dir() -> Option<PathBuf> {
+    let files = find_files_by_extension("/run/media", &["kzi", "kzp"], 2, true).ok()?;
+    files.first().and_then(|file| file.parent()).map(Path::to_path_buf)
 }
 
 pub fn get_save_details(drive_name: &str) -> io::Result<Vec<(String, String, String)>> {
@@ -767,10 +1014,14 @@ pub fn get_save_details(drive_name: &str) -> io::Result<Vec<(String, String, Str
 pub fn delete_save(cart_id: &str, from_drive: &str) -> Result<(), SaveError> {
     let from_dir = get_save_dir_from_drive_name(from_drive);
     let from_cache = get_cache_dir_from_drive_name(from_drive);
+    delete_save_in(Path::new(&from_dir), Path::new(&from_cache), cart_id, from_drive)
+}
 
+/// delete_save() with the drive's save and cache folders already looked up
+fn delete_save_in(from_dir: &Path, from_cache: &Path, cart_id: &str, from_drive: &str) -> Result<(), SaveError> {
     // Check if save exists
-    let save_path = Path::new(&from_dir).join(cart_id);
-    let save_path_tar = Path::new(&from_dir).join(format!("{}.tar", cart_id));
+    let save_path = from_dir.join(cart_id);
+    let save_path_tar = from_dir.join(format!("{}.tar", cart_id));
     if !save_path.exists() && !save_path_tar.exists() {
         //return Err(format!("Save file for {} does not exist on '{}' drive", cart_id, from_drive));
         return Err(SaveError::Message(format!("Save file for {} does not exist on '{}' drive", cart_id, from_drive)));
@@ -780,13 +1031,19 @@ pub fn delete_save(cart_id: &str, from_drive: &str) -> Result<(), SaveError> {
     if from_drive == "internal" {
         //fs::remove_dir_all(save_path).map_err(|e| e.to_string())?;
         fs::remove_dir_all(save_path)?;
+
+        // The cart's other slots go with it
+        let slots_dir = slots_dir_in(from_dir, cart_id)?;
+        if slots_dir.exists() {
+            fs::remove_dir_all(slots_dir)?;
+        }
     } else {
         //fs::remove_file(save_path_tar).map_err(|e| e.to_string())?;
         fs::remove_file(save_path_tar)?;
     }
 
     // Delete cache
-    let cache_path = Path::new(&from_cache).join(cart_id);
+    let cache_path = from_cache.join(cart_id);
     if cache_path.exists() {
         //fs::remove_dir_all(cache_path).map_err(|e| e.to_string())?;
         fs::remove_dir_all(cache_path)?;
@@ -795,6 +1052,116 @@ pub fn delete_save(cart_id: &str, from_drive: &str) -> Result<(), SaveError> {
     Ok(())
 }
 
+// Save slots (internal storage only). The active slot always lives at saves/default/<cart_id>,
+// where the kazeta script mounts it as the upperdir, so launching, copying and deleting all
+// work on it unchanged. The others are parked in saves/slots/<cart_id>/<slot name>.
+
+/// Slot every cart starts out on
+pub const DEFAULT_SAVE_SLOT: &str = "SLOT 1";
+
+/// The internal saves folder (saves/default) the slot functions work in
+fn internal_save_dir() -> PathBuf {
+    PathBuf::from(get_save_dir_from_drive_name("internal"))
+}
+
+/// saves/slots/<cart_id>, next to the internal saves in `internal`
+fn slots_dir_in(internal: &Path, cart_id: &str) -> Result<PathBuf, SaveError> {
+    let saves_dir = internal.parent()
+        .ok_or_else(|| SaveError::Message(format!("No parent folder for {}", internal.display())))?;
+    Ok(saves_dir.join("slots").join(cart_id))
+}
+
+/// Name of the slot currently mounted for a cart
+pub fn active_save_slot(cart_id: &str) -> String {
+    active_save_slot_in(&internal_save_dir(), cart_id)
+}
+
+fn active_save_slot_in(internal: &Path, cart_id: &str) -> String {
+    slots_dir_in(internal, cart_id)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(".active")).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_SAVE_SLOT.to_string())
+}
+
+/// All of a cart's slots, active one included, in name order
+pub fn list_save_slots(cart_id: &str) -> Vec<String> {
+    list_save_slots_in(&internal_save_dir(), cart_id)
+}
+
+fn list_save_slots_in(internal: &Path, cart_id: &str) -> Vec<String> {
+    let mut slots: Vec<String> = slots_dir_in(internal, cart_id)
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    slots.push(active_save_slot_in(internal, cart_id));
+    slots.sort();
+    slots.dedup();
+    slots
+}
+
+/// Makes the given slot the one the cart will mount, parking the current one
+pub fn switch_save_slot(cart_id: &str, slot: &str) -> Result<(), SaveError> {
+    switch_save_slot_in(&internal_save_dir(), cart_id, slot)
+}
+
+fn switch_save_slot_in(internal: &Path, cart_id: &str, slot: &str) -> Result<(), SaveError> {
+    let active = active_save_slot_in(internal, cart_id);
+    if slot == active {
+        return Ok(());
+    }
+
+    let slots_dir = slots_dir_in(internal, cart_id)?;
+    let current = internal.join(cart_id);
+    let parked = slots_dir.join(&active);
+    let incoming = slots_dir.join(slot);
+    if parked.exists() {
+        return Err(SaveError::Message(format!("Slot '{}' of {} is already parked", active, cart_id)));
+    }
+
+    fs::create_dir_all(&slots_dir)?;
+    let had_save = current.exists();
+    if had_save {
+        fs::rename(&current, &parked)?;
+    } else {
+        // Nothing saved yet; keep the slot around so it can be switched back to
+        fs::create_dir_all(&parked)?;
+    }
+    let swapped = if incoming.exists() {
+        fs::rename(&incoming, &current)
+    } else {
+        fs::create_dir_all(&current)
+    };
+    if let Err(e) = swapped {
+        // Put the active slot back where the cart mounts it
+        let undone = if had_save { fs::rename(&parked, &current) } else { fs::remove_dir(&parked) };
+        if let Err(undo_error) = undone {
+            log_error!("Could not restore slot '{}' of {} after a failed switch: {}", active, cart_id, undo_error);
+        }
+        return Err(e.into());
+    }
+    fs::write(slots_dir.join(".active"), slot)?;
+    sync_to_disk();
+    Ok(())
+}
+
+/// Adds an empty slot named after the next free number and switches to it
+pub fn create_save_slot(cart_id: &str) -> Result<String, SaveError> {
+    let existing = list_save_slots(cart_id);
+    let slot = (1..)
+        .map(|n| format!("SLOT {}", n))
+        .find(|name| !existing.contains(name))
+        .unwrap();
+    switch_save_slot(cart_id, &slot)?;
+    Ok(slot)
+}
+
 pub fn copy_save(cart_id: &str, from_drive: &str, to_drive: &str, progress: Arc<AtomicU16>) -> Result<(), SaveError> {
     let from_dir = get_save_dir_from_drive_name(from_drive);
     let to_dir = get_save_dir_from_drive_name(to_drive);
@@ -1014,21 +1381,58 @@ pub fn copy_save(cart_id: &str, from_drive: &str, to_drive: &str, progress: Arc<
 /// Returns playtime in hours with one decimal place
 pub fn calculate_playtime(cart_id: &str, drive_name: &str) -> f32 {
     println!("Calculating playtime for {} on {}", cart_id, drive_name);
-    let save_dir = get_save_dir_from_drive_name(drive_name);
+    parse_playtime_content(&read_playtime_log(cart_id, drive_name))
+}
+
+fn read_playtime_log(cart_id: &str, drive_name: &str) -> String {
+    read_playtime_log_in(Path::new(&get_save_dir_from_drive_name(drive_name)), cart_id)
+}
 
+fn read_playtime_log_in(save_dir: &Path, cart_id: &str) -> String {
     // Check if this is a tar file (external drive) or directory (internal drive)
-    let tar_path = Path::new(&save_dir).join(format!("{}.tar", cart_id));
-    let dir_path = Path::new(&save_dir).join(cart_id);
+    let tar_path = save_dir.join(format!("{}.tar", cart_id));
+    let dir_path = save_dir.join(cart_id);
 
     if tar_path.exists() {
         // External drive: read from tar archive
-        calculate_playtime_from_tar(&tar_path, cart_id)
+        read_playtime_from_tar(&tar_path)
     } else if dir_path.exists() {
         // Internal drive: read from directory
-        calculate_playtime_from_dir(&dir_path, cart_id)
+        read_playtime_from_dir(&dir_path)
     } else {
         // Neither exists
-        0.0
+        String::new()
+    }
+}
+
+/// Playtime figures for the stats screen
+pub struct PlaytimeStats {
+    pub hours: f32,
+    /// Hours played over the last 7 days
+    pub recent_hours: f32,
+    /// End of the most recent session
+    pub last_played: Option<DateTime<FixedOffset>>,
+}
+
+/// Like calculate_playtime, but also works out the last week's share and when the game was last played
+pub fn calculate_playtime_stats(cart_id: &str, drive_name: &str) -> PlaytimeStats {
+    let sessions = parse_playtime_sessions(&read_playtime_log(cart_id, drive_name));
+    let week_ago = Local::now().fixed_offset() - chrono::Duration::days(7);
+
+    let mut total_seconds = 0;
+    let mut recent_seconds = 0;
+    for (start, end) in &sessions {
+        total_seconds += end.signed_duration_since(*start).num_seconds();
+        if *end > week_ago {
+            // Only count the part of the session inside the window
+            recent_seconds += end.signed_duration_since((*start).max(week_ago)).num_seconds();
+        }
+    }
+
+    PlaytimeStats {
+        hours: seconds_to_hours(total_seconds),
+        recent_hours: seconds_to_hours(recent_seconds),
+        last_played: sessions.iter().map(|(_, end)| *end).max(),
     }
 }
 
@@ -1036,11 +1440,13 @@ pub fn calculate_playtime(cart_id: &str, drive_name: &str) -> f32 {
 /// Returns size in MB with one decimal place
 pub fn calculate_save_size(cart_id: &str, drive_name: &str) -> f32 {
     println!("Calculating save size for {} on {}", cart_id, drive_name);
-    let save_dir = get_save_dir_from_drive_name(drive_name);
+    save_size_in(Path::new(&get_save_dir_from_drive_name(drive_name)), cart_id)
+}
 
+fn save_size_in(save_dir: &Path, cart_id: &str) -> f32 {
     // Check if this is a tar file (external drive) or directory (internal drive)
-    let tar_path = Path::new(&save_dir).join(format!("{}.tar", cart_id));
-    let dir_path = Path::new(&save_dir).join(cart_id);
+    let tar_path = save_dir.join(format!("{}.tar", cart_id));
+    let dir_path = save_dir.join(cart_id);
 
     let size_bytes = if tar_path.exists() {
         // External drive: get tar file size
@@ -1061,3 +1467,243 @@ pub fn calculate_save_size(cart_id: &str, drive_name: &str) -> f32 {
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_report_is_read_once_and_cleared() {
+        let state = tempfile::tempdir().unwrap();
+        fs::write(state.path().join(".LAUNCH_STATUS"), "cart=demo\nstage=mount\nerror=no runtime\n").unwrap();
+
+        let report = take_launch_report_in(state.path()).unwrap();
+        assert!(report.failed());
+        assert_eq!(report.error.as_deref(), Some("no runtime"));
+        assert!(!state.path().join(".LAUNCH_STATUS").exists());
+        assert!(take_launch_report_in(state.path()).is_none());
+    }
+
+    #[test]
+    fn network_scan_finds_carts_and_gives_up_on_a_missing_share() {
+        let share = tempfile::tempdir().unwrap();
+        fs::create_dir(share.path().join("Games")).unwrap();
+        fs::write(share.path().join("Games/demo.kzi"), "").unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+
+        let found = scan_network_share(share.path().to_path_buf(), timeout).unwrap();
+        assert_eq!(found, [share.path().join("Games/demo.kzi")]);
+        assert!(scan_network_share(share.path().join("gone"), timeout).is_err());
+    }
+
+    fn write_file(path: &Path, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn parses_sessions_and_skips_malformed_lines() {
+        let content = "\
+2024-01-01T10:00:00+00:00 2024-01-01T11:30:00+00:00
+not-a-date 2024-01-01T11:30:00+00:00
+2024-01-02T10:00:00+00:00 garbage
+2024-01-03T10:00:00+00:00
+2024-01-03T10:00:00+00:00 2024-01-03T11:00:00+00:00 extra
+
+2024-01-04T20:00:00+02:00 2024-01-04T20:30:00+02:00";
+        let sessions = parse_playtime_sessions(content);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].1.signed_duration_since(sessions[0].0).num_seconds(), 5400);
+        assert_eq!(sessions[1].1.signed_duration_since(sessions[1].0).num_seconds(), 1800);
+        assert_eq!(parse_playtime_content(content), 2.0);
+    }
+
+    #[test]
+    fn rounds_seconds_to_tenths_of_an_hour() {
+        assert_eq!(seconds_to_hours(0), 0.0);
+        assert_eq!(seconds_to_hours(179), 0.0);
+        assert_eq!(seconds_to_hours(180), 0.1);
+        assert_eq!(seconds_to_hours(5400), 1.5);
+    }
+
+    #[test]
+    fn measures_an_internal_save() {
+        let saves = tempfile::tempdir().unwrap();
+        let save = saves.path().join("game");
+        write_file(&save.join("save.dat"), &vec![0; 1024 * 1024]);
+        write_file(&save.join("config/options.ini"), &[0; 100]);
+        // Runtime files aren't part of the save
+        write_file(&save.join(".kazeta/share/runtime.bin"), &vec![0; 4 * 1024 * 1024]);
+
+        // 1 MiB + 100 bytes rounds up to the next tenth
+        assert_eq!(save_size_in(saves.path(), "game"), 1.1);
+        assert_eq!(save_size_in(saves.path(), "missing"), 0.0);
+    }
+
+    #[test]
+    fn totals_internal_playtime() {
+        let saves = tempfile::tempdir().unwrap();
+        let playtime = |cart_id| parse_playtime_content(&read_playtime_log_in(saves.path(), cart_id));
+        let var = saves.path().join("game/.kazeta/var");
+        write_file(&var.join("playtime.log"), b"\
+2024-01-01T10:00:00+00:00 2024-01-01T11:00:00+00:00
+broken line here
+2024-01-02T10:00:00+00:00 2024-01-02T10:30:00+00:00
+");
+        // A session still running (start, no end yet) doesn't count
+        write_file(&var.join("playtime_start"), b"2024-01-03T10:00:00+00:00\n");
+        assert_eq!(playtime("game"), 1.5);
+
+        // Once the end is captured, the open session counts too
+        write_file(&var.join("playtime_end"), b"2024-01-03T11:00:00+00:00\n");
+        assert_eq!(playtime("game"), 2.5);
+
+        assert_eq!(playtime("missing"), 0.0);
+    }
+
+    #[test]
+    fn reads_playtime_and_size_from_a_tar() {
+        let dir = tempfile::tempdir().unwrap();
+        let tar_path = dir.path().join("game.tar");
+        {
+            let mut builder = Builder::new(fs::File::create(&tar_path).unwrap());
+            let mut add = |path: &str, contents: &[u8]| {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, path, contents).unwrap();
+            };
+            add(".kazeta/var/playtime.log", b"2024-01-01T10:00:00+00:00 2024-01-01T12:00:00+00:00\n");
+            add(".kazeta/var/playtime_start", b"2024-01-02T10:00:00+00:00\n");
+            add("save.dat", &[1; 2048]);
+            builder.finish().unwrap();
+        }
+
+        assert_eq!(parse_playtime_content(&read_playtime_from_tar(&tar_path)), 2.0);
+        assert_eq!(calculate_size_from_tar(&tar_path), fs::metadata(&tar_path).unwrap().len());
+    }
+
+    #[test]
+    fn validates_cart_ids() {
+        let too_long = "a".repeat(256);
+        let cases: &[(&str, bool)] = &[
+            ("", false),
+            (".", false),
+            ("..", false),
+            ("../x", false),
+            ("a/b", false),
+            ("a\\b", false),
+            ("bad\u{7}id", false),
+            ("tab\tid", false),
+            (&too_long, false),
+            (&too_long[..255], true),
+            ("my-game_2.0", true),
+            ("pokémon", true),
+        ];
+        for &(id, valid) in cases {
+            assert_eq!(validate_cart_id(id).is_ok(), valid, "validate_cart_id({:?})", id);
+        }
+    }
+
+    fn media_ids(state: &StorageMediaState) -> Vec<&str> {
+        state.media.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn tracks_added_and_removed_media() {
+        let mut backend = FakeSaveBackend::default();
+        backend.add_drive("internal", &[]);
+        let mut state = StorageMediaState::new();
+        state.update_media_from(&backend);
+        assert_eq!(media_ids(&state), ["internal"]);
+
+        // Carts and drives without a save folder are listed but not usable
+        backend.add_drive("sdcard", &[]);
+        backend.devices.push(("usb".to_string(), 512, false));
+        backend.add_drive("cart", &[]);
+        backend.carts.push("cart".to_string());
+        state.needs_memory_refresh = false;
+        state.update_media_from(&backend);
+        assert_eq!(state.all_media.len(), 4);
+        assert_eq!(media_ids(&state), ["internal", "sdcard"]);
+        assert!(state.needs_memory_refresh);
+
+        backend.remove_drive("sdcard");
+        state.update_media_from(&backend);
+        assert_eq!(media_ids(&state), ["internal"]);
+    }
+
+    #[test]
+    fn keeps_the_selection_when_another_drive_disappears() {
+        let mut backend = FakeSaveBackend::default();
+        backend.add_drive("internal", &[]);
+        backend.add_drive("sdcard", &[]);
+        backend.add_drive("usb", &[]);
+        let mut state = StorageMediaState::new();
+        state.update_media_from(&backend);
+        state.selected = 2;
+
+        backend.remove_drive("sdcard");
+        state.update_media_from(&backend);
+        assert_eq!(state.media[state.selected].id, "usb");
+
+        // Losing the selected drive falls back to the first one
+        backend.remove_drive("usb");
+        state.update_media_from(&backend);
+        assert_eq!(state.selected, 0);
+        assert_eq!(state.media[state.selected].id, "internal");
+    }
+
+    struct BrokenBackend;
+    impl SaveBackend for BrokenBackend {
+        fn list_devices(&self) -> io::Result<Vec<(String, u32, bool)>> { Err(io::Error::other("lsblk failed")) }
+        fn get_save_details(&self, _: &str) -> io::Result<Vec<(String, String, String)>> { Ok(Vec::new()) }
+        fn has_save_dir(&self, _: &str) -> bool { true }
+        fn is_cart(&self, _: &str) -> bool { false }
+    }
+
+    #[test]
+    fn keeps_the_media_list_when_listing_fails() {
+        let mut backend = FakeSaveBackend::default();
+        backend.add_drive("internal", &[]);
+        let mut state = StorageMediaState::new();
+        state.update_media_from(&backend);
+        state.update_media_from(&BrokenBackend);
+        assert_eq!(media_ids(&state), ["internal"]);
+    }
+
+    #[test]
+    fn rescan_reports_a_failed_listing() {
+        let mut backend = FakeSaveBackend::default();
+        backend.add_drive("internal", &[]);
+        let mut state = StorageMediaState::new();
+        assert_eq!(state.rescan_from(&backend), Some(1));
+
+        state.needs_memory_refresh = false;
+        assert_eq!(state.rescan_from(&BrokenBackend), None);
+        assert!(!state.needs_memory_refresh);
+    }
+
+    #[test]
+    fn switches_slots_and_deletes_parked_ones() {
+        let base = tempfile::tempdir().unwrap();
+        let saves = base.path().join("saves");
+        let internal = saves.join("default");
+        write_file(&internal.join("pong/score"), b"first");
+
+        switch_save_slot_in(&internal, "pong", "SLOT 2").unwrap();
+        assert_eq!(active_save_slot_in(&internal, "pong"), "SLOT 2");
+        assert_eq!(list_save_slots_in(&internal, "pong"), ["SLOT 1", "SLOT 2"]);
+        assert!(!internal.join("pong/score").exists());
+        assert!(saves.join("slots/pong/SLOT 1/score").exists());
+
+        switch_save_slot_in(&internal, "pong", "SLOT 1").unwrap();
+        assert_eq!(fs::read(internal.join("pong/score")).unwrap(), b"first");
+        assert!(saves.join("slots/pong/SLOT 2").is_dir());
+
+        delete_save_in(&internal, &base.path().join("cache"), "pong", "internal").unwrap();
+        assert!(!internal.join("pong").exists());
+        assert!(!saves.join("slots/pong").exists());
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::{
+    config::{get_user_data_dir, Config, RANDOM_CHOICE},
+    save,
+    theme::Theme,
+    VERSION_NUMBER,
+};
+
+/// Prefix of setup snapshot files, so the importer can find them on a drive
+const SNAPSHOT_PREFIX: &str = "kazeta_setup_";
+
+/// What the snapshot's config refers to, spelled out for whoever reads the file
+#[derive(Serialize, Deserialize)]
+struct AssetReferences {
+    theme: String,
+    background: String,
+    logo: String,
+    font: String,
+    bgm_track: Option<String>,
+    sfx_pack: String,
+}
+
+/// A shareable copy of someone's setup
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    bios_version: String,
+    created: String,
+    assets: AssetReferences,
+    config: Config,
+}
+
+/// Everything that's installed on this machine, to check a snapshot's references against
+pub struct KnownAssets<'a> {
+    pub themes: &'a HashMap<String, Theme>,
+    pub backgrounds: &'a [String],
+    pub logos: &'a [String],
+    pub fonts: &'a [String],
+    pub bgm_tracks: &'a [String],
+    pub sfx_packs: &'a [String],
+}
+
+/// Where snapshots go when there's no USB drive to put them on
+fn local_snapshot_dir() -> Option<PathBuf> {
+    get_user_data_dir().map(|dir| dir.join("snapshots"))
+}
+
+/// Writes the current setup to the root of the first writable USB drive
+/// (or <data dir>/snapshots if there isn't one). Returns the file written.
+pub fn export(config: &Config) -> Result<PathBuf, String> {
+    let snapshot = Snapshot {
+        bios_version: VERSION_NUMBER.to_string(),
+        created: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        assets: AssetReferences {
+            theme: config.theme.clone(),
+            background: config.background_selection.clone(),
+            logo: config.logo_selection.clone(),
+            font: config.font_selection.clone(),
+            bgm_track: config.bgm_track.clone(),
+            sfx_pack: config.sfx_pack.clone(),
+        },
+        config: {
+            // Machine specific, meaningless on someone else's device
+            let mut shared = config.clone();
+            shared.data_dir = None;
+            shared.window_width = None;
+            shared.window_height = None;
+            shared.network_share.clear();
+            shared.button_bindings.clear();
+            shared.key_bindings.clear();
+            shared
+        },
+    };
+    let contents = toml::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+
+    let dir = save::writable_external_drives().into_iter().next()
+        .or_else(local_snapshot_dir)
+        .ok_or("No place to write the snapshot")?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join(format!("{}{}.toml", SNAPSHOT_PREFIX, Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Finds the newest snapshot on any USB drive or in <data dir>/snapshots
+fn find_latest_snapshot() -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = save::list_devices().unwrap_or_default()
+        .into_iter()
+        .filter(|(name, _, _)| name != "internal")
+        .map(|(name, _, _)| save::get_drive_root(&name))
+        .collect();
+    dirs.extend(local_snapshot_dir());
+
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name().and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(SNAPSHOT_PREFIX) && n.ends_with(".toml"))
+        })
+        .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+/// Applies the newest snapshot found to `config`. Settings that refer to assets this machine
+/// doesn't have fall back to their defaults; those are returned so the user can be told.
+pub fn import_latest(config: &mut Config, known: &KnownAssets) -> Result<(PathBuf, Vec<String>), String> {
+    let path = find_latest_snapshot().ok_or("NO SNAPSHOT FOUND")?;
+    let contents = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let snapshot: Snapshot = toml::from_str(&contents).map_err(|e| e.to_string())?;
+    let missing = apply(config, snapshot.config, known);
+    Ok((path, missing))
+}
+
+/// Replaces `config` with a snapshot's, keeping what belongs to this machine.
+/// Returns the asset references that fell back to their defaults.
+fn apply(config: &mut Config, mut imported: Config, known: &KnownAssets) -> Vec<String> {
+    let defaults = Config::default();
+    let mut missing = Vec::new();
+
+    let is_known = |choices: &[String], name: &str| name == RANDOM_CHOICE || choices.iter().any(|c| c == name);
+
+    if imported.theme != "Default" && !known.themes.contains_key(&imported.theme) {
+        missing.push(format!("THEME {}", imported.theme));
+        imported.theme = defaults.theme.clone();
+    }
+    if !is_known(known.backgrounds, &imported.background_selection) {
+        missing.push(format!("BACKGROUND {}", imported.background_selection));
+        imported.background_selection = defaults.background_selection.clone();
+    }
+    if !is_known(known.logos, &imported.logo_selection) {
+        missing.push(format!("LOGO {}", imported.logo_selection));
+        imported.logo_selection = defaults.logo_selection.clone();
+    }
+    if !is_known(known.fonts, &imported.font_selection) {
+        missing.push(format!("FONT {}", imported.font_selection));
+        imported.font_selection = defaults.font_selection.clone();
+    }
+    if let Some(track) = imported.bgm_track.clone() {
+        if !is_known(known.bgm_tracks, &track) {
+            missing.push(format!("MUSIC {}", track));
+            imported.bgm_track = defaults.bgm_track.clone();
+        }
+    }
+    if !is_known(known.sfx_packs, &imported.sfx_pack) {
+        missing.push(format!("SOUND PACK {}", imported.sfx_pack));
+        imported.sfx_pack = defaults.sfx_pack.clone();
+    }
+
+    // Keep this machine's own paths, window, audio device, network share and controls
+    imported.data_dir = config.data_dir.take();
+    imported.window_width = config.window_width;
+    imported.window_height = config.window_height;
+    imported.display_index = config.display_index;
+    imported.audio_output = std::mem::take(&mut config.audio_output);
+    imported.network_share = std::mem::take(&mut config.network_share);
+    imported.button_bindings = std::mem::take(&mut config.button_bindings);
+    imported.key_bindings = std::mem::take(&mut config.key_bindings);
+    *config = imported;
+    config.mark_dirty();
+
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_keeps_this_machines_share_and_bindings() {
+        let mut config = Config::default();
+        config.network_share = "//nas/carts".to_string();
+        config.button_bindings.insert("SELECT".to_string(), "East".to_string());
+        config.key_bindings.insert("BACK".to_string(), "Escape".to_string());
+        let mut imported = Config::default();
+        imported.network_share = "//elsewhere/carts".to_string();
+        imported.button_bindings.insert("SELECT".to_string(), "North".to_string());
+
+        let themes = HashMap::new();
+        let none: [String; 0] = [];
+        let sfx_packs = [Config::default().sfx_pack];
+        let known = KnownAssets { themes: &themes, backgrounds: &none, logos: &none, fonts: &none, bgm_tracks: &none, sfx_packs: &sfx_packs };
+        apply(&mut config, imported, &known);
+
+        assert_eq!(config.network_share, "//nas/carts");
+        assert_eq!(config.button_bindings.get("SELECT").map(String::as_str), Some("East"));
+        assert_eq!(config.key_bindings.get("BACK").map(String::as_str), Some("Escape"));
+    }
+}
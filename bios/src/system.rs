@@ -1,10 +1,12 @@
-use crate::config::Config;
-use chrono::{FixedOffset, Utc};
+use crate::config::{Config, get_user_data_dir};
+use chrono::{DateTime, FixedOffset, Utc};
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::Regex;
-use crate::{SystemInfo, AudioSink, BatteryInfo, read_line_from_file};
+use crate::ui::settings::native_resolution_for;
+use crate::{SystemInfo, AudioSink, BatteryInfo, DisplayInfo, MissingDep, read_line_from_file};
 
 // BRIGHTNESS CONTROL
 // Gets the current brightness as a value between 0.0 and 1.0
@@ -70,7 +72,18 @@ pub fn get_system_info() -> SystemInfo {
     .map(|kb| format!("{:.1} GB", kb / 1024.0 / 1024.0)) // Convert from KB to GB
     .unwrap_or_else(|| "N/A".to_string());
 
-    SystemInfo { os_name, kernel, cpu, gpu, ram_total }
+    let base_version = base_system_version().unwrap_or_else(|| "N/A".to_string());
+
+    SystemInfo { os_name, kernel, cpu, gpu, ram_total, base_version }
+}
+
+/// The Kazeta image the system was built from, e.g. "kazeta-plus-1.43". Image builds record it
+/// on the first line of /build_info; older images only have VERSION_ID in /etc/os-release.
+pub fn base_system_version() -> Option<String> {
+    fs::read_to_string("/build_info").ok()
+        .and_then(|info| info.lines().next().map(|line| line.trim().to_string()))
+        .filter(|line| !line.is_empty())
+        .or_else(|| read_line_from_file("/etc/os-release", "VERSION_ID=").map(|id| id.replace('"', "")))
 }
 
 pub fn get_available_sinks() -> Vec<AudioSink> {
@@ -126,6 +139,12 @@ pub fn get_available_sinks() -> Vec<AudioSink> {
 
 /// Gets the current time and formats it using the UTC offset from the config.
 pub fn get_current_local_time_string(config: &Config) -> String {
+    // Format for display (e.g., "05:08 PM")
+    get_current_local_time(config).format("%-I:%M %p").to_string()
+}
+
+/// The current time in the timezone chosen in settings.
+pub fn get_current_local_time(config: &Config) -> DateTime<FixedOffset> {
     // 1. Parse the offset string from the config (e.g., "UTC-4")
     let offset_str = config.timezone.replace("UTC", "");
     let offset_hours: i32 = if offset_str.is_empty() {
@@ -141,10 +160,7 @@ pub fn get_current_local_time_string(config: &Config) -> String {
     let utc_now = Utc::now();
 
     // 4. Convert the UTC time to the desired offset
-    let local_now = utc_now.with_timezone(&fixed_offset);
-
-    // 5. Format for display (e.g., "05:08 PM")
-    local_now.format("%-I:%M %p").to_string()
+    utc_now.with_timezone(&fixed_offset)
 }
 
 /// Gets the current system volume using wpctl.
@@ -191,9 +207,11 @@ pub fn get_battery_info() -> Option<BatteryInfo> {
                 if let (Ok(percentage), Ok(status)) =
                     (fs::read_to_string(capacity_path), fs::read_to_string(status_path))
                     {
+                        let status = status.trim().to_string();
                         return Some(BatteryInfo {
                             percentage: percentage.trim().to_string(),
-                                    status: status.trim().to_string(),
+                            time_remaining: estimate_battery_time(&path, &status),
+                            status,
                         });
                     }
             }
@@ -202,6 +220,155 @@ pub fn get_battery_info() -> Option<BatteryInfo> {
     None
 }
 
+/// Rough time to empty (discharging) or to full (charging), e.g. "2H 15M LEFT".
+/// Uses energy_* / power_now, or charge_* / current_now on batteries that report those instead.
+fn estimate_battery_time(battery_path: &Path, status: &str) -> Option<String> {
+    let read_value = |name: &str| -> Option<f64> {
+        fs::read_to_string(battery_path.join(name)).ok()?.trim().parse().ok()
+    };
+
+    let (now, full, rate) = match (read_value("energy_now"), read_value("energy_full"), read_value("power_now")) {
+        (Some(now), Some(full), Some(rate)) => (now, full, rate),
+        _ => (read_value("charge_now")?, read_value("charge_full")?, read_value("current_now")?),
+    };
+    // Some drivers report a negative rate while discharging
+    let rate = rate.abs();
+    if rate <= 0.0 {
+        return None;
+    }
+
+    let (hours, suffix) = match status {
+        "Discharging" => (now / rate, "LEFT"),
+        "Charging" => ((full - now).max(0.0) / rate, "TO FULL"),
+        _ => return None,
+    };
+
+    let total_minutes = (hours * 60.0).round() as u64;
+    Some(format!("{}H {:02}M {}", total_minutes / 60, total_minutes % 60, suffix))
+}
+
+/// Lists the connected monitors, in xrandr's order. Empty if xrandr isn't available (e.g. Wayland-only setups).
+pub fn get_displays() -> Vec<DisplayInfo> {
+    let Ok(output) = Command::new("xrandr").arg("--listmonitors").output() else {
+        return Vec::new();
+    };
+
+    // Lines look like: " 0: +*DP-1 2560/597x1440/336+0+0  DP-1"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace().skip(1);
+            let flags_name = parts.next()?;
+            let geometry = parts.next()?;
+
+            let (size, position) = geometry.split_once('+')?;
+            let (x, y) = position.split_once('+')?;
+            let (width, height) = size.split_once('x')?;
+            // Drop the physical size in millimetres ("2560/597")
+            let width = width.split('/').next()?;
+            let height = height.split('/').next()?;
+
+            Some(DisplayInfo {
+                name: flags_name.trim_start_matches(['+', '*']).to_string(),
+                primary: flags_name.contains('*'),
+                x: x.parse().ok()?,
+                y: y.parse().ok()?,
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// The preset resolution (and its aspect ratio) that best fits the main display.
+/// Asks xrandr first and falls back to the kernel's mode list when there's no X server to ask.
+pub fn detect_native_resolution() -> Option<(&'static str, &'static str)> {
+    let displays = get_displays();
+    let (width, height) = match displays.iter().find(|d| d.primary).or_else(|| displays.first()) {
+        Some(display) => (display.width, display.height),
+        None => drm_preferred_mode()?,
+    };
+    // Handheld panels are often mounted sideways and report portrait modes (e.g. 800x1280)
+    native_resolution_for(width.max(height), width.min(height))
+}
+
+/// The preferred mode of the first connected DRM output, e.g. (1920, 1080).
+fn drm_preferred_mode() -> Option<(i32, i32)> {
+    let mut connectors: Vec<PathBuf> = fs::read_dir("/sys/class/drm").ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    connectors.sort();
+
+    connectors.iter()
+        .filter(|dir| fs::read_to_string(dir.join("status")).is_ok_and(|s| s.trim() == "connected"))
+        .find_map(|dir| {
+            // The preferred mode is listed first; interlaced modes end in "i"
+            let modes = fs::read_to_string(dir.join("modes")).ok()?;
+            let (width, height) = modes.lines().next()?.split_once('x')?;
+            Some((width.parse().ok()?, height.trim_end_matches('i').parse().ok()?))
+        })
+}
+
+/// Suspends the system (used when the battery is critically low).
+pub fn suspend_system() {
+    match Command::new("systemctl").arg("suspend").status() {
+        Ok(status) if !status.success() => log_error!("Failed to suspend: systemctl exited with {}", status),
+        Ok(_) => {}
+        Err(e) => log_error!("Failed to suspend: {}", e),
+    }
+}
+
+// NETWORK CARTS
+/// Where the network share from config.network_share gets mounted
+fn network_cart_dir() -> Option<PathBuf> {
+    get_user_data_dir().map(|dir| dir.join("run/network"))
+}
+
+/// The network cart folder, if the share is mounted there right now. Reads /proc/mounts only,
+/// so it never touches the network and can't hang when the server is gone.
+pub fn mounted_network_cart_dir() -> Option<PathBuf> {
+    let dir = network_cart_dir()?;
+    let mounts = fs::read_to_string("/proc/mounts").ok()?;
+    let target = dir.to_string_lossy();
+    mounts.lines()
+        .any(|line| line.split_whitespace().nth(1) == Some(target.as_ref()))
+        .then_some(dir)
+}
+
+/// Mounts an SMB (`//server/share`) or NFS (`server:/export`) path read-only for the cart scan.
+/// Blocks for up to MOUNT_TIMEOUT, so call it off the main thread.
+pub fn mount_network_share(share: &str) -> Result<(), String> {
+    const MOUNT_TIMEOUT: &str = "15";
+    if mounted_network_cart_dir().is_some() {
+        return Ok(());
+    }
+    let dir = network_cart_dir().ok_or("no data directory")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+
+    let status = Command::new("timeout")
+        .args([MOUNT_TIMEOUT, "sudo", "kazeta-mount", "net"])
+        .arg(share)
+        .arg(&dir)
+        .status()
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("mount of '{}' failed ({})", share, status))
+    }
+}
+
+/// Unmounts the network share, e.g. after it was removed from the config.
+pub fn unmount_network_share() {
+    if let Some(dir) = mounted_network_cart_dir() {
+        if let Err(e) = Command::new("sudo").args(["kazeta-mount", "net", "--unmount"]).arg(&dir).status() {
+            log_warn!("Failed to unmount the network share: {}", e);
+        }
+    }
+}
+
 /// Gets the current IP address of the device.
 pub fn get_ip_address() -> String {
     let output = Command::new("ip")
@@ -234,3 +401,63 @@ pub fn get_ip_address() -> String {
         Err(_) => "N/A".to_string(),
     }
 }
+
+// PREREQUISITES
+// External tools and files we shell out to. Without these, features fail silently.
+const PREREQUISITES: &[(&str, &str)] = &[
+    ("brightnessctl", "BRIGHTNESS"),
+    ("wpctl", "VOLUME / AUDIO OUTPUT"),
+    ("nmcli", "WI-FI"),
+    ("rfkill", "BLUETOOTH TOGGLE"),
+    ("keyd", "STEAM DECK HOTKEYS"),
+    ("inputplumber", "STEAM DECK BACK BUTTONS"),
+    ("/usr/bin/kazeta", "GAME LAUNCHING"),
+    ("/usr/bin/kazeta-copy-logs", "COPY SESSION LOGS"),
+];
+
+/// Returns true if `name` is an existing absolute path, or an executable found in $PATH.
+fn command_exists(name: &str) -> bool {
+    if name.starts_with('/') {
+        return Path::new(name).exists();
+    }
+
+    std::env::var_os("PATH")
+    .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+    .unwrap_or(false)
+}
+
+/// Checks for the binaries/files listed in PREREQUISITES and returns any that are missing.
+pub fn check_prerequisites() -> Vec<MissingDep> {
+    PREREQUISITES
+    .iter()
+    .filter(|(name, _)| !command_exists(name))
+    .map(|&(name, feature)| MissingDep { name, feature })
+    .collect()
+}
+
+// Remembers which missing deps the user was already warned about, so the warning only shows once
+fn prerequisite_marker_path() -> Option<std::path::PathBuf> {
+    get_user_data_dir().map(|dir| dir.join(".prereq_warning_shown"))
+}
+
+fn missing_names(missing: &[MissingDep]) -> String {
+    missing.iter().map(|d| d.name).collect::<Vec<_>>().join("\n")
+}
+
+/// True if there is something missing that the user hasn't been warned about yet.
+pub fn prerequisite_warning_pending(missing: &[MissingDep]) -> bool {
+    if missing.is_empty() {
+        return false;
+    }
+    let shown = prerequisite_marker_path()
+    .and_then(|path| fs::read_to_string(path).ok())
+    .unwrap_or_default();
+    shown.trim() != missing_names(missing)
+}
+
+/// Records that the user has seen the warning for this set of missing deps.
+pub fn dismiss_prerequisite_warning(missing: &[MissingDep]) {
+    if let Some(path) = prerequisite_marker_path() {
+        let _ = fs::write(path, missing_names(missing));
+    }
+}
@@ -1,6 +1,7 @@
 // Make sure you have the right imports and make your structs public
-use crate::audio::SoundEffects;
-use crate::config::get_user_data_dir;
+use crate::config::{Config, ThemeScheduleEntry, get_user_data_dir, LOGO_SCALE_RANGE};
+use crate::ui::settings::LOGO_ANCHORS;
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
 use macroquad::prelude::*; // for load_string
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -20,15 +21,17 @@ pub struct ThemeConfigFile {
     pub sfx_pack: Option<String>,
     pub bgm_track: Option<String>,
     pub logo_selection: Option<String>,
+    pub logo_scale: Option<f32>,
+    pub logo_anchor: Option<String>,
     pub background_selection: Option<String>,
     pub font_selection: Option<String>,
 }
 
 // This also needs to be public
+// Note: a theme's sound pack isn't preloaded here; it's loaded on activation via sfx_pack_to_reload
 #[derive(Clone)]
 pub struct Theme {
     pub name: String,
-    pub sounds: SoundEffects,
     // Add other pre-loaded assets here if you want
     // pub background: Texture2D,
     pub config: ThemeConfigFile, // Store the parsed config
@@ -37,13 +40,10 @@ pub struct Theme {
 // LOAD CUSTOM THEMES
 pub async fn load_all_themes() -> HashMap<String, Theme> {
     let mut themes = HashMap::new();
-    //let default_sfx = SoundEffects::load("Default").await;
-    let default_sfx = SoundEffects::load("Default");
 
     // create a virtual default theme so we don't crash at startup
     let virtual_default_theme = Theme {
         name: "Default".to_string(),
-        sounds: default_sfx.clone(), // Use the pre-loaded default sounds
         config: ThemeConfigFile { // Create an empty config, just like from an empty theme.toml
             menu_position: None,
             font_color: None,
@@ -56,6 +56,8 @@ pub async fn load_all_themes() -> HashMap<String, Theme> {
             sfx_pack: None,
             bgm_track: None,
             logo_selection: None,
+            logo_scale: None,
+            logo_anchor: None,
             background_selection: None,
             font_selection: None,
         },
@@ -80,15 +82,8 @@ pub async fn load_all_themes() -> HashMap<String, Theme> {
                     // Use macroquad's async load_string to read file contents
                     if let Ok(content) = load_string(&toml_path.to_string_lossy()).await {
                         if let Ok(config) = toml::from_str::<ThemeConfigFile>(&content) {
-                            let sounds = match &config.sfx_pack {
-                                //Some(pack_name) => SoundEffects::load(pack_name).await,
-                                Some(pack_name) => SoundEffects::load(pack_name),
-                                None => default_sfx.clone(),
-                            };
-
                             let loaded_theme = Theme {
                                 name: theme_name.clone(),
-                                sounds,
                                 config,
                             };
 
@@ -102,3 +97,265 @@ pub async fn load_all_themes() -> HashMap<String, Theme> {
     }
     themes
 }
+
+/// theme.toml names its assets by filename. The theme's own files are cached as "<theme>/<filename>"
+/// (see utils::asset_key); anything else (a global asset, "Default", ...) is used as written.
+fn theme_asset_key(theme_name: &str, name: &str) -> String {
+    let in_theme = get_user_data_dir()
+        .is_some_and(|dir| dir.join("themes").join(theme_name).join(name).is_file());
+    if in_theme {
+        format!("{}/{}", theme_name, name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Copies a theme's settings into the config and makes it the active theme.
+/// Returns the SFX pack that needs loading (for sfx_pack_to_reload), if it changed.
+pub fn apply_theme(config: &mut Config, theme_name: &str, loaded_themes: &HashMap<String, Theme>) -> Option<String> {
+    config.theme = theme_name.to_string();
+
+    // The SFX pack the theme wants (themes without one use the default sounds)
+    let mut theme_sfx_pack = "Default".to_string();
+
+    if theme_name == "Default" {
+        log_info!("Switched to Default theme.");
+        let defaults = Config::default();
+        config.set_menu_position(defaults.menu_position());
+
+        theme_sfx_pack = defaults.sfx_pack;
+        config.bgm_track = defaults.bgm_track;
+        config.logo_selection = defaults.logo_selection;
+        config.logo_scale = defaults.logo_scale;
+        config.logo_anchor = defaults.logo_anchor;
+        config.background_selection = defaults.background_selection;
+        config.font_selection = defaults.font_selection;
+        config.font_color = defaults.font_color;
+        config.cursor_color = defaults.cursor_color;
+        config.cursor_style = defaults.cursor_style;
+        config.cursor_blink_speed = defaults.cursor_blink_speed;
+        config.cursor_transition_speed = defaults.cursor_transition_speed;
+        config.background_scroll_speed = defaults.background_scroll_speed;
+        config.color_shift_speed = defaults.color_shift_speed;
+    } else if let Some(theme) = loaded_themes.get(theme_name) {
+        log_info!("Switched to '{}' theme.", theme_name);
+        if let Some(pack) = &theme.config.sfx_pack { theme_sfx_pack = pack.clone(); }
+        config.bgm_track = theme.config.bgm_track.as_deref().map(|track| theme_asset_key(theme_name, track));
+        config.logo_selection = theme.config.logo_selection.as_deref().map_or_else(|| "Kazeta+ (Default)".to_string(), |logo| theme_asset_key(theme_name, logo));
+        config.logo_scale = theme.config.logo_scale.map_or(1.0, |s| s.clamp(*LOGO_SCALE_RANGE.start(), *LOGO_SCALE_RANGE.end()));
+        config.logo_anchor = theme.config.logo_anchor.clone().filter(|a| LOGO_ANCHORS.contains(&a.as_str())).unwrap_or_else(|| "TOP CENTER".to_string());
+        config.background_selection = theme.config.background_selection.as_deref().map_or_else(|| "Default".to_string(), |bg| theme_asset_key(theme_name, bg));
+        config.font_selection = theme.config.font_selection.as_deref().map_or_else(|| "Default".to_string(), |font| theme_asset_key(theme_name, font));
+
+        if let Some(val) = &theme.config.menu_position { config.set_menu_position(val.parse().unwrap_or_default()); }
+        if let Some(val) = &theme.config.font_color { config.font_color = val.clone(); }
+        if let Some(val) = &theme.config.cursor_color { config.cursor_color = val.clone(); }
+        if let Some(val) = &theme.config.cursor_style { config.cursor_style = val.clone(); }
+        if let Some(val) = &theme.config.cursor_blink_speed { config.cursor_blink_speed = val.clone(); }
+        if let Some(val) = &theme.config.cursor_transition_speed { config.cursor_transition_speed = val.clone(); }
+        if let Some(val) = &theme.config.background_scroll_speed { config.background_scroll_speed = val.clone(); }
+        if let Some(val) = &theme.config.color_shift_speed { config.color_shift_speed = val.clone(); }
+    }
+
+    // Switch to the theme's sound pack, unless the user picked one by hand
+    if config.sfx_pack_overridden {
+        log_info!("Keeping manually selected SFX pack '{}'.", config.sfx_pack);
+        None
+    } else if config.sfx_pack != theme_sfx_pack {
+        config.sfx_pack = theme_sfx_pack.clone();
+        Some(theme_sfx_pack)
+    } else {
+        None
+    }
+}
+
+// ===================================
+// THEME SCHEDULE
+// ===================================
+
+/// True if `value` falls in [start, end), wrapping past the end of the range (e.g. 20:00 -> 06:00).
+fn in_wrapping_range<T: PartialOrd>(value: T, start: T, end: T) -> bool {
+    if start <= end {
+        value >= start && value < end
+    } else {
+        value >= start || value < end
+    }
+}
+
+/// Parses "MM-DD" into a comparable (month, day) pair.
+fn parse_month_day(s: &str) -> Option<(u32, u32)> {
+    let (m, d) = s.trim().split_once('-')?;
+    Some((m.parse().ok()?, d.parse().ok()?))
+}
+
+impl ThemeScheduleEntry {
+    /// An entry matches if both its date window and its time window (when given) contain `now`.
+    /// A malformed window never matches.
+    fn matches(&self, now: NaiveDateTime) -> bool {
+        let date_ok = match (&self.start_date, &self.end_date) {
+            (Some(start), Some(end)) => match (parse_month_day(start), parse_month_day(end)) {
+                // end date is inclusive
+                (Some(start), Some(end)) => {
+                    let today = (now.month(), now.day());
+                    today == end || in_wrapping_range(today, start, end)
+                }
+                _ => false,
+            },
+            (None, None) => true,
+            _ => false,
+        };
+
+        let time_ok = match (&self.start, &self.end) {
+            (Some(start), Some(end)) => match (NaiveTime::parse_from_str(start, "%H:%M"), NaiveTime::parse_from_str(end, "%H:%M")) {
+                (Ok(start), Ok(end)) => in_wrapping_range(now.time(), start, end),
+                _ => false,
+            },
+            (None, None) => true,
+            _ => false,
+        };
+
+        date_ok && time_ok
+    }
+}
+
+/// Returns the theme the schedule wants right now (first matching entry wins).
+/// None means no entry matches, so the current theme should be left alone.
+pub fn scheduled_theme(schedule: &[ThemeScheduleEntry], now: NaiveDateTime) -> Option<&str> {
+    schedule.iter().find(|entry| entry.matches(now)).map(|entry| entry.theme.as_str())
+}
+
+/// Switches to the theme the schedule wants at `now`, or back to the user's own theme once no
+/// entry matches. The scheduled theme only lasts for the session and never replaces a theme the
+/// user picked by hand. Returns whether the theme changed.
+pub fn update_scheduled_theme(config: &mut Config, loaded_themes: &HashMap<String, Theme>, now: NaiveDateTime) -> bool {
+    if !config.theme_schedule_allowed() {
+        return false;
+    }
+
+    let scheduled = scheduled_theme(&config.theme_schedule, now)
+        .filter(|theme| loaded_themes.contains_key(*theme))
+        .map(str::to_string);
+    match scheduled {
+        Some(scheduled) if scheduled != config.theme => {
+            log_info!("Theme schedule: switching to '{}'.", scheduled);
+            config.begin_scheduled_theme();
+            apply_theme(config, &scheduled, loaded_themes);
+            true
+        }
+        Some(_) => false,
+        None => {
+            let restored = config.end_scheduled_theme();
+            if restored {
+                log_info!("Theme schedule: back to '{}'.", config.theme);
+            }
+            restored
+        }
+    }
+}
+
+// HOT RELOAD
+/// How often watch_theme_files looks for changes.
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Every file in the user's theme and asset folders (one level into each theme), for change detection.
+fn watched_files() -> Vec<std::path::PathBuf> {
+    let Some(user_dir) = get_user_data_dir() else {
+        return Vec::new();
+    };
+    let list = |dir: std::path::PathBuf| -> Vec<std::path::PathBuf> {
+        fs::read_dir(dir).map(|entries| entries.flatten().map(|e| e.path()).collect()).unwrap_or_default()
+    };
+
+    let mut files: Vec<_> = ["backgrounds", "logos", "fonts", "bgm"].iter()
+        .flat_map(|dir| list(user_dir.join(dir)))
+        .collect();
+    for theme_dir in list(user_dir.join("themes")).into_iter().filter(|p| p.is_dir()) {
+        files.extend(list(theme_dir));
+    }
+    files.retain(|p| p.is_file());
+    files.sort();
+    files
+}
+
+/// Polls the theme and asset folders from a background thread (mtime and size, no inotify)
+/// and sends a message whenever something was added, removed or saved over.
+pub fn watch_theme_files(tx: std::sync::mpsc::Sender<()>) {
+    std::thread::spawn(move || {
+        let mut last = crate::utils::asset_fingerprint(&watched_files());
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            let current = crate::utils::asset_fingerprint(&watched_files());
+            if current != last {
+                last = current;
+                if tx.send(()).is_err() {
+                    return; // main loop is gone
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn night_schedule() -> (Config, HashMap<String, Theme>) {
+        let mut config = Config::default();
+        config.theme_schedule.push(ThemeScheduleEntry {
+            theme: "Night".to_string(),
+            start: Some("20:00".to_string()),
+            end: Some("06:00".to_string()),
+            start_date: None,
+            end_date: None,
+        });
+        let night = Theme {
+            name: "Night".to_string(),
+            config: toml::from_str("font_color = \"#000080\"").unwrap(),
+        };
+        (config, HashMap::from([("Night".to_string(), night)]))
+    }
+
+    #[test]
+    fn scheduled_theme_is_restored_when_no_entry_matches() {
+        let (mut config, themes) = night_schedule();
+        let own_color = config.font_color.clone();
+
+        assert!(update_scheduled_theme(&mut config, &themes, at(21)));
+        assert_eq!(config.theme, "Night");
+        assert_eq!(config.font_color, "#000080");
+        assert!(!update_scheduled_theme(&mut config, &themes, at(23)));
+
+        assert!(update_scheduled_theme(&mut config, &themes, at(12)));
+        assert_eq!(config.theme, "Default");
+        assert_eq!(config.font_color, own_color);
+        assert!(!update_scheduled_theme(&mut config, &themes, at(13)));
+    }
+
+    #[test]
+    fn edits_during_a_schedule_outlast_it() {
+        let (mut config, themes) = night_schedule();
+        update_scheduled_theme(&mut config, &themes, at(21));
+
+        config.set("font_color", "PINK").unwrap();
+        assert_eq!(config.font_color, "PINK");
+        assert!(update_scheduled_theme(&mut config, &themes, at(12)));
+        assert_eq!(config.font_color, "PINK");
+    }
+
+    #[test]
+    fn schedule_leaves_a_picked_theme_alone() {
+        let (mut config, themes) = night_schedule();
+        update_scheduled_theme(&mut config, &themes, at(21));
+
+        // Picking the scheduled theme by hand keeps it past the end of the window
+        config.pick_theme();
+        assert!(!update_scheduled_theme(&mut config, &themes, at(12)));
+        assert_eq!(config.theme, "Night");
+        assert!(!config.end_scheduled_theme());
+    }
+}
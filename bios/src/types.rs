@@ -1,7 +1,10 @@
 use crate::{Color, Vec2, Config, string_to_color, HashMap};
 use macroquad::prelude::*;
 use serde::{Serialize, Deserialize};
+use std::path::PathBuf;
+use std::process::Child;
 use std::str::FromStr;
+use crate::save::CartInfo;
 
 // ===================================
 // TYPES
@@ -19,7 +22,7 @@ pub type SizeCache = HashMap<SizeCacheKey, f32>;
 // ENUMS
 // ===================================
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum MenuPosition {
     Center,
     TopLeft,
@@ -28,6 +31,23 @@ pub enum MenuPosition {
     BottomRight,
 }
 
+/// How PLAY starts a game.
+/// SessionRestart hands the cart to the session via the sentinel file; InPlace
+/// runs it as a child of the BIOS and shows its output on the debug screen.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LaunchMode {
+    SessionRestart,
+    InPlace,
+}
+
+/// How the multi-cart game selection screen lays out the games.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameSelectionView {
+    Grid,
+    List,
+    Carousel,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ShakeTarget {
     None,
@@ -68,17 +88,23 @@ pub enum Screen {
     AssetSettings,
     ConfirmReset,
     ResetComplete,
+    ConfirmResolution,
     Extras,
     Wifi,
     Bluetooth,
     ThemeDownloader,
     ReloadingThemes,
+    AssetManager,
     RuntimeDownloader,
     UpdateChecker,
     Debug,
     GameSelection,
     CdPlayer,
     About,
+    PlaytimeStats,
+    Credits,
+    NoGames,
+    ButtonMapping,
 }
 
 // UI Focus for Save Data Screen
@@ -108,6 +134,35 @@ pub struct CopyOperationState {
     pub running: bool,
     pub should_clear_dialogs: bool,
     pub error_message: Option<String>,
+    /// No progress for a while (flaky or unplugged media); the user may stop waiting
+    pub stalled: bool,
+    /// Bumped for every copy and when one is given up on, so a copy thread that
+    /// finishes late can tell the state no longer belongs to it
+    pub generation: u32,
+    /// Copy threads given up on that are still writing. No new copy starts until they're done.
+    pub abandoned: u32,
+}
+
+impl CopyOperationState {
+    /// Stops waiting on a stalled copy. The copy thread can't be interrupted mid-write,
+    /// so it's left to finish or fail on its own and its result is only logged.
+    pub fn give_up_if_stalled(&mut self) -> bool {
+        if !(self.running && self.stalled) {
+            return false;
+        }
+        log_warn!("Gave up waiting on a stalled save copy at {}%.", self.progress);
+        self.generation = self.generation.wrapping_add(1);
+        self.abandoned += 1;
+        self.running = false;
+        self.stalled = false;
+        self.error_message = Some("ERROR: COPY STOPPED RESPONDING. CHECK THE DRIVES.".to_string());
+        true
+    }
+
+    /// Whether a new copy may start: none running, and none given up on still writing to a drive
+    pub fn can_start_copy(&self) -> bool {
+        !self.running && self.abandoned == 0
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -116,6 +171,40 @@ pub struct AudioSink {
     pub name: String,
 }
 
+/// A binary/file the BIOS relies on that isn't installed, and what stops working without it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingDep {
+    pub name: &'static str,
+    pub feature: &'static str,
+}
+
+/// Sidecar `<name>_logo.json` that turns `<name>_logo.png` into an animated sprite sheet.
+/// Frames are laid out left to right, wrapping after `columns` (all on one row by default).
+#[derive(Deserialize, Debug, Clone)]
+pub struct LogoAnimation {
+    pub frames: u32,
+    pub fps: f32,
+    pub columns: Option<u32>,
+}
+
+/// A game started with the in-place launch mode, kept so the menu can hand control back to it.
+pub struct RunningGame {
+    pub process: Child,
+    pub cart: CartInfo,
+    pub kzi_path: PathBuf,
+}
+
+/// A connected monitor and where it sits on the desktop (from xrandr)
+#[derive(Debug, Clone)]
+pub struct DisplayInfo {
+    pub name: String,
+    pub primary: bool,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
     pub os_name: String,
@@ -123,16 +212,19 @@ pub struct SystemInfo {
     pub cpu: String,
     pub gpu: String,
     pub ram_total: String,
+    pub base_version: String, // the Kazeta image the system was built from
 }
 
 pub struct BatteryInfo {
     pub percentage: String,
     pub status: String,
+    pub time_remaining: Option<String>, // rough time to empty/full, if the battery reports enough to estimate
 }
 
 // color shifting background
 pub struct BackgroundState {
     pub bgx: f32,
+    pub layer_offsets: Vec<f32>, // scroll position of each parallax layer
     pub bg_color: Color,
     pub target: usize,
     pub tg_color: Color,
@@ -149,6 +241,7 @@ pub struct Memory {
 pub struct StorageMedia {
     pub id: String,
     pub free: u32,
+    pub read_only: bool, // mounted read-only (e.g. write-protected SD card)
 }
 
 pub struct AnimationState {
@@ -161,6 +254,10 @@ pub struct AnimationState {
     pub dialog_transition_progress: f32, // Progress of dialog transition (0.0 to 1.0)
     pub dialog_transition_start_pos: Vec2, // Starting position for icon transition
     pub dialog_transition_end_pos: Vec2, // Ending position for icon transition
+    pub hold_progress: f32, // How far a hold-to-confirm has filled (0.0 to 1.0)
+    shake_intensity: f32, // How far things shake, from config.shake_intensity (0 = off)
+    shake_duration: f32, // How long a shake lasts, from config.shake_duration
+    hold_released: bool, // The button has to be let go before another hold can start
 }
 
 // ===================================
@@ -190,6 +287,23 @@ impl FromStr for MenuPosition {
 }
 
 impl MenuPosition {
+    pub const ALL: [MenuPosition; 5] = [Self::Center, Self::TopLeft, Self::TopRight, Self::BottomLeft, Self::BottomRight];
+
+    /// Name in the settings menu, also what Config::set takes
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Center => "CENTER",
+            Self::TopLeft => "TOPLEFT",
+            Self::TopRight => "TOPRIGHT",
+            Self::BottomLeft => "BOTTOMLEFT",
+            Self::BottomRight => "BOTTOMRIGHT",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|position| position.label() == label)
+    }
+
     // Helper function to easily cycle through the options in the settings menu
     pub fn next(&self) -> Self {
         match self {
@@ -212,10 +326,97 @@ impl MenuPosition {
     }
 }
 
+impl LaunchMode {
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::SessionRestart => Self::InPlace,
+            Self::InPlace => Self::SessionRestart,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SessionRestart => "RESTART",
+            Self::InPlace => "IN PLACE",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [Self::SessionRestart, Self::InPlace].into_iter().find(|mode| mode.label() == label)
+    }
+}
+
+impl GameSelectionView {
+    pub const ALL: [GameSelectionView; 3] = [Self::Grid, Self::List, Self::Carousel];
+
+    /// Name in the settings menu, also what Config::set takes
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Grid => "GRID",
+            Self::List => "LIST",
+            Self::Carousel => "CAROUSEL",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|view| view.label() == label)
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Grid => Self::List,
+            Self::List => Self::Carousel,
+            Self::Carousel => Self::Grid,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match self {
+            Self::Grid => Self::Carousel,
+            Self::List => Self::Grid,
+            Self::Carousel => Self::List,
+        }
+    }
+}
+
+impl LogoAnimation {
+    /// The part of the sheet to draw right now, given the sheet's size in pixels.
+    pub fn current_frame(&self, sheet_width: f32, sheet_height: f32) -> Rect {
+        let frames = self.frames.max(1);
+        let columns = self.columns.unwrap_or(frames).clamp(1, frames);
+        let rows = frames.div_ceil(columns);
+        let frame_width = sheet_width / columns as f32;
+        let frame_height = sheet_height / rows as f32;
+
+        let frame = if self.fps > 0.0 { (get_time() * self.fps as f64) as u32 % frames } else { 0 };
+        Rect::new(
+            (frame % columns) as f32 * frame_width,
+            (frame / columns) as f32 * frame_height,
+            frame_width,
+            frame_height,
+        )
+    }
+}
+
+impl RunningGame {
+    /// `None` while the game is still running, otherwise whether it exited cleanly.
+    pub fn exit_result(&mut self) -> Option<bool> {
+        match self.process.try_wait() {
+            Ok(None) => None,
+            Ok(Some(status)) => Some(status.success()),
+            Err(_) => Some(false),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        self.cart.name.as_deref().unwrap_or(&self.cart.id)
+    }
+}
+
 impl AnimationState {
-    const SHAKE_DURATION: f32 = 0.2;    // Duration of shake animation in seconds
-    const SHAKE_INTENSITY: f32 = 3.0;   // How far the arrow shakes
+    const SHAKE_DURATION_RANGE: (f32, f32) = (0.05, 1.0); // Bounds for config.shake_duration, in seconds
     const DIALOG_TRANSITION_DURATION: f32 = 0.4; // Duration of dialog transition animation
+    const HOLD_TO_CONFIRM_DURATION: f32 = 1.0; // How long the confirm button has to be held
 
     pub fn new() -> Self {
         AnimationState {
@@ -228,18 +429,32 @@ impl AnimationState {
             dialog_transition_progress: 0.0,
             dialog_transition_start_pos: Vec2::ZERO,
             dialog_transition_end_pos: Vec2::ZERO,
+            hold_progress: 0.0,
+            shake_intensity: 3.0,
+            shake_duration: 0.2,
+            hold_released: true,
         }
     }
 
     pub fn calculate_shake_offset(&self, target: ShakeTarget) -> f32 {
         if self.shake_target == target && self.shake_time > 0.0 {
-            (self.shake_time / Self::SHAKE_DURATION * std::f32::consts::PI * 8.0).sin() * Self::SHAKE_INTENSITY
+            (self.shake_time / self.shake_duration * std::f32::consts::PI * 8.0).sin() * self.shake_intensity
         } else {
             0.0
         }
     }
 
-    pub fn update_shake(&mut self, delta_time: f32) {
+    pub fn update_shake(&mut self, delta_time: f32, config: &Config) {
+        // Re-read every frame so a settings change applies to the very next shake
+        self.shake_intensity = match config.shake_intensity.as_str() {
+            "OFF" => 0.0,
+            "LOW" => 1.5,
+            "HIGH" => 5.0,
+            _ => 3.0, // NORMAL
+        };
+        let (min_duration, max_duration) = Self::SHAKE_DURATION_RANGE;
+        self.shake_duration = config.shake_duration.clamp(min_duration, max_duration);
+
         // Update shake animation
         if self.shake_time > 0.0 {
             self.shake_time = (self.shake_time - delta_time).max(0.0);
@@ -274,29 +489,49 @@ impl AnimationState {
         }
     }
 
+    /// Starts a shake on `target`. Does nothing with shaking turned off; callers play the reject sound themselves.
+    fn start_shake(&mut self, target: ShakeTarget) {
+        if self.shake_intensity > 0.0 {
+            self.shake_target = target;
+            self.shake_time = self.shake_duration;
+        }
+    }
+
     pub fn trigger_shake(&mut self, is_left: bool) {
-        if is_left {
-            self.shake_target = ShakeTarget::LeftArrow;
-            self.shake_time = Self::SHAKE_DURATION;
-        } else {
-            self.shake_target = ShakeTarget::RightArrow;
-            self.shake_time = Self::SHAKE_DURATION;
+        self.start_shake(if is_left { ShakeTarget::LeftArrow } else { ShakeTarget::RightArrow });
+    }
+
+    /// Fills the hold-to-confirm ring while `held`, emptying it when the button is let go.
+    /// Returns true on the frame it fills up.
+    pub fn update_hold(&mut self, held: bool, delta_time: f32) -> bool {
+        if !held {
+            self.hold_progress = 0.0;
+            self.hold_released = true;
+            return false;
+        }
+        if !self.hold_released {
+            return false;
+        }
+
+        self.hold_progress = (self.hold_progress + delta_time / Self::HOLD_TO_CONFIRM_DURATION).min(1.0);
+        if self.hold_progress >= 1.0 {
+            self.hold_progress = 0.0;
+            self.hold_released = false;
+            return true;
         }
+        false
     }
 
     pub fn trigger_dialog_shake(&mut self) {
-        self.shake_target = ShakeTarget::Dialog;
-        self.shake_time = Self::SHAKE_DURATION;
+        self.start_shake(ShakeTarget::Dialog);
     }
 
     pub fn trigger_play_option_shake(&mut self) {
-        self.shake_target = ShakeTarget::PlayOption;
-        self.shake_time = Self::SHAKE_DURATION;
+        self.start_shake(ShakeTarget::PlayOption);
     }
 
     pub fn trigger_copy_log_option_shake(&mut self) {
-        self.shake_target = ShakeTarget::CopyLogOption;
-        self.shake_time = Self::SHAKE_DURATION;
+        self.start_shake(ShakeTarget::CopyLogOption);
     }
 
     pub fn trigger_transition(&mut self, speed_setting: &str) {
@@ -359,3 +594,29 @@ impl AnimationState {
         self.dialog_transition_start_pos.lerp(self.dialog_transition_end_pos, t)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_copy_starts_while_an_abandoned_one_is_writing() {
+        let mut state = CopyOperationState {
+            progress: 40,
+            running: true,
+            should_clear_dialogs: false,
+            error_message: None,
+            stalled: true,
+            generation: 1,
+            abandoned: 0,
+        };
+        assert!(!state.can_start_copy());
+        assert!(state.give_up_if_stalled());
+        assert!(!state.running);
+        assert!(!state.can_start_copy());
+
+        // The late thread checks out once it's done
+        state.abandoned -= 1;
+        assert!(state.can_start_copy());
+    }
+}
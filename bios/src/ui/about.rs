@@ -1,7 +1,7 @@
 use crate::{
     VideoPlayer,
     audio::SoundEffects,
-    config::Config, FONT_SIZE, SystemInfo, Screen, BackgroundState, BatteryInfo, render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color, InputState,
+    config::Config, BUILD_COMMIT, FONT_SIZE, VERSION_NUMBER, SystemInfo, Screen, BackgroundState, BatteryInfo, render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color, InputState,
     system::get_ip_address,
 };
 use macroquad::prelude::*;
@@ -16,6 +16,12 @@ pub fn update(
     if input_state.back {
         *current_screen = Screen::MainMenu;
         sound_effects.play_back(config);
+    } else if input_state.select {
+        *current_screen = Screen::PlaytimeStats;
+        sound_effects.play_select(config);
+    } else if input_state.secondary {
+        *current_screen = Screen::Credits;
+        sound_effects.play_select(config);
     }
 }
 
@@ -30,6 +36,7 @@ pub fn draw(
     battery_info: &Option<BatteryInfo>,
     current_time_str: &str,
     gcc_adapter_poll_rate: &Option<u32>,
+    total_playtime: f32,
     scale_factor: f32,
 ) {
     render_background(&background_cache, video_cache, &config, background_state);
@@ -51,19 +58,35 @@ pub fn draw(
     let ip_address = get_ip_address();
 
     // --- Hardware Info ---
-    let info = vec![
-        ("OS:", &system_info.os_name),
-        ("KERNEL:", &system_info.kernel),
-        ("CPU:", &system_info.cpu),
-        ("GPU:", &system_info.gpu),
-        ("MEMORY:", &system_info.ram_total),
-        ("IP:", &ip_address), // Display the IP address
+    let mut info = vec![
+        ("BIOS:", format!("{} ({})", VERSION_NUMBER, BUILD_COMMIT)),
+        ("BASE:", system_info.base_version.clone()),
+        ("OS:", system_info.os_name.clone()),
+        ("KERNEL:", system_info.kernel.clone()),
+        ("CPU:", system_info.cpu.clone()),
+        ("GPU:", system_info.gpu.clone()),
+        ("MEMORY:", system_info.ram_total.clone()),
+        ("IP:", ip_address), // Display the IP address
     ];
 
+    if let Some(battery) = battery_info {
+        let battery_text = match &battery.time_remaining {
+            Some(estimate) => format!("{}% ({})", battery.percentage, estimate),
+            None => format!("{}%", battery.percentage),
+        };
+        info.push(("BATTERY:", battery_text));
+    }
+
+    // Across every save on every connected drive
+    info.push(("PLAYTIME:", format!("{:.1} H TOTAL", total_playtime)));
+
+    // Rows squeeze together when there are more than fit above the credits
+    let rows_bottom = screen_height() - (100.0 * scale_factor);
+    let row_height = line_height.min((rows_bottom - current_y) / info.len() as f32);
     for (label, value) in info {
         text_with_config_color(font_cache, config, label, start_x_labels, current_y, about_font_size);
-        text_with_config_color(font_cache, config, value, start_x_values, current_y, about_font_size);
-        current_y += line_height;
+        text_with_config_color(font_cache, config, &value, start_x_values, current_y, about_font_size);
+        current_y += row_height;
     }
 
     // --- Credits ---
@@ -0,0 +1,321 @@
+use crate::{
+    audio::SoundEffects,
+    config::{Config, get_user_data_dir},
+    input::{Action, Bindings},
+    ui::action_label,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, measure_readable_text, InputState, VideoPlayer,
+};
+use macroquad::prelude::*;
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+};
+
+// --- CONSTANTS ---
+const ITEMS_PER_PAGE: usize = 8;
+
+/// Data dir folders listed by the manager: (folder, label, one subfolder per entry)
+const ASSET_KINDS: &[(&str, &str, bool)] = &[
+    ("themes", "THEME", true),
+    ("backgrounds", "BACKGROUND", false),
+    ("logos", "LOGO", false),
+    ("fonts", "FONT", false),
+    ("bgm", "MUSIC", false),
+    ("sfx", "SOUND PACK", true),
+];
+
+// --- State Management & Structs ---
+
+/// Something the user installed into the data dir. The built-in assets live
+/// next to the BIOS, not in the data dir, so they never show up here.
+pub struct InstalledAsset {
+    pub kind: &'static str,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+pub enum AssetManagerView {
+    List,
+    ConfirmDelete { selection: usize }, // 0=Yes, 1=No
+}
+
+pub struct AssetManagerState {
+    pub view: AssetManagerView,
+    pub assets: Vec<InstalledAsset>,
+    pub selected_index: usize,
+    pub message: Option<String>,
+    loaded: bool,
+}
+
+impl AssetManagerState {
+    pub fn new() -> Self {
+        Self {
+            view: AssetManagerView::List,
+            assets: Vec::new(),
+            selected_index: 0,
+            message: None,
+            loaded: false,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.assets = find_installed_assets();
+        self.selected_index = self.selected_index.min(self.assets.len().saturating_sub(1));
+        self.loaded = true;
+    }
+}
+
+// --- Implementation ---
+
+fn find_installed_assets() -> Vec<InstalledAsset> {
+    let Some(data_dir) = get_user_data_dir() else { return Vec::new() };
+    let mut assets = Vec::new();
+
+    for (folder, kind, is_pack) in ASSET_KINDS {
+        let Ok(entries) = fs::read_dir(data_dir.join(folder)) else { continue };
+        let mut found: Vec<InstalledAsset> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir() == *is_pack)
+            // Dot-folders are theme installs still in progress
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| InstalledAsset {
+                kind,
+                name: entry.file_name().to_string_lossy().into_owned(),
+                path: entry.path(),
+            })
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        assets.extend(found);
+    }
+    assets
+}
+
+/// Whether the asset is what the current setup is using, so deleting it would pull it out from under us
+fn is_in_use(asset: &InstalledAsset, config: &Config) -> bool {
+    match asset.kind {
+        "THEME" => config.theme == asset.name,
+        "SOUND PACK" => config.sfx_pack == asset.name,
+        // What's on screen, so a RANDOM pick or a cart's menu music counts too
+        _ => [
+            config.active_background(),
+            config.active_logo(),
+            config.font_selection.as_str(),
+            config.active_bgm_track(),
+        ].contains(&asset.name.as_str()),
+    }
+}
+
+fn delete_asset(asset: &InstalledAsset) -> std::io::Result<()> {
+    if asset.path.is_dir() {
+        fs::remove_dir_all(&asset.path)
+    } else {
+        fs::remove_file(&asset.path)
+    }
+}
+
+/// Handles input for the asset manager. Returns true when something was deleted
+/// and the assets need reloading.
+pub fn update(
+    state: &mut AssetManagerState,
+    input_state: &InputState,
+    current_screen: &mut Screen,
+    sound_effects: &SoundEffects,
+    config: &Config,
+) -> bool {
+    if !state.loaded {
+        state.refresh();
+    }
+
+    match &mut state.view {
+        AssetManagerView::List => {
+            if input_state.back {
+                sound_effects.play_back(config);
+                *current_screen = Screen::Extras;
+                state.message = None;
+                state.loaded = false; // rescan next time in case something was imported
+                return false;
+            }
+            if state.assets.is_empty() {
+                return false;
+            }
+
+            if input_state.down && state.selected_index < state.assets.len() - 1 {
+                state.selected_index += 1;
+                sound_effects.play_cursor_move(config);
+            }
+            if input_state.up && state.selected_index > 0 {
+                state.selected_index -= 1;
+                sound_effects.play_cursor_move(config);
+            }
+            if input_state.right {
+                let next_page = (state.selected_index / ITEMS_PER_PAGE + 1) * ITEMS_PER_PAGE;
+                if next_page < state.assets.len() {
+                    state.selected_index = next_page;
+                    sound_effects.play_cursor_move(config);
+                }
+            }
+            if input_state.left && state.selected_index >= ITEMS_PER_PAGE {
+                state.selected_index = (state.selected_index / ITEMS_PER_PAGE - 1) * ITEMS_PER_PAGE;
+                sound_effects.play_cursor_move(config);
+            }
+
+            if input_state.select || input_state.secondary {
+                let asset = &state.assets[state.selected_index];
+                if is_in_use(asset, config) {
+                    sound_effects.play_reject(config);
+                    state.message = Some(format!("'{}' is in use. Switch away from it first.", asset.name));
+                } else {
+                    sound_effects.play_select(config);
+                    state.message = None;
+                    state.view = AssetManagerView::ConfirmDelete { selection: 1 }; // Default to "NO"
+                }
+            }
+        }
+        AssetManagerView::ConfirmDelete { selection } => {
+            if input_state.left || input_state.right {
+                *selection = 1 - *selection;
+                sound_effects.play_cursor_move(config);
+            }
+            if input_state.back {
+                sound_effects.play_back(config);
+                state.view = AssetManagerView::List;
+                return false;
+            }
+            if input_state.select {
+                sound_effects.play_select(config);
+                let confirmed = *selection == 0;
+                state.view = AssetManagerView::List;
+                if confirmed {
+                    let asset = &state.assets[state.selected_index];
+                    match delete_asset(asset) {
+                        Ok(_) => {
+                            log_info!("Deleted {} '{}' ({})", asset.kind.to_lowercase(), asset.name, asset.path.display());
+                            state.message = Some(format!("'{}' deleted.", asset.name));
+                            state.loaded = false;
+                            return true;
+                        }
+                        Err(e) => {
+                            log_error!("Failed to delete {}: {}", asset.path.display(), e);
+                            state.message = Some(format!("Failed to delete: {}", e));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+pub fn draw(
+    state: &AssetManagerState,
+    animation_state: &mut crate::AnimationState,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+
+    let font = get_current_font(font_cache, config);
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (font_size as f32 * 0.8) as u16;
+    let line_height = font_size as f32 * 1.5;
+
+    // Create a container for the UI
+    let container_w = screen_width() * 0.9;
+    let container_h = screen_height() * 0.8;
+    let container_x = (screen_width() - container_w) / 2.0;
+    let container_y = (screen_height() - container_h) / 2.0;
+    draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    let text_x = container_x + 30.0 * scale_factor;
+    let text_y_start = container_y + 40.0 * scale_factor;
+
+    if state.assets.is_empty() {
+        text_with_config_color(font_cache, config, "No custom assets installed.", text_x, text_y_start, font_size);
+        return;
+    }
+
+    let current_page = state.selected_index / ITEMS_PER_PAGE;
+    let total_pages = state.assets.len().div_ceil(ITEMS_PER_PAGE);
+    let start_index = current_page * ITEMS_PER_PAGE;
+    let end_index = (start_index + ITEMS_PER_PAGE).min(state.assets.len());
+
+    for i in start_index..end_index {
+        let asset = &state.assets[i];
+        let y_pos = text_y_start + ((i - start_index) as f32 * line_height) + 20.0;
+
+        if i == state.selected_index {
+            let cursor_color = animation_state.get_cursor_color(config);
+            draw_rectangle(container_x, y_pos - font_size as f32 - 5.0, container_w, line_height, Color::new(cursor_color.r, cursor_color.g, cursor_color.b, 0.3));
+        }
+
+        let in_use_flag = if is_in_use(asset, config) { " [IN USE]" } else { "" };
+        let display_text = format!("{}: {}{}", asset.kind, asset.name, in_use_flag);
+        text_with_config_color(font_cache, config, &display_text, text_x, y_pos, font_size);
+    }
+
+    if total_pages > 1 {
+        let page_text = format!("Page {} / {}", current_page + 1, total_pages);
+        let page_dims = measure_text(&page_text, Some(font), small_font_size, 1.0);
+        text_with_config_color(font_cache, config, &page_text, screen_width() / 2.0 - page_dims.width / 2.0, text_y_start - (line_height * 0.8), small_font_size);
+    }
+
+    // Status line above the hint
+    if let Some(message) = &state.message {
+        let message_dims = measure_text(message, Some(font), small_font_size, 1.0);
+        text_with_config_color(font_cache, config, message, screen_width() / 2.0 - message_dims.width / 2.0, container_y + container_h - 20.0 - line_height, small_font_size);
+    }
+
+    let hint_text = &format!("Press {} to Delete", action_label(&Bindings::from_config(config), &[Action::Select]));
+    let hint_dims = measure_text(hint_text, Some(font), small_font_size, 1.0);
+    text_with_config_color(font_cache, config, hint_text, screen_width() / 2.0 - hint_dims.width / 2.0, container_y + container_h - 20.0, small_font_size);
+
+    if let AssetManagerView::ConfirmDelete { selection } = state.view {
+        let dialog_w = 400.0 * scale_factor;
+        let dialog_h = 150.0 * scale_factor;
+        let dialog_x = screen_width() / 2.0 - dialog_w / 2.0;
+        let dialog_y = screen_height() / 2.0 - dialog_h / 2.0;
+        draw_rectangle(dialog_x, dialog_y, dialog_w, dialog_h, Color::new(0.1, 0.1, 0.1, 0.9));
+        draw_rectangle_lines(dialog_x, dialog_y, dialog_w, dialog_h, 3.0, WHITE);
+
+        let question = format!("Delete '{}'?", state.assets[state.selected_index].name);
+        let question_dims = measure_readable_text(font_cache, config, &question, font_size);
+        text_with_config_color(font_cache, config, &question, screen_width() / 2.0 - question_dims.width / 2.0, dialog_y + 40.0 * scale_factor, font_size);
+
+        let yes_text = "YES";
+        let no_text = "NO";
+        let yes_dims = measure_text(yes_text, Some(font), font_size, 1.0);
+        let no_dims = measure_text(no_text, Some(font), font_size, 1.0);
+        let yes_x = screen_width() / 2.0 - yes_dims.width - 20.0 * scale_factor;
+        let no_x = screen_width() / 2.0 + 20.0 * scale_factor;
+        let options_y = dialog_y + dialog_h - 50.0 * scale_factor;
+        text_with_config_color(font_cache, config, yes_text, yes_x, options_y, font_size);
+        text_with_config_color(font_cache, config, no_text, no_x, options_y, font_size);
+
+        let cursor_x = if selection == 0 { yes_x } else { no_x };
+        let cursor_w = if selection == 0 { yes_dims.width } else { no_dims.width };
+        let cursor_color = animation_state.get_cursor_color(config);
+        draw_rectangle_lines(cursor_x - 5.0, options_y - font_size as f32, cursor_w + 10.0, line_height, 3.0, cursor_color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_picks_count_as_in_use() {
+        let mut config = Config::default();
+        config.set("background_selection", crate::config::RANDOM_CHOICE).unwrap();
+        let backgrounds = ["Stars_background.png".to_string()];
+        config.resolve_random_picks(&backgrounds, &[], &[]);
+
+        let asset = |name: &str| InstalledAsset { kind: "BACKGROUND", name: name.to_string(), path: PathBuf::new() };
+        assert!(is_in_use(&asset("Stars_background.png"), &config));
+        assert!(!is_in_use(&asset("Clouds_background.png"), &config));
+    }
+}
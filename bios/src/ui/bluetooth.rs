@@ -7,7 +7,7 @@ use crate::{
     config::Config,
     types::{AnimationState, BackgroundState, BatteryInfo, Screen},
     ui::text_with_color,
-    render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color,
+    render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color, measure_readable_text,
     FONT_SIZE, InputState, DEV_MODE, VideoPlayer,
 };
 use futures::StreamExt;
@@ -243,7 +243,7 @@ pub fn draw(
             } else {
                 for (i, device) in state.devices.iter().enumerate() {
                     let y_pos = start_y + (i as f32 * line_height);
-                    let dims = measure_text(&device.name, Some(font), font_size, 1.0);
+                    let dims = measure_readable_text(font_cache, config, &device.name, font_size);
                     let x_pos = center_x - dims.width / 2.0;
 
                     let is_selected = i == state.selected_index;
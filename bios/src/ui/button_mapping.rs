@@ -0,0 +1,149 @@
+use crate::{
+    audio::SoundEffects,
+    config::Config,
+    input::{self, Action, Bindings},
+    ui::text_with_color,
+    utils::step_selection,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, measure_text, string_to_color, text_with_config_color, InputState, VideoPlayer,
+};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+// --- CONSTANTS ---
+/// Give up waiting for a button after this long, so a dead controller can't trap the screen
+const CAPTURE_TIMEOUT: f64 = 5.0;
+
+// --- State Management & Structs ---
+
+pub struct ButtonMappingState {
+    /// One row per action, then RESET TO DEFAULTS
+    selection: usize,
+    /// The action waiting for a button or key, and when the wait started
+    capturing: Option<(Action, f64)>,
+}
+
+impl ButtonMappingState {
+    pub fn new() -> Self {
+        Self {
+            selection: 0,
+            capturing: None,
+        }
+    }
+}
+
+// --- Implementation ---
+
+pub fn update(
+    state: &mut ButtonMappingState,
+    input_state: &mut InputState,
+    current_screen: &mut Screen,
+    sound_effects: &SoundEffects,
+    config: &mut Config,
+) {
+    if let Some((action, started)) = state.capturing {
+        // Whatever is pressed next belongs to the action, even the current SELECT or BACK
+        let bound = match (input_state.last_button, input_state.last_key) {
+            (Some(button), _) => input::bind_button(config, action, button),
+            (None, Some(key)) => input::bind_key(config, action, key),
+            (None, None) => {
+                if get_time() - started > CAPTURE_TIMEOUT {
+                    state.capturing = None;
+                    sound_effects.play_back(config);
+                }
+                return;
+            }
+        };
+        if bound {
+            input_state.apply_bindings(config);
+            sound_effects.play_select(config);
+        } else {
+            sound_effects.play_reject(config);
+        }
+        state.capturing = None;
+        return;
+    }
+
+    let rows = Action::ALL.len() + 1;
+    if input_state.down {
+        state.selection = step_selection(state.selection, rows, true, config.menu_wrap);
+        sound_effects.play_cursor_move(config);
+    }
+    if input_state.up {
+        state.selection = step_selection(state.selection, rows, false, config.menu_wrap);
+        sound_effects.play_cursor_move(config);
+    }
+
+    if input_state.back {
+        *current_screen = Screen::GeneralSettings;
+        sound_effects.play_back(config);
+    } else if input_state.select {
+        match Action::ALL.get(state.selection) {
+            Some(&action) => state.capturing = Some((action, get_time())),
+            None => {
+                input::reset_bindings(config);
+                input_state.apply_bindings(config);
+            }
+        }
+        sound_effects.play_select(config);
+    }
+}
+
+pub fn draw(
+    state: &ButtonMappingState,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+
+    let font = get_current_font(font_cache, config);
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (font_size as f32 * 0.8) as u16;
+    let cursor_color = string_to_color(&config.cursor_color);
+
+    // Create a container for the UI
+    let container_w = screen_width() * 0.9;
+    let container_h = screen_height() * 0.8;
+    let container_x = (screen_width() - container_w) / 2.0;
+    let container_y = (screen_height() - container_h) / 2.0;
+    draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    let title = "BUTTON MAPPING";
+    let title_dims = measure_text(title, Some(font), font_size, 1.0);
+    text_with_color(font_cache, config, title, screen_width() / 2.0 - title_dims.width / 2.0, container_y + 30.0 * scale_factor, font_size, cursor_color);
+
+    let text_x = container_x + 30.0 * scale_factor;
+    let button_x = container_x + container_w * 0.35;
+    let key_x = container_x + container_w * 0.65;
+    let mut y_pos = container_y + 60.0 * scale_factor;
+    let line_height = (container_h - 90.0 * scale_factor) / (Action::ALL.len() + 2) as f32;
+
+    text_with_config_color(font_cache, config, "CONTROLLER", button_x, y_pos, small_font_size);
+    text_with_config_color(font_cache, config, "KEYBOARD", key_x, y_pos, small_font_size);
+    y_pos += line_height;
+
+    let bindings = Bindings::from_config(config);
+    for (row, action) in Action::ALL.into_iter().enumerate() {
+        let selected = row == state.selection;
+        let color = if selected { cursor_color } else { string_to_color(&config.font_color) };
+        text_with_color(font_cache, config, action.name(), text_x, y_pos, small_font_size, color);
+        match state.capturing {
+            Some((capturing, started)) if capturing == action => {
+                let remaining = (CAPTURE_TIMEOUT - (get_time() - started)).ceil().max(0.0);
+                let prompt = format!("PRESS A BUTTON OR KEY FOR {}... ({:.0})", action.name(), remaining);
+                text_with_color(font_cache, config, &prompt, button_x, y_pos, small_font_size, cursor_color);
+            }
+            _ => {
+                text_with_color(font_cache, config, &input::button_label(bindings.button(action)), button_x, y_pos, small_font_size, color);
+                text_with_color(font_cache, config, &input::key_label(bindings.key(action)), key_x, y_pos, small_font_size, color);
+            }
+        }
+        y_pos += line_height;
+    }
+
+    let reset_color = if state.selection == Action::ALL.len() { cursor_color } else { string_to_color(&config.font_color) };
+    text_with_color(font_cache, config, "RESET TO DEFAULTS", text_x, y_pos, small_font_size, reset_color);
+}
@@ -3,7 +3,8 @@ use crate::{
     cd_player_backend::{CdPlayerBackend, PlayerStatus},
     config::Config,
     types::{AnimationState, BackgroundState, Screen},
-    ui::text_with_color,
+    input::{Action, Bindings},
+    ui::{action_label, text_with_color},
     render_background, get_current_font, measure_text, text_with_config_color, InputState, VideoPlayer,
 };
 use macroquad::prelude::*;
@@ -384,7 +385,8 @@ pub fn draw(
     }
 
     // --- Draw Controls Help ---
-    let help_text = "[SOUTH] PLAY/PAUSE | [EAST] BACK | [LB/RB] SEEK 15 SECONDS";
+    let bindings = Bindings::from_config(config);
+    let help_text = &format!("{} PLAY/PAUSE | {} BACK | {} SEEK 15 SECONDS", action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Back]), action_label(&bindings, &[Action::Prev, Action::Next]));
     let help_dims = measure_text(help_text, Some(current_font), (12.0 * scale_factor) as u16, 1.0);
     text_with_config_color(font_cache, config, help_text, (screen_width() - help_dims.width) / 2.0, screen_height() - (20.0 * scale_factor), (12.0 * scale_factor) as u16);
 }
@@ -0,0 +1,130 @@
+use crate::{
+    audio::SoundEffects,
+    config::{Config, get_user_data_dir},
+    ui::text_with_color,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, measure_text, string_to_color, text_with_config_color, InputState, VideoPlayer,
+};
+use macroquad::prelude::*;
+use std::{collections::HashMap, fs};
+
+// --- CONSTANTS ---
+const LINES_PER_PAGE: usize = 12;
+const BUILTIN_CREDITS: &str = include_str!("../../credits.txt");
+
+// --- State Management & Structs ---
+
+pub struct CreditsState {
+    lines: Vec<String>,
+    scroll: usize,
+    loaded: bool,
+}
+
+impl CreditsState {
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            scroll: 0,
+            loaded: false,
+        }
+    }
+
+    /// Reads credits.txt from the user data folder, falling back to the built-in copy.
+    /// Comment lines ("#" not followed by a second "#") are dropped.
+    fn refresh(&mut self) {
+        let text = get_user_data_dir()
+            .and_then(|dir| fs::read_to_string(dir.join("credits.txt")).ok())
+            .unwrap_or_else(|| BUILTIN_CREDITS.to_string());
+        self.lines = text.lines()
+            .filter(|line| !line.starts_with('#') || line.starts_with("##"))
+            .map(|line| line.trim_end().to_string())
+            .skip_while(|line| line.is_empty())
+            .collect();
+        self.scroll = 0;
+        self.loaded = true;
+    }
+}
+
+// --- Implementation ---
+
+pub fn update(
+    state: &mut CreditsState,
+    input_state: &InputState,
+    current_screen: &mut Screen,
+    sound_effects: &SoundEffects,
+    config: &Config,
+) {
+    if !state.loaded {
+        state.refresh();
+    }
+
+    if input_state.back {
+        sound_effects.play_back(config);
+        *current_screen = Screen::About;
+        state.loaded = false; // pick up edits to credits.txt next time
+        return;
+    }
+
+    let max_scroll = state.lines.len().saturating_sub(LINES_PER_PAGE);
+    if input_state.down && state.scroll < max_scroll {
+        state.scroll += 1;
+        sound_effects.play_cursor_move(config);
+    }
+    if input_state.up && state.scroll > 0 {
+        state.scroll -= 1;
+        sound_effects.play_cursor_move(config);
+    }
+}
+
+pub fn draw(
+    state: &CreditsState,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+
+    let font = get_current_font(font_cache, config);
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (font_size as f32 * 0.8) as u16;
+
+    // Create a container for the UI
+    let container_w = screen_width() * 0.9;
+    let container_h = screen_height() * 0.8;
+    let container_x = (screen_width() - container_w) / 2.0;
+    let container_y = (screen_height() - container_h) / 2.0;
+    draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    let text_x = container_x + 30.0 * scale_factor;
+    let text_y_start = container_y + 30.0 * scale_factor;
+    // Leave the bottom row free for the position hint
+    let line_height = (container_h - 60.0 * scale_factor) / LINES_PER_PAGE as f32;
+
+    for (row, line) in state.lines.iter().skip(state.scroll).take(LINES_PER_PAGE).enumerate() {
+        let y_pos = text_y_start + row as f32 * line_height;
+        match line.strip_prefix("##") {
+            Some(heading) => text_with_color(font_cache, config, heading.trim(), text_x, y_pos, font_size, string_to_color(&config.cursor_color)),
+            None => text_with_config_color(font_cache, config, line, text_x, y_pos, small_font_size),
+        }
+    }
+
+    if state.lines.len() > LINES_PER_PAGE {
+        let hint = format!("{}-{} OF {}", state.scroll + 1, (state.scroll + LINES_PER_PAGE).min(state.lines.len()), state.lines.len());
+        let hint_dims = measure_text(&hint, Some(font), small_font_size, 1.0);
+        text_with_config_color(font_cache, config, &hint, screen_width() / 2.0 - hint_dims.width / 2.0, container_y + container_h - 10.0 * scale_factor, small_font_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_bundled_sound_is_credited() {
+        for sound in ["back.wav", "move.wav", "reject.wav", "select.wav", "splash.wav"] {
+            assert!(BUILTIN_CREDITS.contains(sound), "{} is missing from credits.txt", sound);
+        }
+    }
+}
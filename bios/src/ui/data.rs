@@ -3,7 +3,8 @@ use futures;
 use crate::{*, ui::dialog::*, memory::*}; // Use wildcards for convenience or specify each type
 use crate::audio::SoundEffects;
 
-// This function will handle all input and state changes for the data screen
+// This function will handle all input and state changes for the data screen.
+// Returns a message for the caller to flash, if any.
 pub async fn update(
     input_state: &mut InputState,
     current_screen: &mut Screen,
@@ -20,7 +21,11 @@ pub async fn update(
     animation_state: &mut AnimationState,
     scale_factor: f32,
     copy_op_state: &Arc<Mutex<CopyOperationState>>,
-) {
+    playtime_cache: &mut PlaytimeCache,
+    size_cache: &mut SizeCache,
+    running_cart: Option<&str>,
+) -> Option<String> {
+    let mut toast = None;
     let mut action_dialog_id = String::new();
     let mut action_option_value = String::new();
 
@@ -44,6 +49,23 @@ pub async fn update(
                 sound_effects.play_back(&config);
             }
 
+            // Rescan the drives now rather than waiting for the storage thread's next poll
+            if input_state.secondary {
+                if let Ok(mut state) = storage_state.lock() {
+                    match state.rescan() {
+                        Some(found) => {
+                            log_info!("Manual storage rescan found {} usable device(s)", found);
+                            toast = Some(format!("RESCANNED: {} STORAGE DEVICE(S) FOUND", found));
+                            sound_effects.play_select(config);
+                        }
+                        None => {
+                            toast = Some("RESCAN FAILED: COULD NOT LIST STORAGE DEVICES".to_string());
+                            sound_effects.play_error(config);
+                        }
+                    }
+                }
+            }
+
             // Handle storage media switching with tab/bumpers regardless of focus
             if input_state.cycle || input_state.next || input_state.prev {
                 if let Ok(mut state) = storage_state.lock() {
@@ -57,7 +79,7 @@ pub async fn update(
                         }
                     } else if input_state.next {
                         // Next stops at end
-                        if state.selected < state.media.len() - 1 {
+                        if state.selected + 1 < state.media.len() {
                             state.selected += 1;
                             *memories = load_memories(&state.media[state.selected], icon_cache, icon_queue).await;
                             *scroll_offset = 0;
@@ -85,12 +107,12 @@ pub async fn update(
                 UIFocus::Grid => {
                     if input_state.select {
                         let memory_index = get_memory_index(*selected_memory, *scroll_offset);
-                        if let Some(_) = memories.get(memory_index) {
-                            let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                        if let Some(mem) = memories.get(memory_index) {
+                            let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                             animation_state.trigger_dialog_transition(grid_pos, dialog_pos);
-                            dialogs.push(create_main_dialog(&storage_state));
+                            dialogs.push(create_main_dialog(&storage_state, running_cart == Some(mem.id.as_str())));
                             *dialog_state = DialogState::Opening;
-                            sound_effects.play_select(&config);
+                            sound_effects.play_dialog_open(config);
                         }
                     }
                     if input_state.right && *selected_memory < GRID_WIDTH * GRID_HEIGHT - 1 {
@@ -115,6 +137,12 @@ pub async fn update(
                                 *scroll_offset += 1;
                                 animation_state.trigger_transition(&config.cursor_transition_speed);
                                 sound_effects.play_cursor_move(&config);
+                            } else if config.menu_wrap {
+                                // Wrap back around to the top row, keeping the column
+                                *scroll_offset = 0;
+                                *selected_memory %= GRID_WIDTH;
+                                animation_state.trigger_transition(&config.cursor_transition_speed);
+                                sound_effects.play_cursor_move(&config);
                             }
                         }
                     }
@@ -137,6 +165,13 @@ pub async fn update(
                                 input_state.ui_focus = UIFocus::StorageRight;
                                 animation_state.trigger_transition(&config.cursor_transition_speed);
                                 sound_effects.play_cursor_move(&config);
+                            } else if config.menu_wrap {
+                                // Wrap around to the last row that has saves, keeping the column
+                                let rows = memories.len().div_ceil(GRID_WIDTH).max(1);
+                                *scroll_offset = rows.saturating_sub(GRID_HEIGHT);
+                                *selected_memory += GRID_WIDTH * (rows.min(GRID_HEIGHT) - 1);
+                                animation_state.trigger_transition(&config.cursor_transition_speed);
+                                sound_effects.play_cursor_move(&config);
                             }
                         }
                     }
@@ -181,7 +216,7 @@ pub async fn update(
                     }
                     if input_state.select {
                         if let Ok(mut state) = storage_state.lock() {
-                            if state.selected < state.media.len() - 1 {
+                            if state.selected + 1 < state.media.len() {
                                 state.selected += 1;
                                 *memories = load_memories(&state.media[state.selected], icon_cache, icon_queue).await;
                                 *scroll_offset = 0;
@@ -196,8 +231,14 @@ pub async fn update(
             }
         },
         DialogState::Open => {
+            // Back on a stalled copy stops waiting for it instead of working the dialog underneath
+            let gave_up = input_state.back && copy_op_state.lock().is_ok_and(|mut copy_state| copy_state.give_up_if_stalled());
+            if gave_up {
+                sound_effects.play_back(config);
+            }
+
             // When dialog is fully open, only render the dialog
-            if let Some(dialog) = dialogs.last_mut() {
+            if let Some(dialog) = dialogs.last_mut().filter(|_| !gave_up) {
                 //render_dialog(dialog, &memories, *selected_memory, &icon_cache, &font_cache, &config, &copy_op_state, &placeholder, *scroll_offset, &animation_state, &mut playtime_cache, &mut size_cache, scale_factor);
 
                 let mut selection: i32 = dialog.selection as i32 + dialog.options.len() as i32;
@@ -224,9 +265,19 @@ pub async fn update(
                     let new_selection = next_selection;
                     dialog.selection = new_selection;
                 } else {
-                    // We need to handle the select input
-                    if input_state.select {
-                        let selected_option = &dialog.options[dialog.selection];
+                    // Destructive options fill a ring while held instead of firing on press
+                    let selected_option = &dialog.options[dialog.selection];
+                    let needs_hold = config.hold_to_confirm && dialog.id == "confirm_delete" && selected_option.value == "DELETE";
+                    if needs_hold {
+                        if animation_state.update_hold(input_state.select_held, get_frame_time()) {
+                            action_dialog_id = dialog.id.clone();
+                            action_option_value = selected_option.value.clone();
+                            sound_effects.play_select(&config);
+                        }
+                    } else {
+                        animation_state.update_hold(false, 0.0);
+                    }
+                    if !needs_hold && input_state.select {
                         if !selected_option.disabled {
                             action_dialog_id = dialog.id.clone();
                             action_option_value = selected_option.value.clone();
@@ -244,7 +295,7 @@ pub async fn update(
                 }
 
                 if cancel {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     sound_effects.play_back(&config);
@@ -253,13 +304,25 @@ pub async fn update(
             // Handle dialog actions
             match (action_dialog_id.as_str(), action_option_value.as_str()) {
                 ("main", "COPY") => {
-                    dialogs.push(create_copy_storage_dialog(&storage_state));
+                    // A copy that was given up on may still be writing to one of the drives
+                    if copy_op_state.lock().is_ok_and(|copy_state| copy_state.can_start_copy()) {
+                        dialogs.push(create_copy_storage_dialog(&storage_state));
+                    } else {
+                        dialogs.push(create_error_dialog("ERROR: THE LAST COPY IS STILL FINISHING. TRY AGAIN SOON.".to_string()));
+                        sound_effects.play_error(config);
+                    }
                 },
                 ("main", "DELETE") => {
-                    dialogs.push(create_confirm_delete_dialog());
+                    dialogs.push(create_confirm_delete_dialog(config));
+                },
+                ("main", "SLOTS") => {
+                    let memory_index = get_memory_index(*selected_memory, *scroll_offset);
+                    if let Some(mem) = memories.get(memory_index) {
+                        dialogs.push(create_save_slots_dialog(&mem.id));
+                    }
                 },
                 ("main", "CANCEL") => {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     //sound_effects.play_back(&config);
@@ -268,8 +331,15 @@ pub async fn update(
                     if let Ok(mut state) = storage_state.lock() {
                         let memory_index = get_memory_index(*selected_memory, *scroll_offset);
                         if let Some(mem) = memories.get(memory_index) {
-                            if let Err(e) = save::delete_save(&mem.id, &state.media[state.selected].id) {
+                            // The drive may have been unplugged while the dialog was open
+                            let Some(drive_id) = state.media.get(state.selected).map(|m| m.id.clone()) else {
+                                dialogs.push(create_error_dialog("ERROR: STORAGE DEVICE WAS REMOVED".to_string()));
+                                sound_effects.play_error(config);
+                                return None;
+                            };
+                            if let Err(e) = save::delete_save(&mem.id, &drive_id) {
                                 dialogs.push(create_error_dialog(format!("ERROR: {}", e)));
+                                sound_effects.play_error(config);
                             } else {
                                 state.needs_memory_refresh = true;
                                 *dialog_state = DialogState::None;
@@ -279,7 +349,7 @@ pub async fn update(
                     }
                 },
                 ("confirm_delete", "CANCEL") => {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     //sound_effects.play_back(&config);
@@ -289,34 +359,69 @@ pub async fn update(
                     let mem = memories[memory_index].clone();
                     let target_id = target_id.to_string();
                     if let Ok(state) = storage_state.lock() {
-                        let to_media = StorageMedia { id: target_id, free: 0 };
+                        let to_media = StorageMedia { id: target_id, free: 0, read_only: false };
 
                         // Check if save already exists
                         if check_save_exists(&mem, &to_media, icon_cache, icon_queue).await {
                             dialogs.push(create_save_exists_dialog());
-                        } else {
+                        } else if let Some(from_media) = state.media.get(state.selected).cloned() {
                             let thread_state = copy_op_state.clone();
-                            let from_media = state.media[state.selected].clone();
                             thread::spawn(move || {
                                 copy_memory(&mem, &from_media, &to_media, thread_state);
                             });
+                        } else {
+                            dialogs.push(create_error_dialog("ERROR: STORAGE DEVICE WAS REMOVED".to_string()));
+                            sound_effects.play_error(config);
+                        }
+                    }
+                },
+                ("save_slots", "CANCEL") => {
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
+                    animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
+                    *dialog_state = DialogState::Closing;
+                },
+                ("save_slots", slot) => {
+                    let memory_index = get_memory_index(*selected_memory, *scroll_offset);
+                    if let Some(mem) = memories.get(memory_index) {
+                        let result = if slot == "NEW SLOT" {
+                            save::create_save_slot(&mem.id)
+                        } else {
+                            save::switch_save_slot(&mem.id, slot).map(|_| slot.to_string())
+                        };
+                        match result {
+                            Ok(slot) => {
+                                log_info!("{} now uses save {}", mem.id, slot);
+                                // Playtime and size belong to the slot that was just parked
+                                let cache_key = (mem.id.clone(), mem.drive_name.clone());
+                                playtime_cache.remove(&cache_key);
+                                size_cache.remove(&cache_key);
+                                if let Ok(mut state) = storage_state.lock() {
+                                    state.needs_memory_refresh = true;
+                                }
+                                *dialog_state = DialogState::None;
+                            }
+                            Err(e) => {
+                                log_error!("Failed to switch save slot for {}: {}", mem.id, e);
+                                dialogs.push(create_error_dialog(format!("ERROR: {}", e)));
+                                sound_effects.play_error(config);
+                            }
                         }
                     }
                 },
                 ("copy_storage_select", "CANCEL") => {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     sound_effects.play_back(&config);
                 },
                 ("save_exists", "OK") => {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     sound_effects.play_back(&config);
                 },
                 ("error", "OK") => {
-                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, scale_factor);
+                    let (grid_pos, dialog_pos) = calculate_icon_transition_positions(*selected_memory, config, scale_factor);
                     animation_state.trigger_dialog_transition(dialog_pos, grid_pos);
                     *dialog_state = DialogState::Closing;
                     sound_effects.play_back(&config);
@@ -324,22 +429,11 @@ pub async fn update(
                 _ => {} // handles opening and closing states
             }
 
-            if !icon_queue.is_empty() {
-                let (cart_id, icon_path) = icon_queue.remove(0);
-                let texture_future = load_texture(&icon_path);
-                let texture_result = panic::catch_unwind(|| {
-                    futures::executor::block_on(texture_future)
-                });
-
-                if let Ok(Ok(texture)) = texture_result {
-                    icon_cache.insert(cart_id.clone(), texture);
-                }
-            }
-
             // Display any copy operation errors
             if let Ok(mut copy_state) = copy_op_state.lock() {
                 if let Some(error_msg) = copy_state.error_message.take() {
                     dialogs.push(create_error_dialog(error_msg));
+                    sound_effects.play_error(config);
                     *dialog_state = DialogState::Opening;
                 }
                 if copy_state.should_clear_dialogs {
@@ -350,6 +444,28 @@ pub async fn update(
         },
         _ => {}
     }
+
+    // Load one icon from the queue per frame, whatever the dialog is doing, so the grid never stutters
+    if !icon_queue.is_empty() {
+        let (cart_id, icon_path) = icon_queue.remove(0);
+        let texture_future = load_texture(&icon_path);
+        let texture_result = panic::catch_unwind(|| {
+            futures::executor::block_on(texture_future)
+        });
+
+        // A missing icon just means the cart has none; one that's there but won't decode gets flagged
+        match texture_result {
+            Ok(Ok(texture)) => {
+                load_icon_animation(&cart_id, Path::new(&icon_path));
+                icon_cache.insert(cart_id.clone(), texture);
+            }
+            Ok(Err(e)) if Path::new(&icon_path).exists() => mark_icon_broken(&cart_id, Path::new(&icon_path), &e.to_string()),
+            Err(_) => mark_icon_broken(&cart_id, Path::new(&icon_path), "decoder panicked"),
+            Ok(Err(_)) => {}
+        }
+    }
+
+    toast
 }
 
 // This function will handle all drawing for the data screen
@@ -399,6 +515,8 @@ pub fn draw(
         0.0
     };
 
+    let icon_size = grid_icon_size(config) * scale_factor;
+
     if *dialog_state == DialogState::Opening || *dialog_state == DialogState::Closing || *dialog_state == DialogState::None {
 
         // During opening, only render the main view and the transitioning icon
@@ -412,8 +530,8 @@ pub fn draw(
                 };
 
                 let params = DrawTextureParams {
-                    dest_size: Some(Vec2 {x: TILE_SIZE, y: TILE_SIZE }),
-                    source: Some(Rect { x: 0.0, y: 0.0, h: icon.height(), w: icon.width() }),
+                    dest_size: Some(Vec2 {x: icon_size, y: icon_size }),
+                    source: Some(icon_source(&mem.id, icon)),
                     rotation: 0.0,
                     flip_x: false,
                     flip_y: false,
@@ -441,14 +559,14 @@ pub fn draw(
             let cursor_thickness = 6.0 * scale_factor;
             let cursor_scale = animation_state.get_cursor_scale();
 
-            let base_size = tile_size + 6.0;
+            let base_size = icon_size + 6.0;
             let scaled_size = base_size * cursor_scale;
             let offset = (scaled_size - base_size) / 2.0;
 
             draw_rectangle_lines(
                 // Add offset_x to X and offset_y to Y
-                offset_x + pixel_pos(xp, scale_factor) - (3.0 * scale_factor) - selected_offset - offset,
-                offset_y + pixel_pos(yp, scale_factor) - (3.0 * scale_factor) - selected_offset + grid_offset - offset - spread + (yp * row_spread),
+                offset_x + icon_pos(xp, config, scale_factor) - (3.0 * scale_factor) - selected_offset - offset,
+                offset_y + icon_pos(yp, config, scale_factor) - (3.0 * scale_factor) - selected_offset + grid_offset - offset - spread + (yp * row_spread),
                 scaled_size,
                 scaled_size,
                 cursor_thickness,
@@ -461,17 +579,17 @@ pub fn draw(
                 let memory_index = get_memory_index(x + GRID_WIDTH * y, scroll_offset);
 
                 // Add offsets to grid positions
-                let pos_x = offset_x + pixel_pos(x as f32, scale_factor);
-                let pos_y = offset_y + pixel_pos(y as f32, scale_factor) + grid_offset - spread + (y as f32 * row_spread);
+                let pos_x = offset_x + icon_pos(x as f32, config, scale_factor);
+                let pos_y = offset_y + icon_pos(y as f32, config, scale_factor) + grid_offset - spread + (y as f32 * row_spread);
 
                 if xp as usize == x && yp as usize == y {
                     if let UIFocus::Grid = input_state.ui_focus {
-                        draw_rectangle(pos_x-selected_offset, pos_y-selected_offset, tile_size, tile_size, UI_BG_COLOR);
+                        draw_rectangle(pos_x-selected_offset, pos_y-selected_offset, icon_size, icon_size, UI_BG_COLOR);
                     } else {
-                        draw_rectangle(pos_x - (2.0 * scale_factor), pos_y- (2.0 * scale_factor), tile_size + (4.0 * scale_factor), tile_size + (4.0 * scale_factor), UI_BG_COLOR);
+                        draw_rectangle(pos_x - (2.0 * scale_factor), pos_y- (2.0 * scale_factor), icon_size + (4.0 * scale_factor), icon_size + (4.0 * scale_factor), UI_BG_COLOR);
                     }
                 } else {
-                    draw_rectangle(pos_x - (2.0 * scale_factor), pos_y - (2.0 * scale_factor), tile_size + (4.0 * scale_factor), tile_size + (4.0 * scale_factor), UI_BG_COLOR);
+                    draw_rectangle(pos_x - (2.0 * scale_factor), pos_y - (2.0 * scale_factor), icon_size + (4.0 * scale_factor), icon_size + (4.0 * scale_factor), UI_BG_COLOR);
                 }
 
                 let Some(mem) = memories.get(memory_index) else {
@@ -489,23 +607,21 @@ pub fn draw(
                 };
 
                 let params = DrawTextureParams {
-                    dest_size: Some(Vec2 {x: tile_size, y: tile_size }),
-                    source: Some(Rect { x: 0.0, y: 0.0, h: icon.height(), w: icon.width() }),
+                    dest_size: Some(Vec2 {x: icon_size, y: icon_size }),
+                    source: Some(icon_source(&mem.id, icon)),
                     rotation: 0.0,
                     flip_x: false,
                     flip_y: false,
                     pivot: None
                 };
 
-                if xp as usize == x && yp as usize == y {
-                    if let UIFocus::Grid = input_state.ui_focus {
-                        draw_texture_ex(&icon, pos_x-selected_offset, pos_y-selected_offset, WHITE, params);
-                    } else {
-                        draw_texture_ex(&icon, pos_x, pos_y, WHITE, params);
-                    }
+                let (icon_x, icon_y) = if xp as usize == x && yp as usize == y && input_state.ui_focus == UIFocus::Grid {
+                    (pos_x - selected_offset, pos_y - selected_offset)
                 } else {
-                    draw_texture_ex(&icon, pos_x, pos_y, WHITE, params);
-                }
+                    (pos_x, pos_y)
+                };
+                draw_texture_ex(&icon, icon_x, icon_y, WHITE, params);
+                draw_broken_icon_mark(&mem.id, icon_x, icon_y, icon_size);
             }
         }
 
@@ -528,7 +644,13 @@ pub fn draw(
         if let Ok(state) = storage_state.lock() {
             if !state.media.is_empty() {
                 // Draw storage info text (NOW in the correct, scaled box)
-                text_with_config_color(font_cache, config, &state.media[state.selected].id.to_uppercase(), storage_info_x + (2.0 * scale_factor), storage_info_y + (17.0 * scale_factor), font_size);
+                let selected_media = &state.media[state.selected];
+                let media_label = if selected_media.read_only {
+                    format!("{} (READ-ONLY)", selected_media.id.to_uppercase())
+                } else {
+                    selected_media.id.to_uppercase()
+                };
+                text_with_config_color(font_cache, config, &media_label, storage_info_x + (2.0 * scale_factor), storage_info_y + (17.0 * scale_factor), font_size);
 
                 // Get free space in MB
                 let free_mb = state.media[state.selected].free as f32;
@@ -622,7 +744,7 @@ pub fn draw(
                     Vec2::new(right_box_x + tile_size/2.0 - 4.0 - right_offset + right_shake, right_box_y + tile_size/2.0 - nav_arrow_size - right_offset),
                     Vec2::new(right_box_x + tile_size/2.0 - 4.0 - right_offset + right_shake, right_box_y + tile_size/2.0 + nav_arrow_size - right_offset),
                 ];
-                let right_color = if state.selected < state.media.len() - 1 {
+                let right_color = if state.selected + 1 < state.media.len() {
                     WHITE
                 } else {
                     Color { r: 0.3, g: 0.3, b: 0.3, a: 1.0 } // Dark gray when disabled
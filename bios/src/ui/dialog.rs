@@ -1,4 +1,4 @@
-use crate::{StorageMediaState, Arc, Mutex};
+use crate::{config::Config, input::{Action, Bindings}, save, ui::action_label, StorageMediaState, Arc, Mutex};
 
 pub struct DialogOption {
     pub text: String,
@@ -13,10 +13,15 @@ pub struct Dialog {
     pub selection: usize,
 }
 
-pub fn create_confirm_delete_dialog() -> Dialog {
+pub fn create_confirm_delete_dialog(config: &Config) -> Dialog {
+    let desc = if config.hold_to_confirm {
+        format!("HOLD {} ON DELETE TO ERASE THIS SAVE", action_label(&Bindings::from_config(config), &[Action::Select]))
+    } else {
+        "PERMANENTLY DELETE THIS SAVE DATA?".to_string()
+    };
     Dialog {
         id: "confirm_delete".to_string(),
-        desc: Some("PERMANENTLY DELETE THIS SAVE DATA?".to_string()),
+        desc: Some(desc),
         options: vec![
             DialogOption {
                 text: "DELETE".to_string(),
@@ -36,8 +41,18 @@ pub fn create_confirm_delete_dialog() -> Dialog {
 pub fn create_copy_storage_dialog(storage_state: &Arc<Mutex<StorageMediaState>>) -> Dialog {
     let mut options = Vec::new();
     if let Ok(state) = storage_state.lock() {
+        let selected_id = state.media.get(state.selected).map(|m| m.id.as_str());
         for drive in state.media.iter() {
-            if drive.id == state.media[state.selected].id {
+            if Some(drive.id.as_str()) == selected_id {
+                continue;
+            }
+            if drive.read_only {
+                // Can't copy onto a write-protected card
+                options.push(DialogOption {
+                    text: format!("{} (READ-ONLY)", drive.id.clone()),
+                    value: drive.id.clone(),
+                    disabled: true,
+                });
                 continue;
             }
             options.push(DialogOption {
@@ -61,23 +76,31 @@ pub fn create_copy_storage_dialog(storage_state: &Arc<Mutex<StorageMediaState>>)
     }
 }
 
-pub fn create_main_dialog(storage_state: &Arc<Mutex<StorageMediaState>>) -> Dialog {
-    let has_external_devices = if let Ok(state) = storage_state.lock() {
-        state.media.len() > 1
+/// The action dialog for one save. `running` locks everything but CANCEL, for the save of a game
+/// that is still running in place: its files and overlay are in use.
+pub fn create_main_dialog(storage_state: &Arc<Mutex<StorageMediaState>>, running: bool) -> Dialog {
+    let (has_external_devices, read_only, is_internal) = if let Ok(state) = storage_state.lock() {
+        let selected = state.media.get(state.selected);
+        (state.media.len() > 1, selected.is_some_and(|m| m.read_only), selected.is_some_and(|m| m.id == "internal"))
     } else {
-        false
+        (false, false, false)
     };
 
     let options = vec![
         DialogOption {
             text: "COPY".to_string(),
             value: "COPY".to_string(),
-            disabled: !has_external_devices,
+            disabled: !has_external_devices || running,
         },
         DialogOption {
             text: "DELETE".to_string(),
             value: "DELETE".to_string(),
-            disabled: false,
+            disabled: read_only || running,
+        },
+        DialogOption {
+            text: "SLOTS".to_string(),
+            value: "SLOTS".to_string(),
+            disabled: !is_internal || running, // slots only exist for saves on internal storage
         },
         DialogOption {
             text: "CANCEL".to_string(),
@@ -88,7 +111,42 @@ pub fn create_main_dialog(storage_state: &Arc<Mutex<StorageMediaState>>) -> Dial
 
     Dialog {
         id: "main".to_string(),
-        desc: None,
+        desc: if running {
+            Some("THIS GAME IS RUNNING".to_string())
+        } else if read_only {
+            Some("THIS STORAGE IS READ-ONLY".to_string())
+        } else {
+            None
+        },
+        options,
+        selection: 0,
+    }
+}
+
+pub fn create_save_slots_dialog(cart_id: &str) -> Dialog {
+    let active = save::active_save_slot(cart_id);
+    let mut options: Vec<DialogOption> = save::list_save_slots(cart_id)
+        .into_iter()
+        .map(|slot| DialogOption {
+            text: if slot == active { format!("{} (ACTIVE)", slot) } else { slot.clone() },
+            disabled: slot == active,
+            value: slot,
+        })
+        .collect();
+    options.push(DialogOption {
+        text: "NEW SLOT".to_string(),
+        value: "NEW SLOT".to_string(),
+        disabled: false,
+    });
+    options.push(DialogOption {
+        text: "CANCEL".to_string(),
+        value: "CANCEL".to_string(),
+        disabled: false,
+    });
+
+    Dialog {
+        id: "save_slots".to_string(),
+        desc: Some("WHICH SLOT SHOULD THIS GAME USE?".to_string()),
         options,
         selection: 0,
     }
@@ -123,3 +181,31 @@ pub fn create_error_dialog(message: String) -> Dialog {
         selection: 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hold_prompt_names_the_bound_select_button() {
+        let mut config = Config::default();
+        config.hold_to_confirm = true;
+        config.button_bindings.insert("SELECT".to_string(), "East".to_string());
+        let dialog = create_confirm_delete_dialog(&config);
+        assert_eq!(dialog.desc.as_deref(), Some("HOLD [EAST] ON DELETE TO ERASE THIS SAVE"));
+    }
+
+    #[test]
+    fn running_save_only_offers_cancel() {
+        let mut state = StorageMediaState::new();
+        state.media = vec![
+            crate::StorageMedia { id: "internal".to_string(), free: 0, read_only: false },
+            crate::StorageMedia { id: "usb".to_string(), free: 0, read_only: false },
+        ];
+        let storage_state = Arc::new(Mutex::new(state));
+
+        let enabled = |dialog: &Dialog| dialog.options.iter().filter(|o| !o.disabled).map(|o| o.value.clone()).collect::<Vec<_>>();
+        assert_eq!(enabled(&create_main_dialog(&storage_state, false)), ["COPY", "DELETE", "SLOTS", "CANCEL"]);
+        assert_eq!(enabled(&create_main_dialog(&storage_state, true)), ["CANCEL"]);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     config::Config,
     types::{AnimationState, BackgroundState, BatteryInfo, Screen},
     ui::text_with_color,
+    utils::step_selection,
     render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color,
     FONT_SIZE, MENU_PADDING, MENU_OPTION_HEIGHT, InputState, VideoPlayer,
 };
@@ -17,8 +18,22 @@ pub const EXTRAS_MENU_OPTIONS: &[&str] = &[
     "DOWNLOAD RUNTIMES",
     "CD PLAYER",
     "CHECK FOR UPDATES",
+    "EXPORT SETUP",
+    "IMPORT SETUP",
+    "EXPORT DIAGNOSTICS",
+    "IMPORT ASSETS",
+    "MANAGE ASSETS",
 ];
 
+/// Extras entries that need more than a screen change; main.rs carries these out
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtrasAction {
+    ExportSetup,
+    ImportSetup,
+    ExportDiagnostics,
+    ImportAssets,
+}
+
 /// Handles input and state logic for the Extras menu.
 pub fn update(
     current_screen: &mut Screen,
@@ -27,16 +42,14 @@ pub fn update(
     animation_state: &mut AnimationState,
     sound_effects: &SoundEffects,
     config: &Config,
-) {
-    if input_state.up {
-        *extras_menu_selection = if *extras_menu_selection == 0 { EXTRAS_MENU_OPTIONS.len() - 1 } else { *extras_menu_selection - 1 };
-        animation_state.trigger_transition(&config.cursor_transition_speed);
-        sound_effects.play_cursor_move(config);
-    }
-    if input_state.down {
-        *extras_menu_selection = (*extras_menu_selection + 1) % EXTRAS_MENU_OPTIONS.len();
-        animation_state.trigger_transition(&config.cursor_transition_speed);
-        sound_effects.play_cursor_move(config);
+) -> Option<ExtrasAction> {
+    if input_state.up || input_state.down {
+        let next = step_selection(*extras_menu_selection, EXTRAS_MENU_OPTIONS.len(), input_state.down, config.menu_wrap);
+        if next != *extras_menu_selection {
+            *extras_menu_selection = next;
+            animation_state.trigger_transition(&config.cursor_transition_speed);
+            sound_effects.play_cursor_move(config);
+        }
     }
     if input_state.back {
         *current_screen = Screen::MainMenu;
@@ -51,9 +64,15 @@ pub fn update(
             3 => *current_screen = Screen::RuntimeDownloader,
             4 => *current_screen = Screen::CdPlayer,
             5 => *current_screen = Screen::UpdateChecker,
+            6 => return Some(ExtrasAction::ExportSetup),
+            7 => return Some(ExtrasAction::ImportSetup),
+            8 => return Some(ExtrasAction::ExportDiagnostics),
+            9 => return Some(ExtrasAction::ImportAssets),
+            10 => *current_screen = Screen::AssetManager,
             _ => {}
         }
     }
+    None
 }
 
 /// Draws the Extras menu UI.
@@ -0,0 +1,116 @@
+use crate::utils::asset_key;
+use macroquad::prelude::*;
+use once_cell::sync::Lazy;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+/// Every font file loaded so far with its modification time. Theme reloads reuse the
+/// entries whose files haven't changed instead of decoding them again.
+static LOADED_FONTS: Lazy<Mutex<HashMap<PathBuf, (SystemTime, Font)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Characters each loaded font has a glyph for, keyed like the font cache.
+/// macroquad draws missing glyphs as blanks or boxes and has no way to ask ahead of time.
+static COVERAGE: Lazy<Mutex<HashMap<String, HashSet<char>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Records which characters a font file covers under its font cache key.
+pub fn record_coverage(key: &str, bytes: &[u8]) {
+    let Ok(face) = ttf_parser::Face::parse(bytes, 0) else {
+        return;
+    };
+    let mut chars = HashSet::new();
+    if let Some(cmap) = face.tables().cmap {
+        for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+            subtable.codepoints(|code| {
+                if let Some(c) = char::from_u32(code) {
+                    chars.insert(c);
+                }
+            });
+        }
+    }
+    if let Ok(mut coverage) = COVERAGE.lock() {
+        coverage.insert(key.to_string(), chars);
+    }
+}
+
+/// Loads a font file like macroquad's load_ttf_font, noting its character coverage on the way.
+/// A file that was loaded before and hasn't been modified since comes back from memory.
+pub async fn load_font(path: &str) -> Result<Font, macroquad::Error> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(modified) = modified {
+        if let Some((_, font)) = LOADED_FONTS.lock().ok()
+            .and_then(|fonts| fonts.get(Path::new(path)).filter(|(time, _)| *time == modified).cloned())
+        {
+            log_debug!("Reusing unchanged font: {}", path);
+            return Ok(font);
+        }
+    }
+
+    let bytes = load_file(path).await?;
+    let font = load_ttf_font_from_bytes(&bytes)?;
+    if let Some(key) = asset_key(Path::new(path)) {
+        record_coverage(&key, &bytes);
+    }
+    if let (Some(modified), Ok(mut fonts)) = (modified, LOADED_FONTS.lock()) {
+        fonts.insert(PathBuf::from(path), (modified, font.clone()));
+    }
+    Ok(font)
+}
+
+/// The text with every character the font can't draw swapped for an ASCII stand-in
+/// ("Pokémon" stays as is in a font with é, "東京" becomes "Dong Jing" in one without CJK).
+/// Fonts with no recorded coverage are trusted to draw everything.
+pub fn readable<'a>(font_key: &str, text: &'a str) -> Cow<'a, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let Ok(coverage) = COVERAGE.lock() else {
+        return Cow::Borrowed(text);
+    };
+    let Some(chars) = coverage.get(font_key) else {
+        return Cow::Borrowed(text);
+    };
+    if text.chars().all(|c| c.is_whitespace() || chars.contains(&c)) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_whitespace() || chars.contains(&c) {
+            out.push(c);
+        } else {
+            let stand_in = deunicode::deunicode_char(c).unwrap_or("?");
+            // Spell out transliterated words with a space between them, like deunicode does for whole strings
+            if stand_in.len() > 1 && out.chars().last().is_some_and(|last| last.is_alphanumeric()) {
+                out.push(' ');
+            }
+            out.push_str(stand_in.trim());
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOVEMBER: &[u8] = include_bytes!("../../november.ttf");
+
+    #[test]
+    fn keeps_covered_accents_and_transliterates_cjk() {
+        record_coverage("november-test", NOVEMBER);
+
+        assert_eq!(readable("november-test", "Pokémon"), "Pokémon");
+        assert!(matches!(readable("november-test", "Pokémon"), Cow::Borrowed(_)));
+        assert_eq!(readable("november-test", "東京"), "Dong Jing");
+        assert_eq!(readable("november-test", "東京 Pokémon"), "Dong Jing Pokémon");
+
+        // A font we know nothing about is trusted to draw everything
+        assert_eq!(readable("unknown-font", "東京"), "東京");
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
-    Screen, UIFocus, InputState, copy_session_logs_to_sd, trigger_session_restart, start_log_reader, render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color, text_disabled, DEV_MODE, FLASH_MESSAGE_DURATION, FONT_SIZE, MENU_PADDING, MENU_OPTION_HEIGHT, ShakeTarget, save, StorageMediaState, VideoPlayer,
+    Screen, UIFocus, InputState, copy_session_logs_to_sd, trigger_session_restart, launch_in_place, render_background, render_ui_overlay, get_current_font, measure_text, text_with_config_color, text_disabled, FLASH_MESSAGE_DURATION, FONT_SIZE, MENU_PADDING, MENU_OPTION_HEIGHT, ShakeTarget, save, StorageMediaState, VideoPlayer,
     audio::SoundEffects,
     config::Config,
-    types::{AnimationState, BackgroundState, BatteryInfo, MenuPosition},
-    ui::text_with_color,
+    types::{AnimationState, BackgroundState, BatteryInfo, LaunchMode, MenuPosition, RunningGame},
+    ui::{render_flash_message, text_with_color, no_games::NoGamesReason},
+    utils::{is_portrait, step_selection},
 };
 use macroquad::prelude::*;
 use rodio::{buffer::SamplesBuffer, Sink};
@@ -16,6 +17,13 @@ use std::{
 
 pub const MAIN_MENU_OPTIONS: &[&str] = &["DATA", "PLAY", "COPY SESSION LOGS", "SETTINGS", "EXTRAS", "ABOUT"];
 
+/// The main menu labels, with PLAY swapped for RETURN TO GAME while an in-place game is still running.
+pub fn menu_options(game_running: bool) -> Vec<&'static str> {
+    MAIN_MENU_OPTIONS.iter()
+        .map(|&option| if option == "PLAY" && game_running { "RETURN TO GAME" } else { option })
+        .collect()
+}
+
 pub fn update(
     current_screen: &mut Screen,
     main_menu_selection: &mut usize,
@@ -35,28 +43,23 @@ pub fn update(
     available_games: &mut Vec<(save::CartInfo, PathBuf)>,
     game_selection: &mut usize,
     flash_message: &mut Option<(String, f32)>,
-    game_process: &mut Option<std::process::Child>,
+    game_process: &mut Option<RunningGame>,
+    no_games_reason: &mut NoGamesReason,
 ) {
     // Update play option enabled status based on cart connection
-    *play_option_enabled = cart_connected.load(Ordering::Relaxed);
+    *play_option_enabled = cart_connected.load(Ordering::Relaxed) || game_process.is_some();
 
     // Update copy logs option enabled status based on cart connection
     *copy_logs_option_enabled = cart_connected.load(Ordering::Relaxed);
 
     // Handle main menu navigation
-    if input_state.up {
-        if *main_menu_selection == 0 {
-            *main_menu_selection = MAIN_MENU_OPTIONS.len() - 1;
-        } else {
-            *main_menu_selection = (*main_menu_selection - 1) % MAIN_MENU_OPTIONS.len();
+    if input_state.up || input_state.down {
+        let next = step_selection(*main_menu_selection, MAIN_MENU_OPTIONS.len(), input_state.down, config.menu_wrap);
+        if next != *main_menu_selection {
+            *main_menu_selection = next;
+            animation_state.trigger_transition(&config.cursor_transition_speed);
+            sound_effects.play_cursor_move(&config);
         }
-        animation_state.trigger_transition(&config.cursor_transition_speed);
-        sound_effects.play_cursor_move(&config);
-    }
-    if input_state.down {
-        *main_menu_selection = (*main_menu_selection + 1) % MAIN_MENU_OPTIONS.len();
-        animation_state.trigger_transition(&config.cursor_transition_speed);
-        sound_effects.play_cursor_move(&config);
     }
     if input_state.select {
         match *main_menu_selection {
@@ -71,7 +74,12 @@ pub fn update(
                 sound_effects.play_select(&config);
             },
             1 => { // PLAY option
-                if *play_option_enabled {
+                if let Some(game) = game_process {
+                    // An in-place game is still running; go back to it instead of launching another
+                    log_info!("Returning to {}", game.title());
+                    sound_effects.play_select(&config);
+                    *current_screen = Screen::Debug;
+                } else if *play_option_enabled {
                     sound_effects.play_select(&config);
                     log_messages.lock().unwrap().clear();
 
@@ -80,21 +88,26 @@ pub fn update(
                             log_messages.lock().unwrap().append(&mut debug_log);
 
                             let mut games: Vec<(save::CartInfo, PathBuf)> = Vec::new();
-                            let parse_errors: Vec<String> = Vec::new();
+                            let mut parse_errors: Vec<String> = Vec::new();
 
                             for path in &game_paths {
                                 // Handle .kzp vs .kzi parsing
                                 if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
                                     if ext == "kzi" {
                                         // Standard parsing for KZI
-                                        if let Ok(info) = save::parse_kzi_file(path) {
-                                            games.push((info, path.clone()));
+                                        match save::parse_kzi_file(path) {
+                                            Ok(info) => games.push((info, path.clone())),
+                                            Err(e) => parse_errors.push(format!("[Error] {}", e)),
                                         }
                                     } else if ext == "kzp" {
                                         // Logic for KZP (Compressed Package)
                                         // Since we can't easily read inside the package without mounting,
                                         // we construct a CartInfo based on the filename.
                                         let filename = path.file_stem().unwrap().to_string_lossy().to_string();
+                                        if let Err(e) = save::validate_cart_id(&filename) {
+                                            parse_errors.push(format!("[Error] Skipping '{}': {}", path.display(), e));
+                                            continue;
+                                        }
 
                                         // We assume the ID is the filename
                                         let info = save::CartInfo {
@@ -111,50 +124,40 @@ pub fn update(
                                 }
                             }
 
+                            // Skipped carts are logged even when others are playable, so a bad id isn't a silent no-show
+                            for error in &parse_errors {
+                                log_warn!("{}", error);
+                            }
+                            if !games.is_empty() {
+                                log_messages.lock().unwrap().extend(parse_errors.iter().cloned());
+                            }
+
                             match games.len() {
-                                0 => { // Case: Found files, but none were valid
+                                0 => { // Case: no files, or none were valid. Lead with advice, the log is one button away
                                     let mut logs = log_messages.lock().unwrap();
                                     logs.push(format!("[Info] Found {} potential game file(s), but none could be parsed.", game_paths.len()));
                                     logs.push("--- ERRORS ---".to_string());
                                     logs.extend(parse_errors);
-                                    *current_screen = Screen::Debug;
+                                    *no_games_reason = if game_paths.is_empty() {
+                                        NoGamesReason::NoGameFiles
+                                    } else {
+                                        NoGamesReason::Unreadable(game_paths.len())
+                                    };
+                                    *current_screen = Screen::NoGames;
                                 },
                                 1 => {
                                     // Case: Exactly one game found, go to Debug screen and launch
                                     let (cart_info, kzi_path) = games.remove(0);
-                                    sound_effects.play_select(&config);
-
-                                    if DEV_MODE {
-                                        { // Scoped lock to add messages
-                                            let mut logs = log_messages.lock().unwrap();
-                                            logs.push("--- CARTRIDGE FOUND ---".to_string());
-                                            logs.push(format!("Name: {}", cart_info.name.as_deref().unwrap_or("N/A")));
-                                            logs.push(format!("ID: {}", cart_info.id));
-                                            logs.push(format!("Exec: {}", cart_info.exec));
-                                            logs.push(format!("Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None")));
-                                            logs.push(format!("KZI Path: {}", kzi_path.display()));
+                                    sound_effects.play_launch(&config);
+
+                                    match config.launch_mode() {
+                                        LaunchMode::InPlace => {
+                                            *current_screen = launch_in_place(&cart_info, &kzi_path, log_messages, game_process);
                                         }
-                                        println!("[Debug] Single Cartridge Found! Preparing to launch...");
-                                        println!("[Debug]   Name: {}", cart_info.name.as_deref().unwrap_or("N/A"));
-                                        println!("[Debug]   ID: {}", cart_info.id);
-                                        println!("[Debug]   Exec: {}", cart_info.exec);
-                                        println!("[Debug]   Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None"));
-                                        println!("[Debug]   KZI Path: {}", kzi_path.display());
-
-                                        match save::launch_game(&cart_info, &kzi_path) {
-                                            Ok(mut child) => {
-                                                log_messages.lock().unwrap().push("\n--- LAUNCHING GAME ---".to_string());
-                                                start_log_reader(&mut child, log_messages.clone());
-                                                *game_process = Some(child);
-                                            }
-                                            Err(e) => {
-                                                log_messages.lock().unwrap().push(format!("\n--- LAUNCH FAILED ---\nError: {}", e));
-                                            }
+                                        LaunchMode::SessionRestart => {
+                                            // --- PRODUCTION MODE: Fade out and launch ---
+                                            (*current_screen, *fade_start_time) = trigger_session_restart(current_bgm, &music_cache);
                                         }
-                                        *current_screen = Screen::Debug;
-                                    } else {
-                                        // --- PRODUCTION MODE: Fade out and launch ---
-                                        (*current_screen, *fade_start_time) = trigger_session_restart(current_bgm, &music_cache);
                                     }
                                 },
                                 _ => { // multiple games found
@@ -197,7 +200,8 @@ pub fn update(
                             let error_msg = format!("[Error] Error scanning for cartridges: {}", e);
                             println!("[Error] {}", &error_msg);
                             log_messages.lock().unwrap().push(error_msg);
-                            *current_screen = Screen::Debug;
+                            *no_games_reason = NoGamesReason::ScanFailed;
+                            *current_screen = Screen::NoGames;
                         }
                     }
                 } else {
@@ -277,7 +281,18 @@ pub fn draw(
     let current_font = get_current_font(font_cache, config);
 
     // --- Determine menu position based on config ---
-    let (start_x, start_y, is_centered) = match config.menu_position {
+    let (start_x, start_y, is_centered) = match config.menu_position() {
+        // Portrait screens are too narrow for corner menus, so the menu stays centered
+        // under the logo and only the top/bottom half of the position is kept
+        position if is_portrait(config) => {
+            let y = match position {
+                MenuPosition::Center => screen_height() * 0.45,
+                MenuPosition::TopLeft | MenuPosition::TopRight => screen_height() * 0.3,
+                MenuPosition::BottomLeft | MenuPosition::BottomRight =>
+                    screen_height() - margin_y - (menu_options.len() as f32 * menu_option_height),
+            };
+            (screen_width() / 2.0, y, true)
+        },
         MenuPosition::Center => (
             screen_width() / 2.0,
             (screen_height() * 0.3).max(margin_y),
@@ -383,26 +398,6 @@ pub fn draw(
 
     // --- Draw the Flash Message if it exists ---
     if let Some(message) = flash_message {
-        let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
-        let current_font = get_current_font(font_cache, config);
-
-        // Measure the text to center it
-        let dims = measure_text(message, Some(current_font), font_size, 1.0);
-
-        // Calculate position (centered, near the bottom)
-        let x = screen_width() / 2.0 - dims.width / 2.0;
-        let y = screen_height() - (60.0 * scale_factor); // A bit above the version number
-
-        // Draw a semi-transparent background for readability
-        draw_rectangle(
-            x - (10.0 * scale_factor),
-            y - dims.height,
-            dims.width + (20.0 * scale_factor),
-            dims.height + (10.0 * scale_factor),
-            Color::new(0.0, 0.0, 0.0, 0.7),
-        );
-
-        // Draw the message text itself
-        text_with_config_color(font_cache, config, message, x, y, font_size);
+        render_flash_message(message, font_cache, config, scale_factor);
     }
 }
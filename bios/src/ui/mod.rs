@@ -1,21 +1,32 @@
 use crate::{
-    string_to_color, FONT_SIZE, BatteryInfo, MenuPosition, VERSION_NUMBER, BackgroundState, COLOR_TARGETS, UI_BG_COLOR,
+    string_to_color, FONT_SIZE, BatteryInfo, MenuPosition, VERSION_NUMBER, BackgroundState, UI_BG_COLOR,
     save, PathBuf, AnimationState, RECT_COLOR, Memory, Arc, Mutex, PlaytimeCache, SizeCache, TILE_SIZE,
     PADDING, GRID_OFFSET, GRID_WIDTH, ShakeTarget, Dialog, CopyOperationState, UI_BG_COLOR_DIALOG,
-    config::Config,
+    config::{Config, GRADIENT_CHOICE, SOLID_COLOR_CHOICE},
+    input::{self, Action, Bindings},
+    types::{GameSelectionView, LogoAnimation, Screen},
+    utils,
     memory::{get_game_playtime, get_game_size},
     video::VideoPlayer,
 };
 use macroquad::prelude::*;
-use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use std::{collections::{HashMap, HashSet}, path::Path};
 
 pub mod about;
+pub mod asset_manager;
 pub mod bluetooth;
+pub mod button_mapping;
 pub mod cd_player;
+pub mod credits;
 pub mod data;
 pub mod dialog;
 pub mod extras_menu;
+pub mod glyphs;
 pub mod main_menu;
+pub mod no_games;
+pub mod playtime_stats;
+pub mod qr;
 pub mod runtime_downloader;
 pub mod settings;
 pub mod theme_downloader;
@@ -26,129 +37,51 @@ pub mod wifi;
 // SCREEN RENDERING
 // ===================================
 
-// OLD BACKGROUND RENDERING
-/*
 pub fn render_background(
     background_cache: &HashMap<String, Texture2D>,
     video_cache: &mut HashMap<String, VideoPlayer>,
     config: &Config,
     state: &mut BackgroundState,
 ) {
-    if config.background_selection.ends_with(".mp4") { // check if it's a video
-        if let Some(player) = video_cache.get_mut(&config.background_selection) {
-            // We need to calculate an elapsed time for the video.
-            // Since we don't track "when did this background start",
-            // we'll just use the modulo of global time vs duration.
-            // This effectively loops the time signal sent to the player.
-            let loop_time = get_time() % player.duration_secs;
-
-            // Check if we wrapped around (loop start)
-            if loop_time < 0.1 {
-                player.reset();
-            }
+    let area = utils::letterbox_area(config);
+    draw_background(background_cache, video_cache, config, state, area);
 
-            player.update(loop_time);
-
-            let tint_color = if config.color_shift_speed == "OFF" { WHITE } else { state.bg_color };
-
-            draw_texture_ex(
-                &player.texture,
-                0.0,
-                0.0,
-                tint_color,
-                DrawTextureParams {
-                    dest_size: Some(vec2(screen_width(), screen_height())),
-                    ..Default::default()
-                },
-            );
-        }
-        // If video fails to load/find, fall through? Or just draw black?
-        // Ideally fallback to default image, but for now it will just show UI_BG color.
+    // Black bars around a letterboxed/pillarboxed background
+    if area.w < screen_width() {
+        draw_rectangle(0.0, 0.0, area.x, screen_height(), BLACK);
+        draw_rectangle(area.x + area.w, 0.0, screen_width() - area.x - area.w, screen_height(), BLACK);
     }
-    if let Some(background_texture) = background_cache.get(&config.background_selection) { // check if it's an image
-        let tint_color = if config.color_shift_speed == "OFF" {
-            WHITE
-        } else {
-            state.bg_color
-        };
-
-        if config.background_scroll_speed == "OFF" {
-            // --- Static Logic (Stretch to fill) ---
-            draw_texture_ex(
-                background_texture,
-                0.0,
-                0.0,
-                tint_color,
-                DrawTextureParams {
-                    dest_size: Some(vec2(screen_width(), screen_height())),
-                            ..Default::default()
-                },
-            );
-        } else {
-            // --- Scrolling Logic (Scale with aspect ratio) ---
-            let speed = match config.background_scroll_speed.as_str() {
-                "SLOW" => 0.05, "NORMAL" => 0.1, "FAST" => 0.2, _ => 0.0
-            };
-
-            // Calculate new width and height while preserving aspect ratio
-            let aspect_ratio = background_texture.width() / background_texture.height();
-            let scaled_height = screen_height();
-            let scaled_width = scaled_height * aspect_ratio;
-
-            let params = DrawTextureParams {
-                dest_size: Some(vec2(scaled_width, scaled_height)),
-                ..Default::default()
-            };
-
-            // Update scrolling position based on the new scaled width
-            state.bgx = (state.bgx + speed) % scaled_width;
-
-            // Draw the two textures for a seamless loop
-            draw_texture_ex(background_texture, state.bgx - scaled_width, 0.0, tint_color, params.clone());
-            draw_texture_ex(background_texture, state.bgx, 0.0, tint_color, params);
-        }
-
-        // --- COLOR SHIFTING LOGIC ---
-        let transition_speed = match config.color_shift_speed.as_str() {
-            "SLOW" => 0.05,
-            "NORMAL" => 0.1,
-            "FAST" => 0.2,
-            _ => 0.0, // This covers "OFF"
-        };
-
-        // Only run the color update logic if shifting is enabled
-        if transition_speed > 0.0 {
-            let frame_time = get_frame_time();
-
-            state.bg_color.r += (state.tg_color.r - state.bg_color.r) * transition_speed * frame_time;
-            state.bg_color.g += (state.tg_color.g - state.bg_color.g) * transition_speed * frame_time;
-            state.bg_color.b += (state.tg_color.b - state.bg_color.b) * transition_speed * frame_time;
-
-            // --- CORRECTED LOGIC ---
-            // Check if all three channels are close to the target
-            let red_done = (state.bg_color.r - state.tg_color.r).abs() < 0.01;
-            let green_done = (state.bg_color.g - state.tg_color.g).abs() < 0.01;
-            let blue_done = (state.bg_color.b - state.tg_color.b).abs() < 0.01;
-
-            if red_done && green_done && blue_done {
-                state.target = (state.target + 1) % COLOR_TARGETS.len();
-                state.tg_color = COLOR_TARGETS[state.target];
-            }
-        }
-    } else {
-        clear_background(UI_BG_COLOR);
+    if area.h < screen_height() {
+        draw_rectangle(0.0, 0.0, screen_width(), area.y, BLACK);
+        draw_rectangle(0.0, area.y + area.h, screen_width(), screen_height() - area.y - area.h, BLACK);
     }
 }
-*/
-pub fn render_background(
+
+// Draws the selected background into `area` (the whole screen unless letterboxing is on)
+fn draw_background(
     background_cache: &HashMap<String, Texture2D>,
     video_cache: &mut HashMap<String, VideoPlayer>,
     config: &Config,
     state: &mut BackgroundState,
+    area: Rect,
 ) {
+    // 0. Plain color or gradient, no texture at all
+    if config.active_background() == SOLID_COLOR_CHOICE {
+        clear_background(utils::config_color(&config.background_color));
+        return;
+    }
+    if config.active_background() == GRADIENT_CHOICE {
+        render_gradient(
+            utils::config_color(&config.gradient_start),
+            utils::config_color(&config.gradient_end),
+            config.gradient_direction == "HORIZONTAL",
+        );
+        return;
+    }
+
     // 1. Try to draw Video
-    if config.background_selection.ends_with(".mp4") {
-        if let Some(player) = video_cache.get_mut(&config.background_selection) {
+    if config.active_background().ends_with(".mp4") {
+        if let Some(player) = video_cache.get_mut(config.active_background()) {
             let loop_time = get_time() % player.duration_secs;
             if loop_time < 0.1 {
                 player.reset();
@@ -159,10 +92,10 @@ pub fn render_background(
 
             draw_texture_ex(
                 &player.texture,
-                0.0, 0.0,
+                area.x, area.y,
                 tint_color,
                 DrawTextureParams {
-                    dest_size: Some(vec2(screen_width(), screen_height())),
+                    dest_size: Some(vec2(area.w, area.h)),
                     ..Default::default()
                 },
             );
@@ -178,46 +111,127 @@ pub fn render_background(
         }
     }
 
-    // 2. Try to draw Image
-    if let Some(background_texture) = background_cache.get(&config.background_selection) {
+    // 2. Try to draw a parallax set
+    if let Some((set_name, 0)) = utils::parallax_layer(config.active_background()) {
+        let layers: Vec<&Texture2D> = (0..)
+            .map_while(|n| background_cache.get(&format!("{}_bg{}.png", set_name, n)))
+            .collect();
+        render_parallax_layers(&layers, config, state, area);
+        update_color_shift(config, state);
+        return;
+    }
+
+    // 3. Try to draw Image
+    if let Some(background_texture) = background_cache.get(config.active_background()) {
         let tint_color = if config.color_shift_speed == "OFF" { WHITE } else { state.bg_color };
 
         if config.background_scroll_speed == "OFF" {
-            // Static
+            // Static. The portrait layout crops the middle of a landscape image rather than squashing it.
+            let source = utils::is_portrait(config).then(|| {
+                let (tex_w, tex_h) = (background_texture.width(), background_texture.height());
+                let crop_w = (tex_h * area.w / area.h).min(tex_w);
+                Rect::new((tex_w - crop_w) / 2.0, 0.0, crop_w, tex_h)
+            });
             draw_texture_ex(
-                background_texture, 0.0, 0.0, tint_color,
+                background_texture, area.x, area.y, tint_color,
                 DrawTextureParams {
-                    dest_size: Some(vec2(screen_width(), screen_height())),
-                            ..Default::default()
+                    dest_size: Some(vec2(area.w, area.h)),
+                    source,
+                    ..Default::default()
                 },
             );
         } else {
             // Scrolling
-            let speed = match config.background_scroll_speed.as_str() {
-                "SLOW" => 0.05, "NORMAL" => 0.1, "FAST" => 0.2, _ => 0.0
-            };
+            let speed = background_scroll_speed(config);
             let aspect_ratio = background_texture.width() / background_texture.height();
-            let scaled_height = screen_height();
+            let scaled_height = area.h;
             let scaled_width = scaled_height * aspect_ratio;
             let params = DrawTextureParams {
                 dest_size: Some(vec2(scaled_width, scaled_height)),
                 ..Default::default()
             };
 
-            state.bgx = (state.bgx + speed) % scaled_width;
-            draw_texture_ex(background_texture, state.bgx - scaled_width, 0.0, tint_color, params.clone());
-            draw_texture_ex(background_texture, state.bgx, 0.0, tint_color, params);
+            state.bgx = (state.bgx + speed * get_frame_time()) % scaled_width;
+            draw_texture_ex(background_texture, area.x + state.bgx - scaled_width, area.y, tint_color, params.clone());
+            draw_texture_ex(background_texture, area.x + state.bgx, area.y, tint_color, params);
         }
 
         update_color_shift(config, state);
         return;
     }
 
-    // 3. Fallback (Clear to Black/Grey)
+    // 4. Fallback (Clear to Black/Grey)
     clear_background(UI_BG_COLOR);
     update_color_shift(config, state);
 }
 
+// Fills the screen with a two-color gradient, drawn as thin bands
+fn render_gradient(start: Color, end: Color, horizontal: bool) {
+    const BANDS: usize = 128;
+    let length = if horizontal { screen_width() } else { screen_height() };
+    let band_size = length / BANDS as f32;
+
+    for i in 0..BANDS {
+        let t = i as f32 / (BANDS - 1) as f32;
+        let color = Color::new(
+            start.r + (end.r - start.r) * t,
+            start.g + (end.g - start.g) * t,
+            start.b + (end.b - start.b) * t,
+            1.0,
+        );
+        let offset = i as f32 * band_size;
+        // +1 so rounding never leaves a gap between bands
+        if horizontal {
+            draw_rectangle(offset, 0.0, band_size + 1.0, screen_height(), color);
+        } else {
+            draw_rectangle(0.0, offset, screen_width(), band_size + 1.0, color);
+        }
+    }
+}
+
+// Scroll speed in pixels per second, so it looks the same at any frame rate
+// (these match the old per-frame steps at 60 FPS)
+fn background_scroll_speed(config: &Config) -> f32 {
+    match config.background_scroll_speed.as_str() {
+        "SLOW" => 3.0, "NORMAL" => 6.0, "FAST" => 12.0, _ => 0.0
+    }
+}
+
+// Draws parallax layers back (bg0) to front, each scrolling faster than the one behind it
+fn render_parallax_layers(layers: &[&Texture2D], config: &Config, state: &mut BackgroundState, area: Rect) {
+    let tint_color = if config.color_shift_speed == "OFF" { WHITE } else { state.bg_color };
+    let base_speed = background_scroll_speed(config);
+    state.layer_offsets.resize(layers.len(), 0.0);
+
+    for (i, texture) in layers.iter().enumerate() {
+        if base_speed == 0.0 {
+            draw_texture_ex(
+                texture, area.x, area.y, tint_color,
+                DrawTextureParams {
+                    dest_size: Some(vec2(area.w, area.h)),
+                    ..Default::default()
+                },
+            );
+            continue;
+        }
+
+        // The front layer moves at the configured speed, layers behind it proportionally slower
+        let speed = base_speed * (i + 1) as f32 / layers.len() as f32;
+        let aspect_ratio = texture.width() / texture.height();
+        let scaled_height = area.h;
+        let scaled_width = scaled_height * aspect_ratio;
+        let params = DrawTextureParams {
+            dest_size: Some(vec2(scaled_width, scaled_height)),
+            ..Default::default()
+        };
+
+        let offset = &mut state.layer_offsets[i];
+        *offset = (*offset + speed * get_frame_time()) % scaled_width;
+        draw_texture_ex(texture, area.x + *offset - scaled_width, area.y, tint_color, params.clone());
+        draw_texture_ex(texture, area.x + *offset, area.y, tint_color, params);
+    }
+}
+
 // Extracts the color math so we can call it from any branch
 fn update_color_shift(config: &Config, state: &mut BackgroundState) {
     let transition_speed = match config.color_shift_speed.as_str() {
@@ -235,12 +249,198 @@ fn update_color_shift(config: &Config, state: &mut BackgroundState) {
         let blue_done = (state.bg_color.b - state.tg_color.b).abs() < 0.01;
 
         if red_done && green_done && blue_done {
-            state.target = (state.target + 1) % COLOR_TARGETS.len();
-            state.tg_color = COLOR_TARGETS[state.target];
+            let palette = utils::color_targets(config);
+            state.target = (state.target + 1) % palette.len();
+            state.tg_color = palette[state.target];
         }
     }
 }
 
+// Battery outline with a terminal nub, filled according to the charge percentage
+fn draw_battery_icon(x: f32, y: f32, width: f32, height: f32, percentage: f32, color: Color) {
+    let nub_width = width * 0.08;
+    let body_width = width - nub_width;
+    let border = (height * 0.12).max(1.0);
+
+    draw_rectangle_lines(x, y, body_width, height, border, color);
+    draw_rectangle(x + body_width, y + height * 0.3, nub_width, height * 0.4, color);
+
+    let fill_color = if percentage <= 20.0 { RED } else { color };
+    let inner_width = body_width - border * 4.0;
+    draw_rectangle(
+        x + border * 2.0,
+        y + border * 2.0,
+        inner_width * (percentage / 100.0).clamp(0.0, 1.0),
+        height - border * 4.0,
+        fill_color,
+    );
+}
+
+/// Short notice centered near the bottom of the screen (log saved, battery low, ...)
+pub fn render_flash_message(message: &str, font_cache: &HashMap<String, Font>, config: &Config, scale_factor: f32) {
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+
+    // Measure the text to center it
+    let dims = measure_readable_text(font_cache, config, message, font_size);
+
+    // Calculate position (centered, near the bottom)
+    let x = screen_width() / 2.0 - dims.width / 2.0;
+    let y = screen_height() - (60.0 * scale_factor); // A bit above the version number
+
+    // Draw a semi-transparent background for readability
+    draw_rectangle(
+        x - (10.0 * scale_factor),
+        y - dims.height,
+        dims.width + (20.0 * scale_factor),
+        dims.height + (10.0 * scale_factor),
+        Color::new(0.0, 0.0, 0.0, 0.7),
+    );
+
+    // Draw the message text itself
+    text_with_config_color(font_cache, config, message, x, y, font_size);
+}
+
+/// The button legend for a screen, as (button, action) pairs.
+/// Screens that print their own button hints get an empty legend.
+pub fn help_bar_actions(screen: &Screen, config: &Config) -> Vec<(String, &'static str)> {
+    use Action::*;
+    let bindings = Bindings::from_config(config);
+    let legend = |entries: &[(&[Action], &'static str)]| -> Vec<(String, &'static str)> {
+        entries.iter().map(|(actions, action)| (action_label(&bindings, actions), *action)).collect()
+    };
+    match screen {
+        Screen::MainMenu => legend(&[(&[Select], "SELECT")]),
+        Screen::SaveData => legend(&[(&[Select], "SELECT"), (&[Back], "BACK"), (&[Prev, Next], "STORAGE"), (&[Secondary], "RESCAN")]),
+        Screen::GeneralSettings | Screen::AudioSettings | Screen::GuiSettings | Screen::AssetSettings =>
+            legend(&[(&[Select], "SELECT"), (&[Left, Right], "CHANGE"), (&[Prev, Next], "PAGE"), (&[Back], "BACK")]),
+        Screen::Extras | Screen::Wifi => legend(&[(&[Select], "SELECT"), (&[Back], "BACK")]),
+        Screen::Bluetooth => legend(&[(&[Select], "PAIR"), (&[Secondary], "FORGET"), (&[Back], "BACK")]),
+        // The carousel has no pages to flip
+        Screen::GameSelection if config.game_selection_view() == GameSelectionView::Carousel => legend(&[(&[Select], "PLAY"), (&[Back], "BACK")]),
+        Screen::GameSelection => legend(&[(&[Select], "PLAY"), (&[Prev, Next], "PAGE"), (&[Back], "BACK")]),
+        Screen::About => legend(&[(&[Select], "PLAYTIME STATS"), (&[Secondary], "CREDITS"), (&[Back], "BACK")]),
+        Screen::PlaytimeStats | Screen::Credits => legend(&[(&[Up, Down], "SCROLL"), (&[Back], "BACK")]),
+        Screen::NoGames => legend(&[(&[Select], "VIEW DETAILED LOG"), (&[Back], "BACK")]),
+        Screen::ButtonMapping => legend(&[(&[Select], "REBIND"), (&[Back], "BACK")]),
+        _ => Vec::new(),
+    }
+}
+
+/// The controller buttons bound to `actions`, as the help bar shows them: [SOUTH], [LB/RB].
+/// A pair of d-pad directions drops the repeated D-PAD ([LEFT/RIGHT]).
+pub fn action_label(bindings: &Bindings, actions: &[Action]) -> String {
+    let labels: Vec<String> = actions.iter().map(|&action| input::button_label(bindings.button(action))).collect();
+    let labels: Vec<&str> = if labels.len() > 1 && labels.iter().all(|label| label.starts_with("D-PAD ")) {
+        labels.iter().map(|label| label.trim_start_matches("D-PAD ")).collect()
+    } else {
+        labels.iter().map(String::as_str).collect()
+    };
+    format!("[{}]", labels.join("/"))
+}
+
+/// Draws a screen's button legend along the bottom edge.
+pub fn help_bar(actions: &[(String, &str)], font_cache: &HashMap<String, Font>, config: &Config, scale_factor: f32) {
+    if actions.is_empty() {
+        return;
+    }
+    let legend = actions.iter()
+        .map(|(button, action)| format!("{} {}", button, action))
+        .collect::<Vec<_>>()
+        .join("   ");
+
+    let font_size = (FONT_SIZE as f32 * 0.8 * scale_factor) as u16;
+    let dims = measure_text(&legend, Some(get_current_font(font_cache, config)), font_size, 1.0);
+    let bar_height = dims.height + (8.0 * scale_factor);
+    draw_rectangle(0.0, screen_height() - bar_height, screen_width(), bar_height, Color::new(0.0, 0.0, 0.0, 0.5));
+    text_with_config_color(
+        font_cache,
+        config,
+        &legend,
+        screen_width() / 2.0 - dims.width / 2.0,
+        screen_height() - (4.0 * scale_factor),
+        font_size,
+    );
+}
+
+// Animated logos, keyed like the logo cache (filled in when assets are loaded)
+static LOGO_ANIMATIONS: Lazy<Mutex<HashMap<String, LogoAnimation>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Looks for a `.json` sprite sheet description next to each logo and registers the ones found.
+/// Logos without one stay static.
+pub fn load_logo_animations(logo_files: &[PathBuf]) {
+    let mut animations = HashMap::new();
+    for path in logo_files {
+        let Some(file_name) = utils::asset_key(path) else { continue };
+        let json_path = path.with_extension("json");
+        let Ok(contents) = std::fs::read_to_string(&json_path) else { continue };
+
+        match serde_json::from_str::<LogoAnimation>(&contents) {
+            Ok(animation) => {
+                log_info!("Loaded logo animation for {}: {} frames @ {} fps", file_name, animation.frames, animation.fps);
+                animations.insert(file_name, animation);
+            }
+            Err(e) => log_warn!("Invalid logo animation {}: {}", json_path.display(), e),
+        }
+    }
+
+    if let Ok(mut registry) = LOGO_ANIMATIONS.lock() {
+        *registry = animations;
+    }
+}
+
+// Animated cart/save icons, keyed by cart id like the icon caches
+static ICON_ANIMATIONS: Lazy<Mutex<HashMap<String, LogoAnimation>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Checks for an `icon.json` sprite sheet description next to a cart's icon, the same
+/// format animated logos use. Call after the icon texture loads; plain icons stay static.
+pub fn load_icon_animation(cart_id: &str, icon_path: &Path) {
+    let json_path = icon_path.with_extension("json");
+    let Ok(contents) = std::fs::read_to_string(&json_path) else { return };
+
+    match serde_json::from_str::<LogoAnimation>(&contents) {
+        Ok(animation) => {
+            log_info!("Loaded icon animation for {}: {} frames @ {} fps", cart_id, animation.frames, animation.fps);
+            if let Ok(mut registry) = ICON_ANIMATIONS.lock() {
+                registry.insert(cart_id.to_string(), animation);
+            }
+        }
+        Err(e) => log_warn!("Invalid icon animation {}: {}", json_path.display(), e),
+    }
+}
+
+/// The part of a cart's icon texture to draw this frame (the whole texture unless it's animated).
+pub fn icon_source(cart_id: &str, icon: &Texture2D) -> Rect {
+    ICON_ANIMATIONS.lock().ok()
+        .and_then(|animations| animations.get(cart_id).map(|a| a.current_frame(icon.width(), icon.height())))
+        .unwrap_or_else(|| Rect::new(0.0, 0.0, icon.width(), icon.height()))
+}
+
+// Cart ids whose icon file exists but wouldn't decode; they show the placeholder with a mark
+static BROKEN_ICONS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Records an icon that failed to load so it's drawn as the placeholder with a broken-image mark.
+pub fn mark_icon_broken(cart_id: &str, icon_path: &Path, error: &str) {
+    log_warn!("Could not load icon {} for {}: {}", icon_path.display(), cart_id, error);
+    if let Ok(mut broken) = BROKEN_ICONS.lock() {
+        broken.insert(cart_id.to_string());
+    }
+}
+
+/// Draws a small red cross in the corner of a tile whose icon failed to load.
+pub fn draw_broken_icon_mark(cart_id: &str, x: f32, y: f32, size: f32) {
+    let is_broken = BROKEN_ICONS.lock().is_ok_and(|broken| broken.contains(cart_id));
+    if !is_broken {
+        return;
+    }
+    let mark = size * 0.3;
+    let (mx, my) = (x + size - mark, y);
+    draw_rectangle(mx, my, mark, mark, Color::new(0.0, 0.0, 0.0, 0.7));
+    let inset = mark * 0.2;
+    let thickness = (size * 0.05).max(1.0);
+    draw_line(mx + inset, my + inset, mx + mark - inset, my + mark - inset, thickness, RED);
+    draw_line(mx + mark - inset, my + inset, mx + inset, my + mark - inset, thickness, RED);
+}
+
 // UI
 pub fn render_ui_overlay(
     logo_cache: &HashMap<String, Texture2D>,
@@ -257,15 +457,28 @@ pub fn render_ui_overlay(
     let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
 
     // --- UPDATED: Dynamic Logo Drawing ---
-    if config.logo_selection != "None" {
-        if let Some(logo_to_draw) = logo_cache.get(&config.logo_selection) {
+    if config.active_logo() != "None" {
+        if let Some(logo_to_draw) = logo_cache.get(config.active_logo()) {
+            // Sprite sheet logos only show the current frame
+            let source = LOGO_ANIMATIONS.lock().ok()
+                .and_then(|animations| animations.get(config.active_logo()).map(|a| a.current_frame(logo_to_draw.width(), logo_to_draw.height())))
+                .unwrap_or_else(|| Rect::new(0.0, 0.0, logo_to_draw.width(), logo_to_draw.height()));
+
             // Calculate the scaled width and height while preserving aspect ratio
-            let aspect_ratio = logo_to_draw.height() / logo_to_draw.width();
-            let scaled_logo_width = BASE_LOGO_WIDTH * scale_factor;
+            let aspect_ratio = source.h / source.w;
+            let mut scaled_logo_width = BASE_LOGO_WIDTH * config.logo_scale * scale_factor;
+            if utils::is_portrait(config) {
+                // Keep a margin either side on the narrow screen
+                scaled_logo_width = scaled_logo_width.min(screen_width() * 0.8);
+            }
             let scaled_logo_height = scaled_logo_width * aspect_ratio;
 
-            // Center the logo horizontally
-            let x_pos = (screen_width() - scaled_logo_width) / 2.0;
+            // Center the logo horizontally unless the theme anchors it to a corner
+            let x_pos = match config.logo_anchor.as_str() {
+                "TOP LEFT" => 20.0 * scale_factor,
+                "TOP RIGHT" => screen_width() - scaled_logo_width - 20.0 * scale_factor,
+                _ => (screen_width() - scaled_logo_width) / 2.0,
+            };
             let y_pos = 30.0 * scale_factor; // Scale the vertical position as well
 
             draw_texture_ex(
@@ -275,28 +488,54 @@ pub fn render_ui_overlay(
                 WHITE,
                 DrawTextureParams {
                     dest_size: Some(vec2(scaled_logo_width, scaled_logo_height)),
-                    source: Some(Rect::new(0.0, 0.0, logo_to_draw.width(), logo_to_draw.height())),
+                    source: Some(source),
                     ..Default::default()
                 },
             );
         }
     }
 
-    // Clock
-    let time_dims = measure_text(current_time_str, Some(current_font), font_size, 1.0);
+    // Clock, battery and GCC poll rate share a corner, one line each.
+    // If the menu sits in that corner, the block moves to the other side of the screen.
+    let (status_top, mut status_left) = match config.status_corner.as_str() {
+        "TOP LEFT" => (true, true),
+        "BOTTOM LEFT" => (false, true),
+        "BOTTOM RIGHT" => (false, false),
+        _ => (true, false),
+    };
+    let menu_corner = match config.menu_position() {
+        MenuPosition::TopLeft => Some((true, true)),
+        MenuPosition::TopRight => Some((true, false)),
+        MenuPosition::BottomLeft => Some((false, true)),
+        MenuPosition::BottomRight => Some((false, false)),
+        MenuPosition::Center => None,
+    };
+    if menu_corner == Some((status_top, status_left)) {
+        status_left = !status_left;
+    }
 
-    // If the menu is in the top-right, move the clock to the top-left.
-    let time_x = if config.menu_position == MenuPosition::TopRight {
+    let battery_info = battery_info.as_ref().filter(|_| config.battery_display != "OFF");
+    let status_lines = 1 + battery_info.is_some() as usize + gcc_adapter_poll_rate.is_some() as usize;
+    let status_x = |width: f32| if status_left {
         20.0 * scale_factor
     } else {
-        screen_width() - time_dims.width - (20.0 * scale_factor)
+        screen_width() - width - (20.0 * scale_factor)
     };
+    // Bottom corners stack upwards from above the version number and help bar
+    let status_y = |line: usize| if status_top {
+        (20.0 + 20.0 * line as f32) * scale_factor
+    } else {
+        screen_height() - (30.0 + 20.0 * (status_lines - 1 - line) as f32) * scale_factor
+    };
+
+    // Clock
+    let time_dims = measure_text(current_time_str, Some(current_font), font_size, 1.0);
     text_with_config_color(
         font_cache,
         config,
         current_time_str,
-        time_x,
-        20.0 * scale_factor,
+        status_x(time_dims.width),
+        status_y(0),
         font_size,
     );
 
@@ -309,24 +548,37 @@ pub fn render_ui_overlay(
             _ => " ", // For "Unknown" or other statuses
         };
 
-        // print battery
-        let battery_text = format!("BATTERY: {}% {}", info.percentage, status_symbol);
+        let show_icon = matches!(config.battery_display.as_str(), "ICON" | "BOTH");
+        let battery_text = match config.battery_display.as_str() {
+            "ICON" => String::new(),
+            "BOTH" => format!("{}% {}", info.percentage, status_symbol),
+            _ => format!("BATTERY: {}% {}", info.percentage, status_symbol),
+        };
         let batt_dims = measure_text(&battery_text, Some(current_font), font_size, 1.0);
 
-        // If the menu is in the top-right, move the clock to the top-left.
-        let batt_x = if config.menu_position == MenuPosition::TopRight {
-            20.0 * scale_factor
-        } else {
-            screen_width() - batt_dims.width - (20.0 * scale_factor)
-        };
-        text_with_config_color(
-            font_cache,
-            config,
-            &battery_text,
-            batt_x,
-            40.0 * scale_factor,
-            font_size,
-        );
+        let icon_height = font_size as f32 * 0.6;
+        let icon_width = icon_height * 2.0;
+        let icon_gap = if show_icon && !battery_text.is_empty() { 6.0 * scale_factor } else { 0.0 };
+        let total_width = batt_dims.width + if show_icon { icon_width + icon_gap } else { 0.0 };
+
+        let batt_x = status_x(total_width);
+        let batt_y = status_y(1);
+
+        if show_icon {
+            let percentage: f32 = info.percentage.parse().unwrap_or(0.0);
+            draw_battery_icon(batt_x, batt_y - icon_height, icon_width, icon_height, percentage, string_to_color(&config.font_color));
+        }
+
+        if !battery_text.is_empty() {
+            text_with_config_color(
+                font_cache,
+                config,
+                &battery_text,
+                batt_x + total_width - batt_dims.width,
+                batt_y,
+                font_size,
+            );
+        }
     }
 
     // GCC Adapter Poll Rate
@@ -334,20 +586,13 @@ pub fn render_ui_overlay(
         let gcc_text = format!("GCC: {}Hz", rate);
         let gcc_dims = measure_text(&gcc_text, Some(current_font), font_size, 1.0);
 
-        // Position it in the same corner as the battery/clock
-        let gcc_x = if config.menu_position == MenuPosition::TopRight {
-            20.0 * scale_factor
-        } else {
-            screen_width() - gcc_dims.width - (20.0 * scale_factor)
-        };
-
-        // Draw it below the battery line
+        // Last line of the status block, in the same corner as the battery/clock
         text_with_config_color(
             font_cache,
             config,
             &gcc_text,
-            gcc_x,
-            60.0 * scale_factor, // Below the battery's 40.0
+            status_x(gcc_dims.width),
+            status_y(status_lines - 1),
             font_size,
         );
     }
@@ -361,7 +606,7 @@ pub fn render_ui_overlay(
     let version_bottom_margin = 8.0 * scale_factor; // Slightly up from absolute bottom
 
     // If the menu is in the bottom-right, move the version to the bottom-left.
-    let version_x = if config.menu_position == MenuPosition::BottomRight {
+    let version_x = if config.menu_position() == MenuPosition::BottomRight {
         20.0 * scale_factor // Keep standard padding on the left side
     } else {
         screen_width() - version_dims.width - version_margin // Push it further to the right (closer to edge)
@@ -378,40 +623,108 @@ pub fn render_ui_overlay(
 }
 
 // GAME SELECTION
-pub fn render_game_selection_menu(
+/// Icons per row in the game selection grid
+pub const GAME_GRID_WIDTH: usize = 5;
+
+/// Size of one game icon before ICON SIZE and UI scaling
+const GAME_TILE_SIZE: f32 = 60.0;
+const GAME_GRID_PADDING: f32 = 10.0;
+const GAME_LIST_ROW_HEIGHT: f32 = 26.0;
+/// Games shown on each side of the carousel's selection
+const CAROUSEL_NEIGHBOURS: isize = 2;
+
+/// The cursor box around a selected game icon, pulsing with the cursor animation
+fn draw_game_icon_cursor(pos_x: f32, pos_y: f32, tile_size: f32, animation_state: &AnimationState, config: &Config, scale_factor: f32) {
+    let cursor_color = animation_state.get_cursor_color(config);
+    let cursor_scale = animation_state.get_cursor_scale();
+
+    // The base size of the highlight is the tile size plus a small border
+    let base_size = tile_size + (6.0 * scale_factor);
+    let scaled_size = base_size * cursor_scale;
+    let offset = (scaled_size - base_size) / 2.0;
+
+    draw_rectangle_lines(
+        pos_x - (3.0 * scale_factor) - offset,
+        pos_y - (3.0 * scale_factor) - offset,
+        scaled_size,
+        scaled_size,
+        6.0 * scale_factor, // Line thickness
+        cursor_color
+    );
+}
+
+/// Draws one game's icon (or the placeholder) on its backing box
+fn draw_game_icon(cart_info: &save::CartInfo, game_icon_cache: &HashMap<String, Texture2D>, placeholder: &Texture2D, pos_x: f32, pos_y: f32, size: f32) {
+    let icon = game_icon_cache.get(&cart_info.id).unwrap_or(placeholder);
+
+    // Draw background box for the icon
+    draw_rectangle(pos_x, pos_y, size, size, RECT_COLOR);
+
+    // Draw the icon
+    draw_texture_ex(icon, pos_x, pos_y, WHITE, DrawTextureParams {
+        dest_size: Some(vec2(size, size)),
+        source: Some(icon_source(&cart_info.id, icon)),
+        ..Default::default()
+    });
+    draw_broken_icon_mark(&cart_info.id, pos_x, pos_y, size);
+}
+
+/// The area between the logo and the game name at the bottom: (top, height)
+fn game_selection_content_area(scale_factor: f32) -> (f32, f32) {
+    // The logo's Y position is `30.0 * scale_factor`. Let's give it some space.
+    let content_area_start_y = 100.0 * scale_factor;
+    let content_area_height = screen_height() - content_area_start_y - (80.0 * scale_factor); // Leave space at bottom for text
+    (content_area_start_y, content_area_height)
+}
+
+/// How many games one page of the game selection screen holds in the current view:
+/// as many full grid rows or list rows as fit on screen. None for the carousel, which scrolls
+/// one game at a time and has no pages.
+pub fn game_selection_page_size(config: &Config, scale_factor: f32) -> Option<usize> {
+    match config.game_selection_view() {
+        GameSelectionView::Grid => Some(game_grid_page_size(config, scale_factor)),
+        GameSelectionView::List => Some(game_list_page_size(scale_factor)),
+        GameSelectionView::Carousel => None,
+    }
+}
+
+fn game_grid_page_size(config: &Config, scale_factor: f32) -> usize {
+    let (_, content_area_height) = game_selection_content_area(scale_factor);
+    let padding = GAME_GRID_PADDING * config.icon_scale * scale_factor;
+    let row_height = GAME_TILE_SIZE * config.icon_scale * scale_factor + padding;
+    let rows = ((content_area_height + padding) / row_height) as usize;
+    GAME_GRID_WIDTH * rows.max(1)
+}
+
+fn game_list_page_size(scale_factor: f32) -> usize {
+    let (_, content_area_height) = game_selection_content_area(scale_factor);
+    ((content_area_height / (GAME_LIST_ROW_HEIGHT * scale_factor)) as usize).max(1)
+}
+
+fn draw_game_grid(
     games: &[(save::CartInfo, PathBuf)],
     game_icon_cache: &HashMap<String, Texture2D>,
     placeholder: &Texture2D,
     selected_game: usize,
     animation_state: &AnimationState,
-    logo_cache: &HashMap<String, Texture2D>,
-    background_cache: &HashMap<String, Texture2D>,
-    video_cache: &mut HashMap<String, VideoPlayer>,
-    font_cache: &HashMap<String, Font>,
     config: &Config,
-    background_state: &mut BackgroundState,
-    battery_info: &Option<BatteryInfo>,
-    current_time_str: &str,
-    gcc_adapter_poll_rate: &Option<u32>,
     scale_factor: f32,
 ) {
-    render_background(background_cache, video_cache, config, background_state);
-    render_ui_overlay(logo_cache, font_cache, config, battery_info, current_time_str, gcc_adapter_poll_rate, scale_factor);
-
-    const TILE_SIZE: f32 = 60.0;
-    const PADDING: f32 = 10.0;
-
-    let scaled_tile_size = TILE_SIZE * scale_factor;
-    let scaled_padding = PADDING * scale_factor;
+    // The game grid has room to spare, so ICON SIZE scales the spacing along with the icons
+    let scaled_tile_size = GAME_TILE_SIZE * config.icon_scale * scale_factor;
+    let scaled_padding = GAME_GRID_PADDING * config.icon_scale * scale_factor;
 
     // --- 1. Define the Content Area ---
-    // The logo's Y position is `30.0 * scale_factor`. Let's give it some space.
-    let content_area_start_y = 100.0 * scale_factor;
-    let content_area_height = screen_height() - content_area_start_y - (80.0 * scale_factor); // Leave space at bottom for text
+    let (content_area_start_y, content_area_height) = game_selection_content_area(scale_factor);
+
+    // Only the page holding the selection is drawn
+    let page_size = game_grid_page_size(config, scale_factor);
+    let page_start = selected_game / page_size * page_size;
+    let page_games = &games[page_start.min(games.len())..(page_start + page_size).min(games.len())];
 
     // --- 2. Calculate Grid Dimensions ---
-    let grid_width_items = 5;
-    let grid_height_items = (games.len() as f32 / grid_width_items as f32).ceil() as usize;
+    let grid_width_items = GAME_GRID_WIDTH;
+    let grid_height_items = (page_games.len() as f32 / grid_width_items as f32).ceil() as usize;
 
     let total_grid_width = (grid_width_items as f32 * scaled_tile_size) + ((grid_width_items - 1) as f32 * scaled_padding);
     let total_grid_height = (grid_height_items as f32 * scaled_tile_size) + ((grid_height_items - 1) as f32 * scaled_padding);
@@ -420,56 +733,218 @@ pub fn render_game_selection_menu(
     let start_x = (screen_width() - total_grid_width) / 2.0;
     let start_y = content_area_start_y + (content_area_height - total_grid_height) / 2.0;
 
-    // --- 4. Draw the Grid of Icons (this loop is unchanged) ---
-    for (i, (cart_info, _)) in games.iter().enumerate() {
+    // --- 4. Draw the Grid of Icons ---
+    for (i, (cart_info, _)) in page_games.iter().enumerate() {
         let x = i % grid_width_items;
         let y = i / grid_width_items;
 
         let pos_x = start_x + (x as f32 * (scaled_tile_size + scaled_padding));
         let pos_y = start_y + (y as f32 * (scaled_tile_size + scaled_padding));
 
-        let icon = game_icon_cache.get(&cart_info.id).unwrap_or(placeholder);
+        draw_game_icon(cart_info, game_icon_cache, placeholder, pos_x, pos_y, scaled_tile_size);
 
-        // Draw background box for the icon
-        draw_rectangle(pos_x, pos_y, scaled_tile_size, scaled_tile_size, RECT_COLOR);
+        // Draw selection highlight
+        if page_start + i == selected_game {
+            draw_game_icon_cursor(pos_x, pos_y, scaled_tile_size, animation_state, config, scale_factor);
+        }
+    }
+}
 
-        // Draw the icon
-        draw_texture_ex(icon, pos_x, pos_y, WHITE, DrawTextureParams {
-            dest_size: Some(vec2(scaled_tile_size, scaled_tile_size)),
-            ..Default::default()
-        });
+/// One row per game: a small icon, the name, and the runtime it needs
+fn draw_game_list(
+    games: &[(save::CartInfo, PathBuf)],
+    game_icon_cache: &HashMap<String, Texture2D>,
+    placeholder: &Texture2D,
+    selected_game: usize,
+    animation_state: &AnimationState,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    scale_factor: f32,
+) {
+    const ICON_SIZE: f32 = 20.0;
 
-        // Draw selection highlight
-        if i == selected_game {
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (FONT_SIZE as f32 * 0.8 * scale_factor) as u16;
+    let current_font = get_current_font(font_cache, config);
+    let row_height = GAME_LIST_ROW_HEIGHT * scale_factor;
+    let icon_size = ICON_SIZE * scale_factor;
+    let padding = 6.0 * scale_factor;
+
+    let (content_area_start_y, content_area_height) = game_selection_content_area(scale_factor);
+    let list_width = screen_width() * 0.7;
+    let list_x = (screen_width() - list_width) / 2.0;
+
+    // Only the page holding the selection is drawn
+    let visible_rows = game_list_page_size(scale_factor);
+    let first_row = selected_game / visible_rows * visible_rows;
+    let shown = games.len().saturating_sub(first_row).min(visible_rows);
+    let start_y = content_area_start_y + (content_area_height - shown as f32 * row_height) / 2.0;
+
+    draw_rectangle(list_x, start_y, list_width, shown as f32 * row_height, Color::new(0.0, 0.0, 0.0, 0.5));
+
+    for (row, (i, (cart_info, _))) in games.iter().enumerate().skip(first_row).take(visible_rows).enumerate() {
+        let row_y = start_y + row as f32 * row_height;
+        let is_selected = i == selected_game;
+        let name = cart_info.name.as_deref().unwrap_or(&cart_info.id);
+
+        draw_game_icon(cart_info, game_icon_cache, placeholder, list_x + padding, row_y + (row_height - icon_size) / 2.0, icon_size);
+
+        let text_dims = measure_readable_text(font_cache, config, name, font_size);
+        let text_x = list_x + padding * 2.0 + icon_size;
+        let text_y = row_y + row_height / 2.0 + text_dims.height / 2.0;
+
+        if is_selected && config.cursor_style == "BOX" {
             let cursor_color = animation_state.get_cursor_color(config);
             let cursor_scale = animation_state.get_cursor_scale();
-
-            // The base size of the highlight is the tile size plus a small border
-            let base_size = scaled_tile_size + (6.0 * scale_factor);
-            let scaled_size = base_size * cursor_scale;
-            let offset = (scaled_size - base_size) / 2.0;
+            let base_width = list_width;
+            let base_height = row_height;
+            let scaled_width = base_width * cursor_scale;
+            let scaled_height = base_height * cursor_scale;
+            let offset_x = (scaled_width - base_width) / 2.0;
+            let offset_y = (scaled_height - base_height) / 2.0;
 
             draw_rectangle_lines(
-                pos_x - (3.0 * scale_factor) - offset,
-                pos_y - (3.0 * scale_factor) - offset,
-                scaled_size,
-                scaled_size,
-                6.0 * scale_factor, // Line thickness
-                cursor_color
+                list_x - offset_x,
+                row_y - offset_y,
+                scaled_width,
+                scaled_height,
+                4.0 * scale_factor,
+                cursor_color,
             );
         }
+
+        if is_selected && config.cursor_style == "TEXT" {
+            let highlight_color = animation_state.get_cursor_color(config);
+            text_with_color(font_cache, config, name, text_x, text_y, font_size, highlight_color);
+        } else {
+            text_with_config_color(font_cache, config, name, text_x, text_y, font_size);
+        }
+
+        if let Some(runtime) = &cart_info.runtime {
+            let runtime = runtime.to_uppercase();
+            let runtime_dims = measure_text(&runtime, Some(current_font), small_font_size, 1.0);
+            text_with_config_color(font_cache, config, &runtime, list_x + list_width - padding - runtime_dims.width, text_y, small_font_size);
+        }
+    }
+}
+
+/// The selected game large in the middle, its neighbours shrinking away to either side
+fn draw_game_carousel(
+    games: &[(save::CartInfo, PathBuf)],
+    game_icon_cache: &HashMap<String, Texture2D>,
+    placeholder: &Texture2D,
+    selected_game: usize,
+    animation_state: &AnimationState,
+    config: &Config,
+    scale_factor: f32,
+) {
+    let (content_area_start_y, content_area_height) = game_selection_content_area(scale_factor);
+    let center_x = screen_width() / 2.0;
+    let center_y = content_area_start_y + content_area_height / 2.0;
+    let selected_size = (GAME_TILE_SIZE * 2.0 * config.icon_scale * scale_factor).min(content_area_height);
+    let spacing = selected_size * 0.75;
+
+    // Furthest first, so nearer icons overlap them
+    let mut offsets: Vec<isize> = (-CAROUSEL_NEIGHBOURS..=CAROUSEL_NEIGHBOURS).collect();
+    offsets.sort_by_key(|offset| std::cmp::Reverse(offset.abs()));
+    for offset in offsets {
+        let Some((cart_info, _)) = selected_game.checked_add_signed(offset).and_then(|i| games.get(i)) else { continue };
+        let distance = offset.unsigned_abs() as f32;
+        let size = selected_size * (1.0 - 0.25 * distance);
+        let pos_x = center_x + offset as f32 * spacing - size / 2.0;
+        let pos_y = center_y - size / 2.0;
+
+        draw_game_icon(cart_info, game_icon_cache, placeholder, pos_x, pos_y, size);
+        if offset != 0 {
+            // Fade the neighbours back behind the selection
+            draw_rectangle(pos_x, pos_y, size, size, Color::new(0.0, 0.0, 0.0, 0.2 * distance));
+        }
+    }
+
+    if selected_game < games.len() {
+        draw_game_icon_cursor(center_x - selected_size / 2.0, center_y - selected_size / 2.0, selected_size, animation_state, config, scale_factor);
+    }
+}
+
+pub fn render_game_selection_menu(
+    games: &[(save::CartInfo, PathBuf)],
+    game_icon_cache: &HashMap<String, Texture2D>,
+    placeholder: &Texture2D,
+    selected_game: usize,
+    animation_state: &AnimationState,
+    logo_cache: &HashMap<String, Texture2D>,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    battery_info: &Option<BatteryInfo>,
+    current_time_str: &str,
+    gcc_adapter_poll_rate: &Option<u32>,
+    save_drives: Option<&[String]>,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+    render_ui_overlay(logo_cache, font_cache, config, battery_info, current_time_str, gcc_adapter_poll_rate, scale_factor);
+
+    match config.game_selection_view() {
+        GameSelectionView::Grid => draw_game_grid(games, game_icon_cache, placeholder, selected_game, animation_state, config, scale_factor),
+        GameSelectionView::List => draw_game_list(games, game_icon_cache, placeholder, selected_game, animation_state, font_cache, config, scale_factor),
+        GameSelectionView::Carousel => draw_game_carousel(games, game_icon_cache, placeholder, selected_game, animation_state, config, scale_factor),
+    }
+
+    // --- Page indicator, once the games no longer fit on one screen ---
+    let page_size = game_selection_page_size(config, scale_factor).unwrap_or(games.len().max(1));
+    let pages = games.len().div_ceil(page_size);
+    if pages > 1 {
+        let page_text = format!("PAGE {}/{}", selected_game / page_size + 1, pages);
+        let small_font_size = (FONT_SIZE as f32 * 0.8 * scale_factor) as u16;
+        let page_dims = measure_text(&page_text, None, small_font_size, 1.0);
+        text_with_config_color(font_cache, config, &page_text, screen_width() / 2.0 - page_dims.width / 2.0, screen_height() - (62.0 * scale_factor), small_font_size);
     }
 
     // --- Draw Selected Game Name (Subtitle) ---
     if let Some((cart_info, _)) = games.get(selected_game) {
         let name = cart_info.name.as_deref().unwrap_or(&cart_info.id);
         let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
-        let text_dims = measure_text(name, None, font_size, 1.0);
+        let text_dims = measure_readable_text(font_cache, config, name, font_size);
 
         let text_x = screen_width() / 2.0 - text_dims.width / 2.0;
         let text_y = screen_height() - (40.0 * scale_factor);
 
-        text_with_config_color(font_cache, config, name, text_x, text_y, font_size);
+        // The list already shows the name on the highlighted row
+        if config.game_selection_view() != GameSelectionView::List {
+            text_with_config_color(font_cache, config, name, text_x, text_y, font_size);
+        }
+
+        // --- Where this game's save lives ---
+        if let Some(drives) = save_drives {
+            let copies: Vec<String> = drives.iter()
+                .filter(|d| d.as_str() != "internal")
+                .map(|d| d.to_uppercase())
+                .collect();
+            let (location_text, warning) = if drives.iter().any(|d| d == "internal") {
+                if copies.is_empty() {
+                    ("SAVE: INTERNAL".to_string(), false)
+                } else {
+                    (format!("SAVE: INTERNAL (COPIES ON {})", copies.join(", ")), false)
+                }
+            } else if copies.is_empty() {
+                ("NO SAVE YET - ONE WILL BE CREATED ON INTERNAL".to_string(), true)
+            } else {
+                (format!("NO INTERNAL SAVE - COPY IT FROM {} IN DATA FIRST", copies.join(", ")), true)
+            };
+
+            let small_font_size = (FONT_SIZE as f32 * 0.8 * scale_factor) as u16;
+            let location_dims = measure_readable_text(font_cache, config, &location_text, small_font_size);
+            let location_x = screen_width() / 2.0 - location_dims.width / 2.0;
+            let location_y = text_y + (16.0 * scale_factor);
+            if warning {
+                text_with_color(font_cache, config, &location_text, location_x, location_y, small_font_size, YELLOW);
+            } else {
+                text_with_config_color(font_cache, config, &location_text, location_x, location_y, small_font_size);
+            }
+        }
     }
 }
 
@@ -478,6 +953,7 @@ pub fn render_debug_screen(
     log_messages: &[String], // Takes a slice of strings
     scroll_offset: usize,
     flash_message: Option<&str>,
+    game_running: bool,
     font_cache: &HashMap<String, Font>,
     config: &Config,
     scale_factor: f32,
@@ -506,7 +982,14 @@ pub fn render_debug_screen(
     }
 
     // --- Draw the instruction or flash message ---
-    let instruction_text = flash_message.unwrap_or("PRESS [SOUTH] TO SAVE LOG (OR [EAST] TO EXIT)");
+    let bindings = Bindings::from_config(config);
+    let (select, back) = (action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Back]));
+    let instructions = if game_running {
+        format!("PRESS {} TO SAVE LOG, {} FOR THE MENU (OR {} TO QUIT THE GAME)", select, action_label(&bindings, &[Action::Secondary]), back)
+    } else {
+        format!("PRESS {} TO SAVE LOG (OR {} TO EXIT)", select, back)
+    };
+    let instruction_text = flash_message.unwrap_or(&instructions);
     let instruction_font_size = (14.0 * scale_factor) as u16;
     let instruction_text_width = measure_text(instruction_text, None, instruction_font_size, 1.0).width;
     let instruction_x = (screen_width() - instruction_text_width) / 2.0; // Center it
@@ -541,7 +1024,7 @@ pub fn render_dialog_box(
     // --- Draw Message Text (handles multiple lines) ---
     let mut line_y = box_y + 30.0 * scale_factor;
     for line in message.lines() {
-        let text_dims = measure_text(line, Some(current_font), font_size, 1.0);
+        let text_dims = measure_readable_text(font_cache, config, line, font_size);
         let text_x = screen_width() / 2.0 - text_dims.width / 2.0;
         text_with_config_color(font_cache, config, line, text_x, line_y, font_size);
         line_y += text_dims.height + 5.0 * scale_factor;
@@ -601,8 +1084,15 @@ pub fn render_dialog_box(
         text_with_config_color(font_cache, config, opt1, yes_x, option_y, font_size);
         text_with_config_color(font_cache, config, opt2, no_x, option_y, font_size);
 
+        // Hold-to-confirm ring, just left of the option being held
+        let ring_radius = 6.0 * scale_factor;
+        draw_hold_ring(
+            rect_x - ring_radius - 4.0 * scale_factor, rect_y + scaled_height / 2.0,
+            ring_radius, animation_state.hold_progress, cursor_color, scale_factor,
+        );
+
     } else { // No options, just an "OK" implied for the Reset Complete screen
-        let ok_text = "PRESS [SOUTH] TO RESTART";
+        let ok_text = &format!("PRESS {} TO RESTART", action_label(&Bindings::from_config(config), &[Action::Select]));
         let text_dims = measure_text(ok_text, Some(current_font), font_size, 1.0);
         let text_x = screen_width() / 2.0 - text_dims.width / 2.0;
         let text_y = box_y + box_height - 40.0 * scale_factor;
@@ -632,11 +1122,11 @@ pub fn render_dialog(
     let padding = PADDING * scale_factor;
 
     let current_font = get_current_font(font_cache, config);
-    let (copy_progress, copy_running) = {
+    let (copy_progress, copy_running, copy_stalled) = {
         if let Ok(state) = copy_op_state.lock() {
-            (state.progress, state.running)
+            (state.progress, state.running, state.stalled)
         } else {
-            (0, false)
+            (0, false, false)
         }
     };
 
@@ -648,7 +1138,11 @@ pub fn render_dialog(
     // Game icon and name
     if let Some(mem) = memories.get(get_memory_index(selected_memory, scroll_offset)) {
         let icon = icon_cache.get(&mem.id).unwrap_or(placeholder);
-        let params = DrawTextureParams { dest_size: Some(Vec2 { x: tile_size, y: tile_size }), ..Default::default() };
+        let params = DrawTextureParams {
+            dest_size: Some(Vec2 { x: tile_size, y: tile_size }),
+            source: Some(icon_source(&mem.id, icon)),
+            ..Default::default()
+        };
         let icon_pos = animation_state.get_dialog_transition_pos();
         draw_texture_ex(icon, icon_pos.x, icon_pos.y, WHITE, params);
 
@@ -675,15 +1169,24 @@ pub fn render_dialog(
             (screen_width() - (font_size*6) as f32 - 0.4*font_size as f32) * (copy_progress as f32 / 100.0),
             0.8 * font_size as f32, WHITE
         );
+        if copy_stalled {
+            let back = action_label(&Bindings::from_config(config), &[Action::Back]);
+            let lines = ["NO PROGRESS FOR A WHILE. THE DRIVE MAY BE SLOW".to_string(), format!("OR DISCONNECTED. PRESS {} TO STOP WAITING.", back)];
+            for (i, line) in lines.iter().enumerate() {
+                let text_width = measure_text(line, Some(current_font), font_size, 1.0).width;
+                let y_pos = screen_height() / 2.0 + (3.0 + 1.5 * i as f32) * font_size as f32;
+                text_with_config_color(font_cache, config, line, (screen_width() - text_width) / 2.0, y_pos, font_size);
+            }
+        }
     } else if animation_state.dialog_transition_progress >= 1.0 {
         if let Some(desc) = dialog.desc.clone() {
-            let text_width = measure_text(&desc, Some(current_font), font_size, 1.0).width;
+            let text_width = measure_readable_text(font_cache, config, &desc, font_size).width;
             let x_pos = (screen_width() - text_width) / 2.0;
             text_with_config_color(font_cache, config, &desc, x_pos, (font_size * 7) as f32, font_size);
         }
 
         // Centering and drawing dialog options
-        let longest_width = measure_text( &dialog.options.iter() .find(|opt| opt.text.len() == dialog.options.iter().map(|opt| opt.text.len()).max().unwrap_or(0)) .map(|opt| opt.text.to_uppercase()).unwrap_or_default(), Some(current_font), font_size, 1.0).width;
+        let longest_width = measure_readable_text(font_cache, config, &dialog.options.iter() .find(|opt| opt.text.len() == dialog.options.iter().map(|opt| opt.text.len()).max().unwrap_or(0)) .map(|opt| opt.text.to_uppercase()).unwrap_or_default(), font_size).width;
         let options_start_x = (screen_width() - longest_width) / 2.0;
 
         let selection_y = (font_size * 9 + font_size * 2 * (dialog.selection as u16)) as f32;
@@ -710,6 +1213,14 @@ pub fn render_dialog(
             );
         }
 
+        // --- Hold-to-confirm ring, left of the option being held ---
+        let ring_radius = 6.0 * scale_factor;
+        draw_hold_ring(
+            options_start_x - padding - ring_radius,
+            selection_y + font_size as f32 * 0.5,
+            ring_radius, animation_state.hold_progress, cursor_color, scale_factor,
+        );
+
         // --- Draw Text Options ---
         for (i, option) in dialog.options.iter().enumerate() {
             let y_pos = (font_size * 10 + font_size * 2 * (i as u16)) as f32;
@@ -740,6 +1251,16 @@ pub fn render_dialog(
     }
 }
 
+/// The filling ring shown while a hold-to-confirm is in progress, centered on (x, y).
+pub fn draw_hold_ring(x: f32, y: f32, radius: f32, progress: f32, color: Color, scale_factor: f32) {
+    if progress <= 0.0 {
+        return;
+    }
+    let thickness = 3.0 * scale_factor;
+    draw_arc(x, y, 32, radius, -90.0, thickness, 360.0, Color::new(1.0, 1.0, 1.0, 0.2));
+    draw_arc(x, y, 32, radius, -90.0, thickness, 360.0 * progress, color);
+}
+
 // ===================================
 // CURSOR FUNCTIONS
 // ===================================
@@ -748,11 +1269,23 @@ pub fn pixel_pos(v: f32, scale_factor: f32) -> f32 {
     (PADDING + v * TILE_SIZE + v * PADDING) * scale_factor
 }
 
+/// Unscaled size of a data grid icon with the ICON SIZE multiplier applied.
+/// The cells keep their spacing (all 13 columns still have to fit), so bigger
+/// icons grow into the gaps instead of pushing the grid off screen.
+pub fn grid_icon_size(config: &Config) -> f32 {
+    TILE_SIZE * config.icon_scale
+}
+
+/// Where the icon for grid cell `v` starts: its cell, nudged so the icon stays centered in it
+pub fn icon_pos(v: f32, config: &Config, scale_factor: f32) -> f32 {
+    pixel_pos(v, scale_factor) + (TILE_SIZE - grid_icon_size(config)) / 2.0 * scale_factor
+}
+
 pub fn get_memory_index(selected_memory: usize, scroll_offset: usize) -> usize {
     selected_memory + GRID_WIDTH * scroll_offset
 }
 
-pub fn calculate_icon_transition_positions(selected_memory: usize, scale_factor: f32) -> (Vec2, Vec2) {
+pub fn calculate_icon_transition_positions(selected_memory: usize, config: &Config, scale_factor: f32) -> (Vec2, Vec2) {
     let xp = (selected_memory % GRID_WIDTH) as f32;
     let yp = (selected_memory / GRID_WIDTH) as f32;
 
@@ -761,8 +1294,8 @@ pub fn calculate_icon_transition_positions(selected_memory: usize, scale_factor:
     let padding = PADDING * scale_factor;
 
     let grid_pos = Vec2::new(
-        pixel_pos(xp, scale_factor),
-        pixel_pos(yp, scale_factor) + grid_offset
+        icon_pos(xp, config, scale_factor),
+        icon_pos(yp, config, scale_factor) + grid_offset
     );
     let dialog_pos = Vec2::new(padding, padding);
     (grid_pos, dialog_pos)
@@ -783,10 +1316,23 @@ pub fn get_current_font<'a>(
     .unwrap_or_else(|| &font_cache["Default"])
 }
 
+/// `text` as the current font can draw it (see glyphs::readable).
+pub fn readable_text<'a>(font_cache: &HashMap<String, Font>, config: &Config, text: &'a str) -> std::borrow::Cow<'a, str> {
+    let font_key = if font_cache.contains_key(&config.font_selection) { config.font_selection.as_str() } else { "Default" };
+    glyphs::readable(font_key, text)
+}
+
+/// Measures text the way text_with_color / text_with_config_color will draw it: in the current
+/// font, after missing characters are transliterated (which can make it much longer)
+pub fn measure_readable_text(font_cache: &HashMap<String, Font>, config: &Config, text: &str, font_size: u16) -> TextDimensions {
+    measure_text(&readable_text(font_cache, config, text), Some(get_current_font(font_cache, config)), font_size, 1.0)
+}
+
 // Draws text with a specific color passed in (ignoring config.font_color)
 // Useful for the "TEXT" cursor style.
 pub fn text_with_color(font_cache: &HashMap<String, Font>, config: &Config, text: &str, x: f32, y: f32, font_size: u16, color: Color) {
     let font = get_current_font(font_cache, config);
+    let text = &readable_text(font_cache, config, text);
     let shadow_offset = 1.0 * (font_size as f32 / FONT_SIZE as f32);
 
     // Shadow
@@ -809,6 +1355,7 @@ pub fn text_with_color(font_cache: &HashMap<String, Font>, config: &Config, text
 // A new function specifically for drawing text that respects the config color
 pub fn text_with_config_color(font_cache: &HashMap<String, Font>, config: &Config, text: &str, x: f32, y: f32, font_size: u16) {
     let font = get_current_font(font_cache, config);
+    let text = &readable_text(font_cache, config, text);
 
     // Shadow should scale with font size
     let shadow_offset = 1.0 * (font_size as f32 / FONT_SIZE as f32);
@@ -0,0 +1,112 @@
+use crate::{
+    audio::SoundEffects,
+    config::Config,
+    ui::text_with_color,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, measure_text, string_to_color, text_with_config_color, wrap_text, InputState, VideoPlayer,
+};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Why PLAY came up empty, which decides the advice shown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoGamesReason {
+    /// The cart couldn't be scanned at all
+    ScanFailed,
+    /// The cart was scanned but has no .kzi or .kzp files on it
+    NoGameFiles,
+    /// This many game files were found, but none of them could be read
+    Unreadable(usize),
+}
+
+impl NoGamesReason {
+    fn title(&self) -> String {
+        match self {
+            Self::ScanFailed => "COULDN'T READ THE CARTRIDGE".to_string(),
+            Self::NoGameFiles => "NO GAMES FOUND".to_string(),
+            Self::Unreadable(count) => format!("FOUND {} GAME FILE(S), BUT NONE COULD BE LOADED", count),
+        }
+    }
+
+    /// Likely causes, most likely first
+    fn hints(&self) -> &'static [&'static str] {
+        match self {
+            Self::ScanFailed => &[
+                "The cart may not be mounted yet. Reinsert it and try again.",
+                "The card or drive may be damaged or use an unsupported format.",
+            ],
+            Self::NoGameFiles => &[
+                "The cart needs a .kzi file (or a .kzp package) in its top folder.",
+                "Check that the right card or drive is inserted.",
+                "Games nested in extra subfolders won't be found.",
+            ],
+            Self::Unreadable(_) => &[
+                "A .kzi file is missing its id or exec line, or has a typo.",
+                "The cart id may contain characters that aren't allowed.",
+                "The detailed log names each file that failed and why.",
+            ],
+        }
+    }
+}
+
+pub fn update(
+    input_state: &InputState,
+    current_screen: &mut Screen,
+    sound_effects: &SoundEffects,
+    config: &Config,
+) {
+    if input_state.back {
+        *current_screen = Screen::MainMenu;
+        sound_effects.play_back(config);
+    } else if input_state.select {
+        // VIEW DETAILED LOG
+        *current_screen = Screen::Debug;
+        sound_effects.play_select(config);
+    }
+}
+
+pub fn draw(
+    reason: NoGamesReason,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+
+    let font = get_current_font(font_cache, config);
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (font_size as f32 * 0.8) as u16;
+
+    // Create a container for the UI
+    let container_w = screen_width() * 0.9;
+    let container_h = screen_height() * 0.7; // room for hints that wrap
+    let container_x = (screen_width() - container_w) / 2.0;
+    let container_y = (screen_height() - container_h) / 2.0;
+    draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    let text_x = container_x + 30.0 * scale_factor;
+    let mut y_pos = container_y + 35.0 * scale_factor;
+
+    let title = reason.title();
+    let title_dims = measure_text(&title, Some(font), font_size, 1.0);
+    text_with_color(font_cache, config, &title, screen_width() / 2.0 - title_dims.width / 2.0, y_pos, font_size, string_to_color(&config.cursor_color));
+    y_pos += 35.0 * scale_factor;
+
+    text_with_config_color(font_cache, config, "LIKELY CAUSES:", text_x, y_pos, small_font_size);
+    y_pos += 25.0 * scale_factor;
+    // Long hints wrap, continuation lines lining up under the text after the dash
+    let dash_width = measure_text("- ", Some(font), small_font_size, 1.0).width;
+    let wrap_width = container_x + container_w - 30.0 * scale_factor - text_x - dash_width;
+    for hint in reason.hints() {
+        for (i, line) in wrap_text(hint, font.clone(), small_font_size, wrap_width).iter().enumerate() {
+            if i == 0 {
+                text_with_config_color(font_cache, config, &format!("- {}", line), text_x, y_pos, small_font_size);
+            } else {
+                text_with_config_color(font_cache, config, line, text_x + dash_width, y_pos, small_font_size);
+            }
+            y_pos += 20.0 * scale_factor;
+        }
+    }
+}
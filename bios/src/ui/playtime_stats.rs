@@ -0,0 +1,154 @@
+use crate::{
+    audio::SoundEffects,
+    config::Config,
+    save::{self, PlaytimeStats},
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, InputState, VideoPlayer,
+};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+// --- CONSTANTS ---
+const ITEMS_PER_PAGE: usize = 7;
+
+// --- State Management & Structs ---
+
+pub struct GameStats {
+    pub name: String,
+    pub stats: PlaytimeStats,
+}
+
+pub struct PlaytimeStatsState {
+    pub games: Vec<GameStats>,
+    pub scroll: usize,
+    loaded: bool,
+}
+
+impl PlaytimeStatsState {
+    pub fn new() -> Self {
+        Self {
+            games: Vec::new(),
+            scroll: 0,
+            loaded: false,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.games = collect_stats();
+        self.scroll = 0;
+        self.loaded = true;
+    }
+}
+
+// --- Implementation ---
+
+/// Reads every save on every drive, most recently played first. A game with saves on
+/// several drives is listed once, using the copy that was played last.
+fn collect_stats() -> Vec<GameStats> {
+    let mut by_cart: HashMap<String, GameStats> = HashMap::new();
+
+    for (drive_name, _, _) in save::list_devices().unwrap_or_default() {
+        let Ok(details) = save::get_save_details(&drive_name) else { continue };
+        for (cart_id, name, _) in details {
+            let stats = save::calculate_playtime_stats(&cart_id, &drive_name);
+            let newer = by_cart.get(&cart_id).is_none_or(|existing| stats.last_played > existing.stats.last_played);
+            if newer {
+                let name = if name.is_empty() { cart_id.clone() } else { name };
+                by_cart.insert(cart_id, GameStats { name, stats });
+            }
+        }
+    }
+
+    let mut games: Vec<GameStats> = by_cart.into_values().collect();
+    games.sort_by(|a, b| b.stats.last_played.cmp(&a.stats.last_played));
+    games
+}
+
+pub fn update(
+    state: &mut PlaytimeStatsState,
+    input_state: &InputState,
+    current_screen: &mut Screen,
+    sound_effects: &SoundEffects,
+    config: &Config,
+) {
+    if !state.loaded {
+        state.refresh();
+    }
+
+    if input_state.back {
+        sound_effects.play_back(config);
+        *current_screen = Screen::About;
+        state.loaded = false; // read again next time, there may have been new sessions
+        return;
+    }
+
+    let max_scroll = state.games.len().saturating_sub(ITEMS_PER_PAGE);
+    if input_state.down && state.scroll < max_scroll {
+        state.scroll += 1;
+        sound_effects.play_cursor_move(config);
+    }
+    if input_state.up && state.scroll > 0 {
+        state.scroll -= 1;
+        sound_effects.play_cursor_move(config);
+    }
+}
+
+pub fn draw(
+    state: &PlaytimeStatsState,
+    background_cache: &HashMap<String, Texture2D>,
+    video_cache: &mut HashMap<String, VideoPlayer>,
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    background_state: &mut BackgroundState,
+    scale_factor: f32,
+) {
+    render_background(background_cache, video_cache, config, background_state);
+
+    let font = get_current_font(font_cache, config);
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let small_font_size = (font_size as f32 * 0.8) as u16;
+    let row_height = font_size as f32 * 3.0;
+
+    // Create a container for the UI
+    let container_w = screen_width() * 0.9;
+    let container_h = screen_height() * 0.8;
+    let container_x = (screen_width() - container_w) / 2.0;
+    let container_y = (screen_height() - container_h) / 2.0;
+    draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    let text_x = container_x + 30.0 * scale_factor;
+    let text_y_start = container_y + 30.0 * scale_factor;
+    let bar_max_w = container_w - 60.0 * scale_factor;
+    let bar_h = 6.0 * scale_factor;
+
+    if state.games.is_empty() {
+        text_with_config_color(font_cache, config, "No playtime recorded yet.", text_x, text_y_start, font_size);
+        return;
+    }
+
+    // Bars are scaled to the most played game
+    let max_hours = state.games.iter().map(|g| g.stats.hours).fold(0.0, f32::max).max(0.1);
+
+    for (row, game) in state.games.iter().skip(state.scroll).take(ITEMS_PER_PAGE).enumerate() {
+        let y_pos = text_y_start + row as f32 * row_height;
+
+        let last_played = game.stats.last_played
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "NEVER".to_string());
+        let label = format!("{}  {:.1} H ({:.1} H THIS WEEK)  LAST: {}", game.name, game.stats.hours, game.stats.recent_hours, last_played);
+        text_with_config_color(font_cache, config, &label, text_x, y_pos, small_font_size);
+
+        // Total playtime bar, with the last week's share drawn over it
+        let bar_y = y_pos + 6.0 * scale_factor;
+        let total_w = bar_max_w * game.stats.hours / max_hours;
+        let recent_w = bar_max_w * game.stats.recent_hours / max_hours;
+        draw_rectangle(text_x, bar_y, bar_max_w, bar_h, Color::new(1.0, 1.0, 1.0, 0.1));
+        draw_rectangle(text_x, bar_y, total_w, bar_h, Color::new(1.0, 1.0, 1.0, 0.5));
+        draw_rectangle(text_x, bar_y, recent_w, bar_h, Color::new(0.3, 0.8, 1.0, 0.9));
+    }
+
+    if state.games.len() > ITEMS_PER_PAGE {
+        let hint = format!("{}-{} OF {}", state.scroll + 1, (state.scroll + ITEMS_PER_PAGE).min(state.games.len()), state.games.len());
+        let hint_dims = measure_text(&hint, Some(font), small_font_size, 1.0);
+        text_with_config_color(font_cache, config, &hint, screen_width() / 2.0 - hint_dims.width / 2.0, container_y + container_h - 10.0 * scale_factor, small_font_size);
+    }
+}
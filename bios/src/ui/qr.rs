@@ -0,0 +1,49 @@
+use macroquad::prelude::*;
+use qrcodegen::{QrCode, QrCodeEcc};
+
+/// Blank modules drawn around the code so phone cameras can find its edges.
+const QUIET_ZONE: usize = 4;
+
+/// An encoded QR code as a square grid of dark/light modules.
+pub struct QrGrid {
+    size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrGrid {
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    /// Width of the code in modules, including the quiet zone.
+    pub fn total_modules(&self) -> usize {
+        self.size + QUIET_ZONE * 2
+    }
+}
+
+/// Encodes `text` into a module grid. Returns None if the text is too long to fit.
+pub fn encode(text: &str) -> Option<QrGrid> {
+    let code = QrCode::encode_text(text, QrCodeEcc::Medium).ok()?;
+    let size = code.size() as usize;
+    let modules = (0..size * size)
+        .map(|i| code.get_module((i % size) as i32, (i / size) as i32))
+        .collect();
+    Some(QrGrid { size, modules })
+}
+
+/// Draws the grid with its top-left corner at (x, y), scaled to fit `side` pixels.
+pub fn draw_qr(grid: &QrGrid, x: f32, y: f32, side: f32) {
+    // Snap to whole pixels so neighbouring modules don't leave hairline gaps
+    let module = (side / grid.total_modules() as f32).floor().max(1.0);
+    let full = module * grid.total_modules() as f32;
+    draw_rectangle(x, y, full, full, WHITE);
+
+    let origin = QUIET_ZONE as f32 * module;
+    for row in 0..grid.size {
+        for col in 0..grid.size {
+            if grid.is_dark(col, row) {
+                draw_rectangle(x + origin + col as f32 * module, y + origin + row as f32 * module, module, module, BLACK);
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     audio::SoundEffects,
     config::{Config, get_user_data_dir},
-    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, InputState, wrap_text, DEV_MODE, VideoPlayer,
+    input::{Action, Bindings},
+    ui::action_label,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, measure_readable_text, InputState, wrap_text, DEV_MODE, VideoPlayer,
 };
 use macroquad::prelude::*;
 use serde::Deserialize;
@@ -447,7 +449,8 @@ pub fn draw(
 
             // Draw pagination controls and hint text
             let hint_y = container_y + container_h - 20.0;
-            let hint_text = "Press [SOUTH] to Download, [WEST] to Delete";
+            let bindings = Bindings::from_config(config);
+            let hint_text = &format!("Press {} to Download, {} to Delete", action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Secondary]));
             let hint_dims = measure_text(hint_text, Some(font), (font_size as f32 * 0.8) as u16, 1.0);
             text_with_config_color(font_cache, config, hint_text, screen_width() / 2.0 - hint_dims.width / 2.0, hint_y, (font_size as f32 * 0.8) as u16);
 
@@ -466,7 +469,7 @@ pub fn draw(
             draw_rectangle_lines(dialog_x, dialog_y, dialog_w, dialog_h, 3.0, WHITE);
 
             let question = format!("Delete '{}'?", runtime.name);
-            let question_dims = measure_text(&question, Some(font), font_size, 1.0);
+            let question_dims = measure_readable_text(font_cache, config, &question, font_size);
             text_with_config_color(font_cache, config, &question, screen_width() / 2.0 - question_dims.width / 2.0, dialog_y + 40.0 * scale_factor, font_size);
 
             let yes_text = "YES";
@@ -493,7 +496,7 @@ pub fn draw(
             draw_rectangle_lines(dialog_x, dialog_y, dialog_w, dialog_h, 3.0, WHITE);
 
             let question = format!("'{}' is already installed.", runtime.name);
-            let question_dims = measure_text(&question, Some(font), font_size, 1.0);
+            let question_dims = measure_readable_text(font_cache, config, &question, font_size);
             text_with_config_color(font_cache, config, &question, screen_width() / 2.0 - question_dims.width / 2.0, dialog_y + 40.0 * scale_factor, font_size);
 
             let question2 = "Re-download and overwrite?";
@@ -555,7 +558,7 @@ pub fn draw(
             let text_dims = measure_text(msg, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, msg, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
 
-            let continue_text = "Press [SOUTH] to continue";
+            let continue_text = &format!("Press {} to continue", action_label(&Bindings::from_config(config), &[Action::Select]));
             let continue_dims = measure_text(continue_text, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, continue_text, screen_width() / 2.0 - continue_dims.width / 2.0, screen_height() / 2.0 + line_height * 2.0, font_size);
         }
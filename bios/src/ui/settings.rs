@@ -1,11 +1,11 @@
 use crate::{
     AnimationState, AudioSink, BackgroundState, BatteryInfo, InputState, Screen,
     render_background, render_ui_overlay, get_current_font, measure_text,
-    text_with_config_color, DEV_MODE, theme, text_with_color, VideoPlayer,
-    audio::{SoundEffects, play_new_bgm},
-    config::Config,
-    system::{adjust_system_volume, get_system_volume, set_brightness, get_current_brightness},
-    utils::{apply_resolution, trim_extension},
+    text_with_config_color, measure_readable_text, DEV_MODE, theme, text_with_color, VideoPlayer,
+    audio::{SoundEffects, play_new_bgm, play_speaker_test},
+    config::{Config, step_percent},
+    system::{adjust_system_volume, get_system_volume, set_brightness, get_current_brightness, get_displays},
+    utils::{apply_display, apply_resolution, is_portrait, step_selection, trim_extension},
 };
 use macroquad::prelude::*;
 use rodio::{buffer::SamplesBuffer, Sink};
@@ -24,12 +24,25 @@ pub const GENERAL_SETTINGS: &[&str] = &[
     "RESET SETTINGS",
     "RESOLUTION",
     "ASPECT RATIO",
+    "LAYOUT",
+    "LETTERBOX",
     "SHOW SPLASH SCREEN",
     "TIME ZONE",
     "BRIGHTNESS",
     "WI-FI",
     "BLUETOOTH",
     "AUTOBOOT",
+    "FULLSCREEN",
+    "DISPLAY",
+    "DISPLAY HOTPLUG",
+    "LAUNCH MODE",
+    "ON GAME EXIT",
+    "HOLD TO CONFIRM",
+    "HOLD BACK FOR MENU",
+    "MENU WRAP",
+    "SAVE NOTIFICATIONS",
+    "BUTTON MAPPING",
+    "NETWORK SHARE",
     "AUDIO SETTINGS",
 ];
 
@@ -38,6 +51,9 @@ pub const AUDIO_SETTINGS: &[&str] = &[
     "BGM VOLUME",
     "SFX VOLUME",
     "AUDIO OUTPUT",
+    "TEST SPEAKERS",
+    "BOOT CHIME",
+    "BGM ON DEBUG SCREEN",
     "VIDEO SETTINGS",
     "GUI CUSTOMIZATION",
 ];
@@ -50,8 +66,16 @@ pub const GUI_CUSTOMIZATION_SETTINGS: &[&str] = &[
     "CURSOR STYLE",
     "CURSOR BLINK SPEED",
     "TRANSITION ANIMATION",
+    "REJECT SHAKE",
     "BACKGROUND SCROLLING",
     "COLOR GRADIENT SHIFTING",
+    "COLOR PALETTE",
+    "BATTERY DISPLAY",
+    "CLOCK POSITION",
+    "HELP BAR",
+    "ICON SIZE",
+    "GAME SELECTION VIEW",
+    "LOADING MESSAGES",
     "AUDIO SETTINGS",
     "CUSTOM ASSETS SETTINGS",
 ];
@@ -61,7 +85,15 @@ pub const CUSTOM_ASSET_SETTINGS: &[&str] = &[
     "SOUND PACK",
     "LOGO",
     "BACKGROUND",
+    "BACKGROUND COLOR",
+    "GRADIENT START",
+    "GRADIENT END",
+    "GRADIENT DIRECTION",
     "FONT TYPE",
+    "LOAD BACKGROUNDS",
+    "LOAD LOGOS",
+    "LOAD FONTS",
+    "LOAD MUSIC",
     "GUI CUSTOMIZATION SETTINGS",
 ];
 
@@ -103,6 +135,37 @@ pub const CURSOR_STYLES: &[&str] = &["BOX", "TEXT"];
 
 pub const SPEEDS: &[&str] = &["OFF", "SLOW", "NORMAL", "FAST"];
 
+pub const BATTERY_DISPLAYS: &[&str] = &["OFF", "ICON", "PERCENT", "BOTH"];
+
+pub const COLOR_PALETTES: &[&str] = &["DEFAULT", "PASTEL", "NEON", "GRAYSCALE", "CUSTOM"];
+
+/// ICON SIZE multipliers. Above 1.25 the data grid's icons would touch.
+pub const ICON_SCALES: &[f32] = &[0.75, 1.0, 1.25];
+
+/// Where the logo sits along the top of the screen.
+pub const LOGO_ANCHORS: &[&str] = &["TOP CENTER", "TOP LEFT", "TOP RIGHT"];
+
+/// Where the clock and battery are drawn.
+pub const STATUS_CORNERS: &[&str] = &["TOP RIGHT", "TOP LEFT", "BOTTOM LEFT", "BOTTOM RIGHT"];
+
+/// How hard arrows, options and dialogs shake when an input is rejected. OFF keeps just the reject sound.
+pub const SHAKE_LEVELS: &[&str] = &["OFF", "LOW", "NORMAL", "HIGH"];
+
+pub const LOADING_PERSONAS: &[&str] = &["KAZETA", "MONIKA", "BENDER", "CUSTOM"];
+
+/// What the BGM does while the debug screen (and an in-place game) is up.
+pub const BGM_ON_DEBUG: &[&str] = &["PLAY", "DUCK", "STOP"];
+
+/// Where an in-place game that exits cleanly returns to.
+pub const GAME_EXIT_ACTIONS: &[&str] = &["MAIN MENU", "GAME SELECTION", "RELAUNCH"];
+
+/// What happens when the display changes mid-session: offer its native resolution, switch automatically, or ignore it.
+pub const DISPLAY_HOTPLUG: &[&str] = &["ASK", "AUTO", "OFF"];
+
+pub const LAYOUTS: &[&str] = &["AUTO", "LANDSCAPE", "PORTRAIT"];
+
+pub const GRADIENT_DIRECTIONS: &[&str] = &["VERTICAL", "HORIZONTAL"];
+
 pub const TIMEZONES: [&str; 25] = [
     "UTC-12", "UTC-11", "UTC-10", "UTC-9", "UTC-8", "UTC-7", "UTC-6",
     "UTC-5", "UTC-4", "UTC-3", "UTC-2", "UTC-1", "UTC", "UTC+1",
@@ -120,6 +183,41 @@ fn matches_aspect_ratio(res: &str, ratio: &str) -> bool {
     }
 }
 
+/// The largest preset resolution that fits a display of this size, with its aspect ratio.
+/// Presets with the display's own shape win so a TV doesn't end up letterboxed.
+pub fn native_resolution_for(width: i32, height: i32) -> Option<(&'static str, &'static str)> {
+    if height <= 0 {
+        return None;
+    }
+    let shape = width as f32 / height as f32;
+    ASPECT_RATIOS.iter()
+        .flat_map(|&ratio| RESOLUTIONS.iter().filter(move |&&r| matches_aspect_ratio(r, ratio)).map(move |&r| (r, ratio)))
+        .filter_map(|(res, ratio)| {
+            let (w, h) = res.split_once('x')?;
+            let (w, h) = (w.parse::<i32>().ok()?, h.parse::<i32>().ok()?);
+            (w <= width && h <= height).then_some((res, ratio, w, h))
+        })
+        .max_by_key(|&(_, _, w, h)| (((w as f32 / h as f32) - shape).abs() < 0.02, w * h))
+        .map(|(res, ratio, _, _)| (res, ratio))
+}
+
+/// The first row to draw when `visible_rows` of a `count`-row page fit on screen:
+/// scrolls just far enough to keep `selection` in view.
+fn first_visible_row(selection: usize, count: usize, visible_rows: usize) -> usize {
+    selection.saturating_sub(visible_rows.saturating_sub(1)).min(count.saturating_sub(visible_rows))
+}
+
+/// Up/down arrows at `x` marking settings rows scrolled out of view above and below
+fn draw_scroll_arrows(x: f32, top_y: f32, bottom_y: f32, more_above: bool, more_below: bool, scale_factor: f32) {
+    let size = 4.0 * scale_factor;
+    if more_above {
+        draw_triangle(vec2(x, top_y - size), vec2(x - size, top_y + size), vec2(x + size, top_y + size), WHITE);
+    }
+    if more_below {
+        draw_triangle(vec2(x, bottom_y + size), vec2(x - size, bottom_y - size), vec2(x + size, bottom_y - size), WHITE);
+    }
+}
+
 // SETTINGS
 pub fn render_settings_page(
     page_number: usize,
@@ -144,11 +242,12 @@ pub fn render_settings_page(
     let menu_padding = MENU_PADDING * scale_factor;
     let settings_start_y = SETTINGS_START_Y * scale_factor;
     let settings_option_height = SETTINGS_OPTION_HEIGHT * scale_factor;
-    let right_margin = 50.0 * scale_factor;
-    let left_margin = 50.0 * scale_factor;
-
-    // get currently selected font at start
-    let current_font = get_current_font(font_cache, config);
+    // Portrait screens are too narrow for label and value side by side, so each
+    // option takes two lines: the label, then its value underneath
+    let portrait = is_portrait(config);
+    let row_height = if portrait { settings_option_height * 2.0 } else { settings_option_height };
+    let right_margin = (if portrait { 20.0 } else { 50.0 }) * scale_factor;
+    let left_margin = (if portrait { 20.0 } else { 50.0 }) * scale_factor;
 
     render_background(background_cache, video_cache, config, background_state);
 
@@ -157,14 +256,21 @@ pub fn render_settings_page(
 
     render_ui_overlay(logo_cache, font_cache, config, battery_info, current_time_str, gcc_adapter_poll_rate, scale_factor);
 
-    // Loop through and draw all settings options
-    for (i, label_text) in options.iter().enumerate() {
-        let y_pos_base = settings_start_y + (i as f32 * settings_option_height);
+    // Pages longer than the screen scroll so the selection stays in view
+    let bottom_margin = 30.0 * scale_factor;
+    let visible_rows = (((screen_height() - settings_start_y - bottom_margin) / row_height) as usize).max(1);
+    let first_row = first_visible_row(selection, options.len(), visible_rows);
+
+    // Loop through and draw the visible settings options
+    for (i, label_text) in options.iter().enumerate().skip(first_row).take(visible_rows) {
+        let label_y_base = settings_start_y + ((i - first_row) as f32 * row_height);
+        let y_pos_base = if portrait { label_y_base + settings_option_height } else { label_y_base };
 
         let value_text = get_settings_value(page_number, i, config, system_volume, brightness);
-        let value_dims = measure_text(&value_text.to_uppercase(), Some(current_font), font_size, 1.0);
+        let value_dims = measure_readable_text(font_cache, config, &value_text.to_uppercase(), font_size);
         let value_x = screen_width() - value_dims.width - right_margin;
         let text_y = y_pos_base + (settings_option_height / 2.0) + (value_dims.offset_y * 0.5);
+        let label_y = label_y_base + (settings_option_height / 2.0) + (value_dims.offset_y * 0.5);
 
         let is_selected = i == selection;
 
@@ -187,7 +293,7 @@ pub fn render_settings_page(
         }
 
         // 2. Draw Label (Standard)
-        text_with_config_color(font_cache, config, label_text, left_margin, text_y, font_size);
+        text_with_config_color(font_cache, config, label_text, left_margin, label_y, font_size);
 
         // 3. Draw Value (Conditional Color)
         if is_selected && config.cursor_style == "TEXT" {
@@ -200,6 +306,16 @@ pub fn render_settings_page(
         }
     }
 
+    // Arrows hinting at options scrolled out of view
+    let top_y = settings_start_y + settings_option_height / 2.0;
+    let bottom_y = settings_start_y + visible_rows as f32 * row_height - settings_option_height / 2.0;
+    draw_scroll_arrows(left_margin / 2.0, top_y, bottom_y, first_row > 0, first_row + visible_rows < options.len(), scale_factor);
+
+    // Live preview of the font/color/cursor combination on the customization pages
+    if page_number == 3 || page_number == 4 {
+        draw_style_preview(font_cache, config, animation_state, scale_factor);
+    }
+
     // let the user know what page they're on
     // 1. Determine the title text based on the page number
     let page_title = match page_number {
@@ -230,6 +346,63 @@ pub fn render_settings_page(
     );
 }
 
+// STYLE PREVIEW
+// A normal item next to a "selected" one, using the current font, font color and cursor style.
+// Tucked into the bottom-left corner so it doesn't collide with the option list.
+fn draw_style_preview(
+    font_cache: &HashMap<String, Font>,
+    config: &Config,
+    animation_state: &AnimationState,
+    scale_factor: f32,
+) {
+    const NORMAL_TEXT: &str = "Aa 123";
+    const SELECTED_TEXT: &str = "SELECTED";
+
+    let font_size = (FONT_SIZE as f32 * scale_factor) as u16;
+    let menu_padding = MENU_PADDING * scale_factor;
+    let current_font = get_current_font(font_cache, config);
+
+    let normal_dims = measure_text(NORMAL_TEXT, Some(current_font), font_size, 1.0);
+    let selected_dims = measure_text(SELECTED_TEXT, Some(current_font), font_size, 1.0);
+    let gap = menu_padding * 3.0;
+    let x_pos = 50.0 * scale_factor;
+    let selected_x = x_pos + normal_dims.width + gap;
+    // In the portrait layout the page title takes the whole bottom line, so sit above it
+    let y_pos = screen_height() - (if is_portrait(config) { 45.0 } else { 20.0 }) * scale_factor;
+    let text_height = normal_dims.height.max(selected_dims.height);
+
+    // backing panel so the sample reads the same over any background
+    draw_rectangle(
+        x_pos - menu_padding * 2.0,
+        y_pos - text_height - menu_padding * 2.0,
+        normal_dims.width + gap + selected_dims.width + menu_padding * 4.0,
+        text_height + menu_padding * 4.0,
+        Color::new(0.0, 0.0, 0.0, 0.6),
+    );
+
+    text_with_config_color(font_cache, config, NORMAL_TEXT, x_pos, y_pos, font_size);
+
+    let cursor_color = animation_state.get_cursor_color(config);
+    if config.cursor_style == "BOX" {
+        let cursor_scale = animation_state.get_cursor_scale();
+        let base_width = selected_dims.width + (menu_padding * 2.0);
+        let base_height = text_height + (menu_padding * 2.0);
+        let scaled_width = base_width * cursor_scale;
+        let scaled_height = base_height * cursor_scale;
+        draw_rectangle_lines(
+            selected_x - menu_padding - (scaled_width - base_width) / 2.0,
+            y_pos - text_height - menu_padding - (scaled_height - base_height) / 2.0,
+            scaled_width,
+            scaled_height,
+            2.0 * scale_factor,
+            cursor_color,
+        );
+        text_with_config_color(font_cache, config, SELECTED_TEXT, selected_x, y_pos, font_size);
+    } else {
+        text_with_color(font_cache, config, SELECTED_TEXT, selected_x, y_pos, font_size, cursor_color);
+    }
+}
+
 // SETTINGS VALUE
 // Text for the settings on the RIGHT side
 pub fn get_settings_value(page: usize, index: usize, config: &Config, system_volume: f32, brightness: f32) -> String {
@@ -239,13 +412,27 @@ pub fn get_settings_value(page: usize, index: usize, config: &Config, system_vol
             0 => "CONFIRM".to_string(), // RESET SETTINGS
             1 => config.resolution.clone(), // RESOLUTION
             2 => config.aspect_ratio.clone(), // ASPECT RATIO
-            3 => if config.show_splash_screen { "ON" } else { "OFF" }.to_string(), // SPLASH SCREEN TOGGLE
-            4 => config.timezone.clone().to_uppercase(), // TIME ZONE
-            5 => format!("{:.0}%", brightness * 100.0), // BRIGHTNESS
-            6 => if config.wifi { "ON" } else { "OFF" }.to_string(), // WI-FI
-            7 => if config.bluetooth { "ON" } else { "OFF" }.to_string(), // BLUETOOTH
-            8 => if config.autoboot { "ON" } else { "OFF" }.to_string(), // AUTOBOOT
-            9 => "->".to_string(),
+            3 => config.layout.clone(), // LAYOUT
+            4 => if config.letterbox { "ON" } else { "OFF" }.to_string(), // LETTERBOX
+            5 => if config.show_splash_screen { "ON" } else { "OFF" }.to_string(), // SPLASH SCREEN TOGGLE
+            6 => config.timezone.clone().to_uppercase(), // TIME ZONE
+            7 => format!("{:.0}%", brightness * 100.0), // BRIGHTNESS
+            8 => if config.wifi { "ON" } else { "OFF" }.to_string(), // WI-FI
+            9 => if config.bluetooth { "ON" } else { "OFF" }.to_string(), // BLUETOOTH
+            10 => if config.autoboot { "ON" } else { "OFF" }.to_string(), // AUTOBOOT
+            11 => if config.fullscreen { "ON" } else { "OFF" }.to_string(), // FULLSCREEN
+            12 if DEV_MODE => format!("MONITOR {}", config.display_index + 1), // DISPLAY
+            12 => "DESKTOP ONLY".to_string(), // the session build owns a single screen
+            13 => config.display_hotplug.clone(), // DISPLAY HOTPLUG
+            14 => config.launch_mode().label().to_string(), // LAUNCH MODE
+            15 => config.on_game_exit.clone(), // ON GAME EXIT
+            16 => if config.hold_to_confirm { "ON" } else { "OFF" }.to_string(), // HOLD TO CONFIRM
+            17 => if config.hold_back_to_menu { "ON" } else { "OFF" }.to_string(), // HOLD BACK FOR MENU
+            18 => if config.menu_wrap { "ON" } else { "OFF" }.to_string(), // MENU WRAP
+            19 => if config.save_toasts { "ON" } else { "OFF" }.to_string(), // SAVE NOTIFICATIONS
+            20 => "->".to_string(), // BUTTON MAPPING
+            21 => if config.network_share.trim().is_empty() { "OFF".to_string() } else { config.network_share.trim().to_string() }, // NETWORK SHARE
+            22 => "->".to_string(),
             _ => "".to_string(),
         },
         // AUDIO SETTINGS
@@ -254,23 +441,34 @@ pub fn get_settings_value(page: usize, index: usize, config: &Config, system_vol
             1 => format!("{:.0}%", config.bgm_volume * 100.0), // BGM VOLUME
             2 => format!("{:.0}%", config.sfx_volume * 100.0), // SFX VOLUME
             3 => config.audio_output.clone().to_uppercase(), // AUDIO OUTPUT
-            4 => "<-".to_string(),
-            5 => "->".to_string(),
+            4 => "PLAY".to_string(), // TEST SPEAKERS
+            5 => if config.boot_chime { "ON" } else { "OFF" }.to_string(), // BOOT CHIME
+            6 => config.bgm_on_debug.clone(), // BGM ON DEBUG SCREEN
+            7 => "<-".to_string(),
+            8 => "->".to_string(),
             _ => "".to_string(),
         },
         // GUI CUSTOMIZATION
         3 => match index {
             0 => config.theme.clone().replace('_', " ").to_uppercase(), // THEME SELECTION
-            1 => format!("{:?}", config.menu_position).to_uppercase(), // MENU POSITION
+            1 => config.menu_position().label().to_string(), // MENU POSITION
             2 => config.font_color.clone(), // FONT COLOR
             3 => config.cursor_color.clone(), // CURSOR COLOR
             4 => config.cursor_style.clone(), // CURSOR STYLE
             5 => config.cursor_blink_speed.clone(), // CURSOR BLINK SPEED
             6 => config.cursor_transition_speed.clone(), // CURSOR TRANSITION SPEED
-            7 => config.background_scroll_speed.clone(), // BACKGROUND SCROLL SPEED
-            8 => config.color_shift_speed.clone(), // COLOR SHIFTING GRADIENT SPEED
-            9 => "<-".to_string(),
-            10 => "->".to_string(),
+            7 => config.shake_intensity.clone(), // REJECT SHAKE
+            8 => config.background_scroll_speed.clone(), // BACKGROUND SCROLL SPEED
+            9 => config.color_shift_speed.clone(), // COLOR SHIFTING GRADIENT SPEED
+            10 => config.color_palette.clone(), // COLOR PALETTE
+            11 => config.battery_display.clone(), // BATTERY DISPLAY
+            12 => config.status_corner.clone(), // CLOCK POSITION
+            13 => if config.show_help_bar { "ON" } else { "OFF" }.to_string(), // HELP BAR
+            14 => format!("{:.0}%", config.icon_scale * 100.0), // ICON SIZE
+            15 => config.game_selection_view().label().to_string(), // GAME SELECTION VIEW
+            16 => config.loading_persona.clone(), // LOADING MESSAGES
+            17 => "<-".to_string(),
+            18 => "->".to_string(),
             _ => "".to_string(),
         },
         // CUSTOM ASSETS
@@ -292,17 +490,39 @@ pub fn get_settings_value(page: usize, index: usize, config: &Config, system_vol
                 // Always show the currently selected background
                 trim_extension(&config.background_selection).replace('_', " ").to_uppercase()
             },
-            4 => { // FONT TYPE
+            4 => config.background_color.clone(), // BACKGROUND COLOR
+            5 => config.gradient_start.clone(), // GRADIENT START
+            6 => config.gradient_end.clone(), // GRADIENT END
+            7 => config.gradient_direction.clone(), // GRADIENT DIRECTION
+            8 => { // FONT TYPE
                 // Always show the currently selected font
                 trim_extension(&config.font_selection).replace('_', " ").to_uppercase()
             },
-            5 => "<-".to_string(),
+            9 => if config.load_backgrounds { "ON" } else { "OFF" }.to_string(), // LOAD BACKGROUNDS
+            10 => if config.load_logos { "ON" } else { "OFF" }.to_string(), // LOAD LOGOS
+            11 => if config.load_fonts { "ON" } else { "OFF" }.to_string(), // LOAD FONTS
+            12 => if config.load_music { "ON" } else { "OFF" }.to_string(), // LOAD MUSIC
+            13 => "<-".to_string(),
             _ => "".to_string(),
         },
         _ => "".to_string(), // Default case for unknown pages
     }
 }
 
+/// Applies a change through Config::set. The values come from the lists above,
+/// so a refusal means a bug rather than bad input; it's logged and ignored.
+fn set_config(config: &mut Config, key: &str, value: &str) {
+    if let Err(e) = config.set(key, value) {
+        log_warn!("Ignoring settings change: {}", e);
+    }
+}
+
+fn toggle_config(config: &mut Config, key: &str) {
+    if let Err(e) = config.toggle(key) {
+        log_warn!("Ignoring settings change: {}", e);
+    }
+}
+
 // --- UPDATE FUNCTION ---
 pub fn update(
     current_screen: &mut Screen,
@@ -335,13 +555,12 @@ pub fn update(
     };
 
     // INPUT HANDLING
-    if input_state.up {
-        *settings_menu_selection = if *settings_menu_selection == 0 { options.len() - 1 } else { *settings_menu_selection - 1 };
-        sound_effects.play_cursor_move(&config);
-    }
-    if input_state.down {
-        *settings_menu_selection = (*settings_menu_selection + 1) % options.len();
-        sound_effects.play_cursor_move(&config);
+    if input_state.up || input_state.down {
+        let next = step_selection(*settings_menu_selection, options.len(), input_state.down, config.menu_wrap);
+        if next != *settings_menu_selection {
+            *settings_menu_selection = next;
+            sound_effects.play_cursor_move(&config);
+        }
     }
     if input_state.back {
         *current_screen = Screen::MainMenu;
@@ -402,8 +621,7 @@ pub fn update(
                     };
 
                     // 4. Apply
-                    config.resolution = filtered_resolutions[new_index].to_string();
-                    config.save();
+                    set_config(config, "resolution", filtered_resolutions[new_index]);
                     apply_resolution(&config.resolution);
                     sound_effects.play_cursor_move(&config);
                 }
@@ -417,28 +635,44 @@ pub fn update(
                         (current_index + ASPECT_RATIOS.len() - 1) % ASPECT_RATIOS.len()
                     };
 
-                    config.aspect_ratio = ASPECT_RATIOS[new_index].to_string();
+                    set_config(config, "aspect_ratio", ASPECT_RATIOS[new_index]);
 
                     // [!] AUTO-SWITCH RESOLUTION
                     // When ratio changes, switch to the "Best" (first) resolution for that ratio
                     // to prevent invalid states (like 4:3 ratio but 1920x1080 resolution).
                     if let Some(new_res) = RESOLUTIONS.iter().find(|&&r| matches_aspect_ratio(r, &config.aspect_ratio)) {
-                        config.resolution = new_res.to_string();
+                        set_config(config, "resolution", new_res);
                         apply_resolution(&config.resolution);
                     }
 
-                    config.save();
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            3 => { // SPLASH SCREEN
+            3 => { // LAYOUT
                 if input_state.left || input_state.right {
-                    config.show_splash_screen = !config.show_splash_screen;
-                    config.save();
+                    let current_index = LAYOUTS.iter().position(|&l| l == config.layout).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % LAYOUTS.len()
+                    } else {
+                        (current_index + LAYOUTS.len() - 1) % LAYOUTS.len()
+                    };
+                    set_config(config, "layout", LAYOUTS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            4 => { // LETTERBOX
+                if input_state.left || input_state.right {
+                    toggle_config(config, "letterbox");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            5 => { // SPLASH SCREEN
+                if input_state.left || input_state.right {
+                    toggle_config(config, "show_splash_screen");
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            4 => { // TIME ZONE
+            6 => { // TIME ZONE
                 let mut change_occurred = false;
 
                 // Find the current index of the timezone in our array
@@ -446,39 +680,38 @@ pub fn update(
                     if input_state.left {
                         // Decrement and wrap around if we go below zero
                         let new_index = (current_index + TIMEZONES.len() - 1) % TIMEZONES.len();
-                        config.timezone = TIMEZONES[new_index].to_string();
+                        set_config(config, "timezone", TIMEZONES[new_index]);
                         change_occurred = true;
                     }
                     if input_state.right {
                         // Increment and wrap around using the modulo operator
                         let new_index = (current_index + 1) % TIMEZONES.len();
-                        config.timezone = TIMEZONES[new_index].to_string();
+                        set_config(config, "timezone", TIMEZONES[new_index]);
                         change_occurred = true;
                     }
                 }
 
                 if change_occurred {
                     sound_effects.play_cursor_move(&config);
-                    config.save();
                 }
             },
-            5 => { // BRIGHTNESS
+            7 => { // BRIGHTNESS
+                let step = step_percent(config.brightness_step) as f32 / 100.0;
                 if input_state.left {
-                    set_brightness(*brightness - 0.1); // Decrease by 10%
+                    set_brightness(*brightness - step);
                     *brightness = get_current_brightness().unwrap_or(*brightness); // Refresh the value
                     sound_effects.play_cursor_move(&config);
                 }
                 if input_state.right {
-                    set_brightness(*brightness + 0.1); // Increase by 10%
+                    set_brightness(*brightness + step);
                     *brightness = get_current_brightness().unwrap_or(*brightness); // Refresh the value
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            6 => { // WI-FI
+            8 => { // WI-FI
                 if input_state.left || input_state.right {
                     // Toggle the state optimistically and save immediately.
-                    config.wifi = !config.wifi;
-                    config.save();
+                    toggle_config(config, "wifi");
                     sound_effects.play_cursor_move(&config);
 
                     let action = if config.wifi { "on" } else { "off" };
@@ -513,10 +746,9 @@ pub fn update(
                     }
                 }
             },
-            7 => { // BLUETOOTH
+            9 => { // BLUETOOTH
                 if input_state.left || input_state.right {
-                    config.bluetooth = !config.bluetooth;
-                    config.save();
+                    toggle_config(config, "bluetooth");
                     sound_effects.play_cursor_move(&config);
 
                     let action = if config.bluetooth { "unblock" } else { "block" };
@@ -550,14 +782,108 @@ pub fn update(
                     }
                 }
             },
-            8 => { // AUTOBOOT
+            10 => { // AUTOBOOT
                 if input_state.left || input_state.right {
-                    config.autoboot = !config.autoboot;
-                    config.save();
+                    toggle_config(config, "autoboot");
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            9 => { // GO TO AUDIO SETTINGS
+            11 => { // FULLSCREEN
+                if input_state.left || input_state.right {
+                    toggle_config(config, "fullscreen");
+                    // Applied right away, the layout follows the new screen size through scale_factor
+                    set_fullscreen(config.fullscreen);
+                    if !config.fullscreen {
+                        // Go back to the saved window size (desktop) or the configured resolution
+                        match config.window_size() {
+                            Some((w, h)) if DEV_MODE => request_new_screen_size(w as f32, h as f32),
+                            _ => apply_resolution(&config.resolution),
+                        }
+                    }
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            // The session build owns a single screen, moving its window would only break it
+            12 if !DEV_MODE && (input_state.select || input_state.left || input_state.right) => sound_effects.play_reject(config),
+            12 if DEV_MODE => { // DISPLAY
+                if input_state.left || input_state.right {
+                    let display_count = get_displays().len().max(1);
+                    let current_index = config.display_index.min(display_count - 1);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % display_count
+                    } else {
+                        (current_index + display_count - 1) % display_count
+                    };
+                    set_config(config, "display_index", &new_index.to_string());
+                    apply_display(config);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            13 => { // DISPLAY HOTPLUG
+                if input_state.left || input_state.right {
+                    let current_index = DISPLAY_HOTPLUG.iter().position(|&s| s == config.display_hotplug).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % DISPLAY_HOTPLUG.len()
+                    } else {
+                        (current_index + DISPLAY_HOTPLUG.len() - 1) % DISPLAY_HOTPLUG.len()
+                    };
+
+                    set_config(config, "display_hotplug", DISPLAY_HOTPLUG[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            14 => { // LAUNCH MODE
+                if input_state.left || input_state.right {
+                    config.set_launch_mode(config.launch_mode().toggle());
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            15 => { // ON GAME EXIT
+                if input_state.left || input_state.right {
+                    let current_index = GAME_EXIT_ACTIONS.iter().position(|&s| s == config.on_game_exit).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % GAME_EXIT_ACTIONS.len()
+                    } else {
+                        (current_index + GAME_EXIT_ACTIONS.len() - 1) % GAME_EXIT_ACTIONS.len()
+                    };
+
+                    set_config(config, "on_game_exit", GAME_EXIT_ACTIONS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            16 => { // HOLD TO CONFIRM
+                if input_state.left || input_state.right {
+                    toggle_config(config, "hold_to_confirm");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            17 => { // HOLD BACK FOR MENU
+                if input_state.left || input_state.right {
+                    toggle_config(config, "hold_back_to_menu");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            18 => { // MENU WRAP
+                if input_state.left || input_state.right {
+                    toggle_config(config, "menu_wrap");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            19 => { // SAVE NOTIFICATIONS
+                if input_state.left || input_state.right {
+                    toggle_config(config, "save_toasts");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            20 => { // BUTTON MAPPING
+                if input_state.select {
+                    *current_screen = Screen::ButtonMapping;
+                    sound_effects.play_select(config);
+                }
+            },
+            // NETWORK SHARE is read-only: the share path is set in config.toml, there's no way to type one in here
+            21 if input_state.select || input_state.left || input_state.right => sound_effects.play_reject(config),
+            22 => { // GO TO AUDIO SETTINGS
                 if input_state.select {
                     *current_screen = Screen::AudioSettings;
                     *settings_menu_selection = 0;
@@ -570,44 +896,37 @@ pub fn update(
         // AUDIO SETTINGS
         2 => match settings_menu_selection {
             0 => { // MASTER VOLUME
+                let step = step_percent(config.volume_step);
                 if input_state.left {
-                    adjust_system_volume("10%-"); // Decrease by 10%
+                    adjust_system_volume(&format!("{}%-", step));
                     *system_volume = get_system_volume().unwrap_or(*system_volume); // Refresh the value
                     sound_effects.play_cursor_move(&config);
                 }
                 if input_state.right {
-                    adjust_system_volume("10%+"); // Increase by 10%
+                    adjust_system_volume(&format!("{}%+", step));
                     *system_volume = get_system_volume().unwrap_or(*system_volume); // Refresh the value
                     sound_effects.play_cursor_move(&config);
                 }
             },
             1 => { // BGM VOLUME
                 if input_state.left || input_state.right {
-                    if input_state.left {
-                        config.bgm_volume = (config.bgm_volume - 0.1).max(0.0);
-                    }
-                    if input_state.right {
-                        config.bgm_volume = (config.bgm_volume + 0.1).min(1.0);
-                    }
+                    let step = step_percent(config.bgm_volume_step) as f32 / 100.0;
+                    let step = if input_state.right { step } else { -step };
+                    set_config(config, "bgm_volume", &(config.bgm_volume + step).to_string());
 
                     // Change the volume of the currently playing sound
                     if let Some(sink) = current_bgm {
                         sink.set_volume(config.bgm_volume);
                     }
 
-                    config.save();
                     sound_effects.play_cursor_move(&config);
                 }
             },
             2 => { // SFX Volume
                 if input_state.left || input_state.right {
-                    if input_state.left {
-                        config.sfx_volume = (config.sfx_volume - 0.1).max(0.0);
-                    }
-                    if input_state.right {
-                        config.sfx_volume = (config.sfx_volume + 0.1).min(1.0);
-                    }
-                    config.save();
+                    let step = step_percent(config.sfx_volume_step) as f32 / 100.0;
+                    let step = if input_state.right { step } else { -step };
+                    set_config(config, "sfx_volume", &(config.sfx_volume + step).to_string());
                     sound_effects.play_cursor_move(&config); // Test the new volume
                 }
             },
@@ -627,7 +946,7 @@ pub fn update(
 
                     if new_index != current_index {
                         let new_sink = &available_sinks[new_index];
-                        config.audio_output = new_sink.name.clone();
+                        set_config(config, "audio_output", &new_sink.name);
 
                         // Apply the change immediately
                         let _ = Command::new("wpctl").arg("set-default").arg(new_sink.id.to_string()).status();
@@ -642,14 +961,39 @@ pub fn update(
                     }
                 }
             },
-            4 => { // GO TO GENERAL SETTINGS
+            4 => { // TEST SPEAKERS
+                if input_state.select {
+                    log_info!("Playing speaker test on {}.", config.audio_output);
+                    play_speaker_test();
+                }
+            },
+            5 => { // BOOT CHIME
+                if input_state.left || input_state.right {
+                    toggle_config(config, "boot_chime");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            6 => { // BGM ON DEBUG SCREEN
+                if input_state.left || input_state.right {
+                    let current_index = BGM_ON_DEBUG.iter().position(|&s| s == config.bgm_on_debug).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % BGM_ON_DEBUG.len()
+                    } else {
+                        (current_index + BGM_ON_DEBUG.len() - 1) % BGM_ON_DEBUG.len()
+                    };
+
+                    set_config(config, "bgm_on_debug", BGM_ON_DEBUG[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            7 => { // GO TO GENERAL SETTINGS
                 if input_state.select {
                     *current_screen = Screen::GeneralSettings;
                     *settings_menu_selection = 0;
                     sound_effects.play_select(&config);
                 }
             },
-            5 => { // GO TO GUI CUSTOMIZATION
+            8 => { // GO TO GUI CUSTOMIZATION
                 if input_state.select {
                     *current_screen = Screen::GuiSettings;
                     *settings_menu_selection = 0;
@@ -680,71 +1024,32 @@ pub fn update(
                     let new_theme_name = theme_names[new_index].clone();
 
                     if config.theme != new_theme_name {
-                        config.theme = new_theme_name.clone();
-
-                        if new_theme_name == "Default" {
-                            println!("[INFO] Switched to Default theme.");
-                            let defaults = Config::default();
-
-                            config.sfx_pack = defaults.sfx_pack;
-                            config.bgm_track = defaults.bgm_track;
-                            config.logo_selection = defaults.logo_selection;
-                            config.background_selection = defaults.background_selection;
-                            config.font_selection = defaults.font_selection;
-                            config.menu_position = defaults.menu_position;
-                            config.font_color = defaults.font_color;
-                            config.cursor_color = defaults.cursor_color;
-                            config.cursor_style = defaults.cursor_style;
-                            config.cursor_blink_speed = defaults.cursor_blink_speed;
-                            config.cursor_transition_speed = defaults.cursor_transition_speed;
-                            config.background_scroll_speed = defaults.background_scroll_speed;
-                            config.color_shift_speed = defaults.color_shift_speed;
-
-                            if let Some(default_theme) = loaded_themes.get("Default") {
-                                *sound_effects = default_theme.sounds.clone();
-                            }
-                        } else {
-                            if let Some(theme) = loaded_themes.get(&new_theme_name) {
-                                println!("[INFO] Switched to '{}' theme.", new_theme_name);
-                                *sound_effects = theme.sounds.clone();
-                                config.sfx_pack = theme.config.sfx_pack.clone().unwrap_or_else(|| "Default".to_string());
-                                config.bgm_track = theme.config.bgm_track.clone();
-                                config.logo_selection = theme.config.logo_selection.clone().unwrap_or_else(|| "Kazeta+ (Default)".to_string());
-                                config.background_selection = theme.config.background_selection.clone().unwrap_or_else(|| "Default".to_string());
-                                config.font_selection = theme.config.font_selection.clone().unwrap_or_else(|| "Default".to_string());
-
-                                if let Some(val) = &theme.config.menu_position { config.menu_position = val.parse().unwrap_or_default(); }
-                                if let Some(val) = &theme.config.font_color { config.font_color = val.clone(); }
-                                if let Some(val) = &theme.config.cursor_color { config.cursor_color = val.clone(); }
-                                if let Some(val) = &theme.config.cursor_style { config.cursor_style = val.clone(); }
-                                if let Some(val) = &theme.config.cursor_blink_speed { config.cursor_blink_speed = val.clone(); }
-                                if let Some(val) = &theme.config.cursor_transition_speed { config.cursor_transition_speed = val.clone(); }
-                                if let Some(val) = &theme.config.background_scroll_speed { config.background_scroll_speed = val.clone(); }
-                                if let Some(val) = &theme.config.color_shift_speed { config.color_shift_speed = val.clone(); }
-                            }
+                        // A hand-picked theme is saved and outlasts the schedule for this session
+                        config.pick_theme();
+                        if let Some(pack) = theme::apply_theme(config, &new_theme_name, loaded_themes) {
+                            *sfx_pack_to_reload = Some(pack);
                         }
 
+                        config.resolve_random_picks(background_choices, logo_choices, bgm_choices);
                         play_new_bgm(
-                            &config.bgm_track.clone().unwrap_or_else(|| "OFF".to_string()),
+                            config.active_bgm_track(),
                             config.bgm_volume,
                             music_cache,
                             current_bgm,
                         );
 
-                        sound_effects.play_cursor_move(config);
-                        config.save();
+                        // The reload plays a sound from the new pack itself
+                        if sfx_pack_to_reload.is_none() {
+                            sound_effects.play_cursor_move(config);
+                        }
+                        config.mark_dirty();
                     }
                 }
             },
             1 => { // MENU POSITION
-                if input_state.left {
-                    config.menu_position = config.menu_position.prev();
-                    config.save();
-                    sound_effects.play_cursor_move(config);
-                }
-                if input_state.right {
-                    config.menu_position = config.menu_position.next();
-                    config.save();
+                if input_state.left || input_state.right {
+                    let position = if input_state.right { config.menu_position().next() } else { config.menu_position().prev() };
+                    config.set_menu_position(position);
                     sound_effects.play_cursor_move(config);
                 }
             },
@@ -757,8 +1062,7 @@ pub fn update(
                     } else {
                         (current_index + COLORS.len() - 1) % COLORS.len()
                     };
-                    config.font_color = COLORS[new_index].to_string();
-                    config.save();
+                    set_config(config, "font_color", COLORS[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             }
@@ -772,8 +1076,7 @@ pub fn update(
                         (current_index + COLORS.len() - 1) % COLORS.len()
                     };
 
-                    config.cursor_color = COLORS[new_index].to_string();
-                    config.save();
+                    set_config(config, "cursor_color", COLORS[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
@@ -785,8 +1088,7 @@ pub fn update(
                     } else {
                         (current_index + CURSOR_STYLES.len() - 1) % CURSOR_STYLES.len()
                     };
-                    config.cursor_style = CURSOR_STYLES[new_index].to_string();
-                    config.save();
+                    set_config(config, "cursor_style", CURSOR_STYLES[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
@@ -799,8 +1101,7 @@ pub fn update(
                         (current_index + SPEEDS.len() - 1) % SPEEDS.len()
                     };
 
-                    config.cursor_blink_speed = SPEEDS[new_index].to_string();
-                    config.save();
+                    set_config(config, "cursor_blink_speed", SPEEDS[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
@@ -813,14 +1114,26 @@ pub fn update(
                         (current_index + SPEEDS.len() - 1) % SPEEDS.len()
                     };
 
-                    config.cursor_transition_speed = SPEEDS[new_index].to_string();
-                    config.save();
+                    set_config(config, "cursor_transition_speed", SPEEDS[new_index]);
                     // Trigger a transition so the user sees the effect immediately!
                     animation_state.trigger_transition(&config.cursor_transition_speed);
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            7 => { // BACKGROUND SCROLLING
+            7 => { // REJECT SHAKE
+                if input_state.left || input_state.right {
+                    let current_index = SHAKE_LEVELS.iter().position(|&s| s == config.shake_intensity).unwrap_or(2);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % SHAKE_LEVELS.len()
+                    } else {
+                        (current_index + SHAKE_LEVELS.len() - 1) % SHAKE_LEVELS.len()
+                    };
+
+                    set_config(config, "shake_intensity", SHAKE_LEVELS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            8 => { // BACKGROUND SCROLLING
                 if input_state.left || input_state.right {
                     let current_index = SPEEDS.iter().position(|&s| s == config.background_scroll_speed).unwrap_or(0);
                     let new_index = if input_state.right {
@@ -829,12 +1142,11 @@ pub fn update(
                         (current_index + SPEEDS.len() - 1) % SPEEDS.len()
                     };
 
-                    config.background_scroll_speed = SPEEDS[new_index].to_string();
-                    config.save();
+                    set_config(config, "background_scroll_speed", SPEEDS[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            8 => { // COLOR GRADIENT SHIFTING
+            9 => { // COLOR GRADIENT SHIFTING
                 if input_state.left || input_state.right {
                     let current_index = SPEEDS.iter().position(|&s| s == config.color_shift_speed).unwrap_or(0);
                     let new_index = if input_state.right {
@@ -843,19 +1155,96 @@ pub fn update(
                         (current_index + SPEEDS.len() - 1) % SPEEDS.len()
                     };
 
-                    config.color_shift_speed = SPEEDS[new_index].to_string();
-                    config.save();
+                    set_config(config, "color_shift_speed", SPEEDS[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            9 => { // GO TO AUDIO SETTINGS
+            10 => { // COLOR PALETTE
+                if input_state.left || input_state.right {
+                    let current_index = COLOR_PALETTES.iter().position(|&s| s == config.color_palette).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % COLOR_PALETTES.len()
+                    } else {
+                        (current_index + COLOR_PALETTES.len() - 1) % COLOR_PALETTES.len()
+                    };
+
+                    set_config(config, "color_palette", COLOR_PALETTES[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            11 => { // BATTERY DISPLAY
+                if input_state.left || input_state.right {
+                    let current_index = BATTERY_DISPLAYS.iter().position(|&s| s == config.battery_display).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % BATTERY_DISPLAYS.len()
+                    } else {
+                        (current_index + BATTERY_DISPLAYS.len() - 1) % BATTERY_DISPLAYS.len()
+                    };
+
+                    set_config(config, "battery_display", BATTERY_DISPLAYS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            12 => { // CLOCK POSITION
+                if input_state.left || input_state.right {
+                    let current_index = STATUS_CORNERS.iter().position(|&s| s == config.status_corner).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % STATUS_CORNERS.len()
+                    } else {
+                        (current_index + STATUS_CORNERS.len() - 1) % STATUS_CORNERS.len()
+                    };
+
+                    set_config(config, "status_corner", STATUS_CORNERS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            13 => { // HELP BAR
+                if input_state.left || input_state.right {
+                    toggle_config(config, "show_help_bar");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            14 => { // ICON SIZE
+                if input_state.left || input_state.right {
+                    let current_index = ICON_SCALES.iter().position(|&s| s == config.icon_scale).unwrap_or(1);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % ICON_SCALES.len()
+                    } else {
+                        (current_index + ICON_SCALES.len() - 1) % ICON_SCALES.len()
+                    };
+
+                    set_config(config, "icon_scale", &ICON_SCALES[new_index].to_string());
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            15 => { // GAME SELECTION VIEW
+                if input_state.left || input_state.right {
+                    let view = if input_state.right { config.game_selection_view().next() } else { config.game_selection_view().prev() };
+                    config.set_game_selection_view(view);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            16 => { // LOADING MESSAGES
+                if input_state.left || input_state.right {
+                    let current_index = LOADING_PERSONAS.iter().position(|&s| s == config.loading_persona).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % LOADING_PERSONAS.len()
+                    } else {
+                        (current_index + LOADING_PERSONAS.len() - 1) % LOADING_PERSONAS.len()
+                    };
+
+                    set_config(config, "loading_persona", LOADING_PERSONAS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            17 => { // GO TO AUDIO SETTINGS
                 if input_state.select {
                     *current_screen = Screen::AudioSettings;
                     *settings_menu_selection = 0;
                     sound_effects.play_select(&config);
                 }
             },
-            10 => { // GO TO CUSTOM ASSETS
+            18 => { // GO TO CUSTOM ASSETS
                 if input_state.select {
                     *current_screen = Screen::AssetSettings;
                     *settings_menu_selection = 0;
@@ -880,16 +1269,14 @@ pub fn update(
                     }
 
                     let new_track = &bgm_choices[new_index];
-                    play_new_bgm(new_track, config.bgm_volume, &music_cache, current_bgm);
 
-                    // Update the config with the new choice
-                    if new_track == "OFF" {
-                        config.bgm_track = None;
-                    } else {
-                        config.bgm_track = Some(new_track.clone());
-                    }
+                    // Update the config with the new choice ("OFF" clears it)
+                    set_config(config, "bgm_track", new_track);
+
+                    // RANDOM needs resolving to a real track before it can play
+                    config.resolve_random_picks(background_choices, logo_choices, bgm_choices);
+                    play_new_bgm(config.active_bgm_track(), config.bgm_volume, &music_cache, current_bgm);
 
-                    config.save();
                     sound_effects.play_cursor_move(&config);
                 }
             },
@@ -908,13 +1295,17 @@ pub fn update(
 
                     if &config.sfx_pack != new_pack_name {
                         // 1. Update the config value
-                        config.sfx_pack = new_pack_name.clone();
+                        set_config(config, "sfx_pack", new_pack_name);
+
+                        // Remember this was a manual pick so theme changes don't undo it.
+                        // Going back to the theme's own pack hands control back to the theme.
+                        let theme_pack = loaded_themes.get(&config.theme)
+                        .and_then(|t| t.config.sfx_pack.clone())
+                        .unwrap_or_else(|| "Default".to_string());
+                        set_config(config, "sfx_pack_overridden", if *new_pack_name != theme_pack { "ON" } else { "OFF" });
 
                         // 2. Set the request for the main loop to handle
                         *sfx_pack_to_reload = Some(new_pack_name.clone());
-
-                        // 3. Save the config
-                        config.save();
                     }
                 }
             },
@@ -932,9 +1323,9 @@ pub fn update(
                     }
 
                     // Update the config with the new choice
-                    config.logo_selection = logo_choices[new_index].clone();
+                    set_config(config, "logo_selection", &logo_choices[new_index]);
+                    config.resolve_random_picks(background_choices, logo_choices, bgm_choices);
 
-                    config.save();
                     sound_effects.play_cursor_move(&config);
                 }
             },
@@ -952,13 +1343,66 @@ pub fn update(
                     }
 
                     // Update the config with the new choice
-                    config.background_selection = background_choices[new_index].clone();
+                    set_config(config, "background_selection", &background_choices[new_index]);
+                    config.resolve_random_picks(background_choices, logo_choices, bgm_choices);
 
-                    config.save();
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            4 => { // FONT TYPE
+            4 => { // BACKGROUND COLOR (used by the SOLID COLOR background)
+                if input_state.left || input_state.right {
+                    // A custom "#RRGGBB" isn't in the list, so cycling starts over from the first color
+                    let current_index = COLORS.iter().position(|&c| c == config.background_color).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % COLORS.len()
+                    } else {
+                        (current_index + COLORS.len() - 1) % COLORS.len()
+                    };
+
+                    set_config(config, "background_color", COLORS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            5 => { // GRADIENT START
+                if input_state.left || input_state.right {
+                    let current_index = COLORS.iter().position(|&c| c == config.gradient_start).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % COLORS.len()
+                    } else {
+                        (current_index + COLORS.len() - 1) % COLORS.len()
+                    };
+
+                    set_config(config, "gradient_start", COLORS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            6 => { // GRADIENT END
+                if input_state.left || input_state.right {
+                    let current_index = COLORS.iter().position(|&c| c == config.gradient_end).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % COLORS.len()
+                    } else {
+                        (current_index + COLORS.len() - 1) % COLORS.len()
+                    };
+
+                    set_config(config, "gradient_end", COLORS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            7 => { // GRADIENT DIRECTION
+                if input_state.left || input_state.right {
+                    let current_index = GRADIENT_DIRECTIONS.iter().position(|&c| c == config.gradient_direction).unwrap_or(0);
+                    let new_index = if input_state.right {
+                        (current_index + 1) % GRADIENT_DIRECTIONS.len()
+                    } else {
+                        (current_index + GRADIENT_DIRECTIONS.len() - 1) % GRADIENT_DIRECTIONS.len()
+                    };
+
+                    set_config(config, "gradient_direction", GRADIENT_DIRECTIONS[new_index]);
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            8 => { // FONT TYPE
                 if input_state.left || input_state.right {
                     let current_index = font_choices.iter().position(|name| name == &config.font_selection).unwrap_or(0);
                     let new_index = if input_state.right {
@@ -967,12 +1411,35 @@ pub fn update(
                         (current_index + font_choices.len() - 1) % font_choices.len()
                     };
 
-                    config.font_selection = font_choices[new_index].clone();
-                    config.save();
+                    set_config(config, "font_selection", &font_choices[new_index]);
                     sound_effects.play_cursor_move(&config);
                 }
             },
-            5 => { // GO TO GUI CUSTOMIZATION SETTINGS
+            9 => { // LOAD BACKGROUNDS
+                if input_state.left || input_state.right {
+                    toggle_config(config, "load_backgrounds");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            10 => { // LOAD LOGOS
+                if input_state.left || input_state.right {
+                    toggle_config(config, "load_logos");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            11 => { // LOAD FONTS
+                if input_state.left || input_state.right {
+                    toggle_config(config, "load_fonts");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            12 => { // LOAD MUSIC
+                if input_state.left || input_state.right {
+                    toggle_config(config, "load_music");
+                    sound_effects.play_cursor_move(config);
+                }
+            },
+            13 => { // GO TO GUI CUSTOMIZATION SETTINGS
                 if input_state.select {
                     *current_screen = Screen::GuiSettings;
                     *settings_menu_selection = 0;
@@ -984,3 +1451,19 @@ pub fn update(
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_scroll_keeps_the_selection_in_view() {
+        // Everything fits: no scrolling
+        assert_eq!(first_visible_row(5, 10, 12), 0);
+        // Selection near the top stays put, further down it's kept on the last visible row
+        assert_eq!(first_visible_row(3, 20, 8), 0);
+        assert_eq!(first_visible_row(10, 20, 8), 3);
+        // Never scrolls past the end of the page
+        assert_eq!(first_visible_row(19, 20, 8), 12);
+    }
+}
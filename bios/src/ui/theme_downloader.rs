@@ -1,22 +1,27 @@
 use crate::{
     audio::SoundEffects,
     config::{Config, get_user_data_dir},
-    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, InputState, wrap_text, VideoPlayer,
+    input::{Action, Bindings},
+    ui::action_label,
+    FONT_SIZE, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, measure_readable_text, InputState, wrap_text, VideoPlayer,
 };
 use macroquad::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
-    fs, io, thread,
+    fs, io::{self, Read}, thread,
     collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     process::Command,
-    sync::mpsc::{channel, Receiver, Sender},
+    sync::{Arc, atomic::{AtomicBool, Ordering}, mpsc::{channel, Receiver, Sender}},
+    time::{Duration, Instant},
 };
 use toml;
 
 // --- CONSTANTS ---
 const ITEMS_PER_PAGE: usize = 5;
+/// Last catalog fetched from GitHub, shown when the device is offline (in the user data dir)
+const CATALOG_CACHE_FILE: &str = "theme_catalog.json";
 
 // --- State Management & Structs ---
 
@@ -24,7 +29,17 @@ pub enum DownloaderState {
     Idle,
     FetchingList,
     DisplayingList,
-    Downloading(String),
+    Downloading {
+        name: String,
+        progress: Option<f32>, // 0.0 to 1.0, None if size unknown
+        received_mb: f32,
+        extracting: Option<(usize, usize, String)>, // (file number, file count, file name) once unpacking
+    },
+    ConfirmRetry {
+        theme: RemoteTheme,
+        error: String,
+        selection: usize, // 0=Yes, 1=No
+    },
     Success(String),
     Error(String),
     ConfirmDelete {
@@ -44,11 +59,22 @@ pub enum DownloaderState {
 
 enum DownloaderMessage {
     ThemeList(Result<Vec<RemoteTheme>, String>),
+    CachedThemeList(Vec<RemoteTheme>), // the fetch failed, this is the last list that worked
+    DownloadProgress {
+        progress: Option<f32>,
+        received_mb: f32,
+    },
+    ExtractProgress {
+        file: usize,
+        total: usize,
+        name: String,
+    },
+    DownloadFailed { theme: RemoteTheme, error: String }, // network trouble, offered a retry
     InstallResult(Result<String, String>),
     ConversionResult(Result<String, String>), // -- NEW -- For audio conversion success/error
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RemoteTheme {
     pub name: String,         // Display name, e.g., "Soul Calibur II"
     pub folder_name: String,  // Directory name, e.g., "soul_calibur_ii"
@@ -83,6 +109,8 @@ pub struct ThemeDownloaderState {
     tx: Sender<DownloaderMessage>,
     pub has_audio_tools_option: bool,
     pub current_page: usize,
+    pub offline: bool, // showing the cached catalog
+    cancel_download: Arc<AtomicBool>,
 }
 
 #[derive(Deserialize)]
@@ -111,6 +139,8 @@ impl ThemeDownloaderState {
             tx,
             has_audio_tools_option: true,
             current_page: 0,
+            offline: false,
+            cancel_download: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -118,6 +148,18 @@ impl ThemeDownloaderState {
         fetch_theme_list(self.tx.clone());
         self.screen_state = DownloaderState::FetchingList;
     }
+
+    fn start_download(&mut self, theme: RemoteTheme) {
+        // Each download gets its own flag, so cancelling one can't stop the next
+        self.cancel_download = Arc::new(AtomicBool::new(false));
+        self.screen_state = DownloaderState::Downloading {
+            name: theme.name.clone(),
+            progress: Some(0.0),
+            received_mb: 0.0,
+            extracting: None,
+        };
+        download_and_extract_theme(theme, self.tx.clone(), self.cancel_download.clone());
+    }
 }
 
 pub fn update(
@@ -134,6 +176,12 @@ pub fn update(
                 *current_screen = Screen::Extras;
                 state.screen_state = DownloaderState::Idle; // Reset for next time
             }
+            DownloaderState::Downloading { .. } => {
+                // The worker stops at its next chunk or file and leaves the installed themes untouched
+                state.cancel_download.store(true, Ordering::Relaxed);
+                state.screen_state = DownloaderState::DisplayingList;
+                state.current_page = state.selected_index / ITEMS_PER_PAGE;
+            }
             _ => { // For any sub-menu, go back to the list and reset page
                 state.screen_state = DownloaderState::DisplayingList;
                 state.current_page = state.selected_index / ITEMS_PER_PAGE;
@@ -144,21 +192,31 @@ pub fn update(
 
     if let Ok(msg) = state.rx.try_recv() {
         match msg {
-            DownloaderMessage::ThemeList(Ok(mut themes)) => { // Make themes mutable
-                // Get a list of folders currently installed
-                let installed_themes = get_installed_theme_folders();
-
-                // Check each remote theme against the installed list
-                for theme in themes.iter_mut() {
-                    if installed_themes.contains(&theme.folder_name) {
-                        theme.is_installed = true;
-                    }
-                }
-
-                state.themes = themes;
+            DownloaderMessage::ThemeList(Ok(themes)) => {
+                state.themes = mark_installed(themes);
+                state.offline = false;
+                state.screen_state = DownloaderState::DisplayingList;
+            }
+            DownloaderMessage::CachedThemeList(themes) => {
+                state.themes = mark_installed(themes);
+                state.offline = true;
                 state.screen_state = DownloaderState::DisplayingList;
             }
             DownloaderMessage::ThemeList(Err(e)) => { state.screen_state = DownloaderState::Error(e); }
+            DownloaderMessage::DownloadProgress { progress, received_mb } => {
+                if let DownloaderState::Downloading { progress: p, received_mb: mb, .. } = &mut state.screen_state {
+                    *p = progress;
+                    *mb = received_mb;
+                }
+            }
+            DownloaderMessage::ExtractProgress { file, total, name } => {
+                if let DownloaderState::Downloading { extracting, .. } = &mut state.screen_state {
+                    *extracting = Some((file, total, name));
+                }
+            }
+            DownloaderMessage::DownloadFailed { theme, error } => {
+                state.screen_state = DownloaderState::ConfirmRetry { theme, error, selection: 0 };
+            }
             DownloaderMessage::InstallResult(Ok(theme_name)) => { state.screen_state = DownloaderState::Success(format!("'{}' installed!", theme_name)); *current_screen = Screen::ReloadingThemes; }
             DownloaderMessage::InstallResult(Err(e)) => { state.screen_state = DownloaderState::Error(e); }
             DownloaderMessage::ConversionResult(Ok(msg)) => {
@@ -225,8 +283,7 @@ pub fn update(
                         };
                     } else {
                         // Not installed, download immediately
-                        state.screen_state = DownloaderState::Downloading(theme.name.clone());
-                        download_and_extract_theme(theme, state.tx.clone());
+                        state.start_download(theme);
                     }
                 } else {
                     // This is the existing logic for audio tools
@@ -291,9 +348,7 @@ pub fn update(
                     // Clone the theme *before* changing the state,
                     // so we are not using the borrowed `theme` variable after the state change.
                     let theme_to_download = theme.clone();
-
-                    state.screen_state = DownloaderState::Downloading(theme_to_download.name.clone());
-                    download_and_extract_theme(theme_to_download, state.tx.clone());
+                    state.start_download(theme_to_download);
                 } else { // User selected NO
                     state.screen_state = DownloaderState::DisplayingList;
                 }
@@ -304,6 +359,21 @@ pub fn update(
                 state.screen_state = DownloaderState::DisplayingList;
             }
         }
+        DownloaderState::ConfirmRetry { theme, selection, .. } => {
+            if input_state.left || input_state.right {
+                *selection = 1 - *selection;
+                sound_effects.play_cursor_move(config);
+            }
+            if input_state.select {
+                sound_effects.play_select(config);
+                if *selection == 0 {
+                    let theme_to_download = theme.clone();
+                    state.start_download(theme_to_download);
+                } else {
+                    state.screen_state = DownloaderState::DisplayingList;
+                }
+            }
+        }
         DownloaderState::ConfirmConvertToWav { selection } => {
             if input_state.left || input_state.right { *selection = 1 - *selection; sound_effects.play_cursor_move(&config); }
             if input_state.select {
@@ -466,10 +536,18 @@ pub fn draw(
 
             // Draw pagination controls and hint text
             let hint_y = container_y + container_h - 20.0;
-            let hint_text = "Press [SOUTH] to Download, [WEST] to Delete";
+            let bindings = Bindings::from_config(config);
+            let hint_text = &format!("Press {} to Download, {} to Delete", action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Secondary]));
             let hint_dims = measure_text(hint_text, Some(font), (font_size as f32 * 0.8) as u16, 1.0);
             text_with_config_color(font_cache, config, hint_text, screen_width() / 2.0 - hint_dims.width / 2.0, hint_y, (font_size as f32 * 0.8) as u16);
 
+            if state.offline {
+                let offline_text = "OFFLINE - cached list";
+                let offline_font_size = (font_size as f32 * 0.8) as u16;
+                let offline_dims = measure_text(offline_text, Some(font), offline_font_size, 1.0);
+                text_with_config_color(font_cache, config, offline_text, container_x + container_w - offline_dims.width - 20.0 * scale_factor, text_y_start - (line_height * 0.8), offline_font_size);
+            }
+
             if total_pages > 1 {
                 let page_text = format!("Page {} / {}", state.current_page + 1, total_pages);
                 let page_dims = measure_text(&page_text, Some(font), (font_size as f32 * 0.8) as u16, 1.0);
@@ -485,7 +563,7 @@ pub fn draw(
             draw_rectangle_lines(dialog_x, dialog_y, dialog_w, dialog_h, 3.0, WHITE);
 
             let question = format!("Delete '{}'?", theme_display_name);
-            let question_dims = measure_text(&question, Some(font), font_size, 1.0);
+            let question_dims = measure_readable_text(font_cache, config, &question, font_size);
             text_with_config_color(font_cache, config, &question, screen_width() / 2.0 - question_dims.width / 2.0, dialog_y + 40.0 * scale_factor, font_size);
 
             let yes_text = "YES";
@@ -513,7 +591,7 @@ pub fn draw(
 
             // Line 1
             let question = format!("'{}' is already installed.", theme.name);
-            let question_dims = measure_text(&question, Some(font), font_size, 1.0);
+            let question_dims = measure_readable_text(font_cache, config, &question, font_size);
             text_with_config_color(font_cache, config, &question, screen_width() / 2.0 - question_dims.width / 2.0, dialog_y + 40.0 * scale_factor, font_size);
 
             // Line 2
@@ -580,16 +658,63 @@ pub fn draw(
             let text_dims = measure_text(msg, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, msg, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
         }
-        DownloaderState::Downloading(name) => {
-            let text = format!("Downloading {}...", name);
+        DownloaderState::Downloading { name, progress, received_mb, extracting } => {
+            let center_x = screen_width() / 2.0;
+            let center_y = screen_height() / 2.0;
+
+            let text = match extracting {
+                Some(_) => format!("Installing {}...", name),
+                None => format!("Downloading {}...", name),
+            };
             let text_dims = measure_text(&text, Some(font), font_size, 1.0);
-            text_with_config_color(font_cache, config, &text, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
+            text_with_config_color(font_cache, config, &text, center_x - text_dims.width / 2.0, center_y - 60.0 * scale_factor, font_size);
+
+            let bar_w = screen_width() * 0.6;
+            let bar_h = 30.0 * scale_factor;
+            let bar_x = center_x - bar_w / 2.0;
+            let bar_y = center_y;
+            draw_rectangle(bar_x, bar_y, bar_w, bar_h, BLACK);
+            draw_rectangle_lines(bar_x, bar_y, bar_w, bar_h, 3.0, WHITE);
+
+            // The bar follows the download, then the files being unpacked
+            let (fill, progress_text) = match (extracting, progress) {
+                (Some((file, total, file_name)), _) => {
+                    (Some(*file as f32 / (*total).max(1) as f32), format!("File {} of {}: {}", file, total, file_name))
+                }
+                (None, Some(p)) => (Some(*p), format!("{:.0}% ({:.1} MB)", p * 100.0, received_mb)),
+                (None, None) => (None, format!("Downloading... ({:.1} MB)", received_mb)),
+            };
+            match fill {
+                Some(p) => draw_rectangle(bar_x, bar_y, bar_w * p.clamp(0.0, 1.0), bar_h, WHITE),
+                None => {
+                    // Size unknown, sweep a block across the bar
+                    let scan_width = bar_w * 0.1;
+                    let scan_pos = (get_time() as f32 * (bar_w * 0.5)) % (bar_w - scan_width);
+                    draw_rectangle(bar_x + scan_pos, bar_y, scan_width, bar_h, WHITE);
+                }
+            }
+
+            let small_font_size = (font_size as f32 * 0.8) as u16;
+            let progress_dims = measure_text(&progress_text, Some(font), small_font_size, 1.0);
+            text_with_config_color(font_cache, config, &progress_text, center_x - progress_dims.width / 2.0, bar_y + bar_h + 40.0 * scale_factor, small_font_size);
+
+            let hint_text = &format!("Press {} to Cancel", action_label(&Bindings::from_config(config), &[Action::Back]));
+            let hint_dims = measure_text(hint_text, Some(font), small_font_size, 1.0);
+            text_with_config_color(font_cache, config, hint_text, center_x - hint_dims.width / 2.0, container_y + container_h - 20.0, small_font_size);
+        }
+        DownloaderState::ConfirmRetry { theme, error, selection } => {
+            draw_conversion_dialog(
+                font_cache, config, font, font_size, line_height, scale_factor, animation_state,
+                "Download Failed. Retry?",
+                &[&theme.name, error],
+                *selection
+            );
         }
         DownloaderState::Success(msg) | DownloaderState::Error(msg) => {
             let text_dims = measure_text(msg, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, msg, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
 
-            let continue_text = "Press [SOUTH] to continue";
+            let continue_text = &format!("Press {} to continue", action_label(&Bindings::from_config(config), &[Action::Select]));
             let continue_dims = measure_text(continue_text, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, continue_text, screen_width() / 2.0 - continue_dims.width / 2.0, screen_height() / 2.0 + line_height * 2.0, font_size);
         }
@@ -660,24 +785,124 @@ fn fetch_theme_list(tx: Sender<DownloaderMessage>) {
             },
             Err(_) => Err("Failed to fetch theme list from GitHub.".to_string()),
         };
-        tx.send(DownloaderMessage::ThemeList(result)).unwrap();
+
+        let message = match result {
+            Ok(themes) => {
+                save_catalog_cache(&themes);
+                DownloaderMessage::ThemeList(Ok(themes))
+            }
+            Err(e) => match load_catalog_cache() {
+                Some(themes) => {
+                    log_warn!("{} Showing the cached catalog.", e);
+                    DownloaderMessage::CachedThemeList(themes)
+                }
+                None => DownloaderMessage::ThemeList(Err(e)),
+            },
+        };
+        tx.send(message).unwrap();
     });
 }
 
-fn download_and_extract_theme(theme: RemoteTheme, tx: Sender<DownloaderMessage>) {
+/// Downloads and installs a theme, reporting progress as it goes. Network errors come back as
+/// DownloadFailed so the user can retry; once `cancel` is set the worker stops and reports nothing.
+fn download_and_extract_theme(theme: RemoteTheme, tx: Sender<DownloaderMessage>, cancel: Arc<AtomicBool>) {
     thread::spawn(move || {
-        let result = (|| -> Result<String, String> {
-            let themes_dir = get_user_data_dir().ok_or("Could not find user data directory.")?.join("themes");
-            let response_bytes = reqwest::blocking::get(&theme.download_url).map_err(|e| format!("Download failed: {}", e))?.bytes().map_err(|e| format!("Failed to read download: {}", e))?;
-            let reader = io::Cursor::new(response_bytes);
-            let mut archive = zip::ZipArchive::new(reader).map_err(|e| format!("Invalid zip file: {}", e))?;
-            archive.extract(&themes_dir).map_err(|e| format!("Failed to extract theme: {}", e))?;
-            Ok(theme.name)
-        })();
-        tx.send(DownloaderMessage::InstallResult(result)).unwrap();
+        let archive_bytes = match download_theme_archive(&theme, &tx, &cancel) {
+            Ok(bytes) => bytes,
+            Err(_) if cancel.load(Ordering::Relaxed) => return,
+            Err(error) => {
+                tx.send(DownloaderMessage::DownloadFailed { theme, error }).ok();
+                return;
+            }
+        };
+
+        let result = install_theme_archive(archive_bytes, &tx, &cancel).map(|_| theme.name);
+        if !cancel.load(Ordering::Relaxed) {
+            tx.send(DownloaderMessage::InstallResult(result)).ok();
+        }
     });
 }
 
+/// Streams the theme's zip into memory, sending a progress update every 50ms.
+fn download_theme_archive(theme: &RemoteTheme, tx: &Sender<DownloaderMessage>, cancel: &AtomicBool) -> Result<Vec<u8>, String> {
+    let client = reqwest::blocking::Client::builder().user_agent("KazetaPlus-Theme-Downloader").build().map_err(|e| e.to_string())?;
+    let mut response = client.get(&theme.download_url).send().map_err(|e| format!("Download failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed: Server returned {}", response.status()));
+    }
+
+    let total_size = response.content_length();
+    let mut received = Vec::new();
+    let mut buffer = [0; 8192];
+    let mut last_update = Instant::now();
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("Download cancelled".to_string());
+        }
+        let bytes_read = response.read(&mut buffer).map_err(|e| format!("Failed to read download: {}", e))?;
+        if bytes_read == 0 { break; }
+        received.extend_from_slice(&buffer[..bytes_read]);
+
+        if last_update.elapsed() >= Duration::from_millis(50) {
+            let progress = total_size.map(|total| received.len() as f32 / total as f32);
+            let received_mb = received.len() as f32 / 1024.0 / 1024.0;
+            tx.send(DownloaderMessage::DownloadProgress { progress, received_mb }).ok();
+            last_update = Instant::now();
+        }
+    }
+    Ok(received)
+}
+
+/// Unpacks the zip into a staging folder one file at a time, then swaps its contents into the
+/// themes folder. A cancelled or failed install leaves any existing copy of the theme as it was.
+/// Each install stages in its own hidden temp folder (removed on drop), so two installs can't
+/// trample each other's files.
+fn install_theme_archive(archive_bytes: Vec<u8>, tx: &Sender<DownloaderMessage>, cancel: &AtomicBool) -> Result<(), String> {
+    let themes_dir = get_user_data_dir().ok_or("Could not find user data directory.")?.join("themes");
+    fs::create_dir_all(&themes_dir).map_err(|e| format!("Failed to create {}: {}", themes_dir.display(), e))?;
+    let staging = tempfile::tempdir_in(&themes_dir).map_err(|e| format!("Failed to create a staging folder in {}: {}", themes_dir.display(), e))?;
+    let staging_dir = staging.path();
+
+    (|| -> Result<(), String> {
+        let mut archive = zip::ZipArchive::new(io::Cursor::new(archive_bytes)).map_err(|e| format!("Invalid zip file: {}", e))?;
+        let total = archive.len();
+
+        for i in 0..total {
+            if cancel.load(Ordering::Relaxed) {
+                return Err("Install cancelled".to_string());
+            }
+            let mut file = archive.by_index(i).map_err(|e| format!("Failed to read zip: {}", e))?;
+            let Some(relative_path) = file.enclosed_name() else { continue }; // skip entries escaping the folder
+            let name = relative_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            tx.send(DownloaderMessage::ExtractProgress { file: i + 1, total, name }).ok();
+
+            let out_path = staging_dir.join(&relative_path);
+            if file.is_dir() {
+                fs::create_dir_all(&out_path).map_err(|e| format!("Failed to extract theme: {}", e))?;
+            } else {
+                if let Some(parent) = out_path.parent() {
+                    fs::create_dir_all(parent).map_err(|e| format!("Failed to extract theme: {}", e))?;
+                }
+                let mut out_file = fs::File::create(&out_path).map_err(|e| format!("Failed to extract theme: {}", e))?;
+                io::copy(&mut file, &mut out_file).map_err(|e| format!("Failed to extract theme: {}", e))?;
+            }
+        }
+
+        // Replace the old copies of whatever the zip contained
+        for entry in fs::read_dir(staging_dir).map_err(|e| e.to_string())?.flatten() {
+            let target = themes_dir.join(entry.file_name());
+            if target.is_dir() {
+                fs::remove_dir_all(&target).map_err(|e| format!("Failed to replace {}: {}", target.display(), e))?;
+            } else if target.exists() {
+                fs::remove_file(&target).map_err(|e| format!("Failed to replace {}: {}", target.display(), e))?;
+            }
+            fs::rename(entry.path(), &target).map_err(|e| format!("Failed to install {}: {}", target.display(), e))?;
+        }
+        Ok(())
+    })()
+}
+
 fn convert_files_to_wav(tx: Sender<DownloaderMessage>) {
     thread::spawn(move || {
         let result = (|| -> Result<String, String> {
@@ -793,6 +1018,34 @@ fn update_theme_toml(audio_path: &Path, new_ext: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Flags the catalog entries whose folder is already in the themes directory.
+fn mark_installed(mut themes: Vec<RemoteTheme>) -> Vec<RemoteTheme> {
+    let installed_themes = get_installed_theme_folders();
+    for theme in themes.iter_mut() {
+        theme.is_installed = installed_themes.contains(&theme.folder_name);
+    }
+    themes
+}
+
+fn catalog_cache_path() -> Option<PathBuf> {
+    get_user_data_dir().map(|dir| dir.join(CATALOG_CACHE_FILE))
+}
+
+fn save_catalog_cache(themes: &[RemoteTheme]) {
+    let Some(path) = catalog_cache_path() else { return };
+    match serde_json::to_string(themes) {
+        Ok(json) => if let Err(e) = fs::write(&path, json) {
+            log_warn!("Failed to cache theme catalog to {}: {}", path.display(), e);
+        },
+        Err(e) => log_warn!("Failed to serialize theme catalog: {}", e),
+    }
+}
+
+fn load_catalog_cache() -> Option<Vec<RemoteTheme>> {
+    let content = fs::read_to_string(catalog_cache_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
 /// Scans the user's themes directory and returns a HashSet of installed theme folder names.
 fn get_installed_theme_folders() -> HashSet<String> {
     if let Some(themes_dir) = get_user_data_dir().map(|d| d.join("themes")) {
@@ -800,8 +1053,8 @@ fn get_installed_theme_folders() -> HashSet<String> {
             // Use flatten() to filter out any read errors on individual entries
             return entries.flatten()
             .filter_map(|entry| {
-                // Check if it's a directory
-                if entry.path().is_dir() {
+                // Check if it's a directory (hidden ones are in-progress installs)
+                if entry.path().is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
                     // Try to convert the file/folder name to a String
                     entry.file_name().into_string().ok()
                 } else {
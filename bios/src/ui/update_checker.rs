@@ -1,6 +1,8 @@
 use crate::{
     audio::SoundEffects,
     config::Config,
+    input::{Action, Bindings},
+    ui::action_label,
     FONT_SIZE, VERSION_NUMBER, Screen, BackgroundState, render_background, get_current_font, text_with_config_color, InputState, wrap_text, VideoPlayer,
 };
 use macroquad::prelude::*;
@@ -221,6 +223,8 @@ pub fn draw(
     draw_rectangle(container_x, container_y, container_w, container_h, Color::new(0.0, 0.0, 0.0, 0.75));
     let text_x = container_x + 30.0 * scale_factor;
     let text_y_start = container_y + 40.0 * scale_factor;
+    let bindings = Bindings::from_config(config);
+    let return_text = format!("Press {} or {} to return.", action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Back]));
 
     match &state.screen_state {
         UpdateCheckerScreenState::Idle => {
@@ -236,7 +240,7 @@ pub fn draw(
         UpdateCheckerScreenState::UpToDate => {
             text_with_config_color(font_cache, config, "You are running the latest version.", text_x, text_y_start, font_size);
             text_with_config_color(font_cache, config, &format!("Current version: {}", VERSION_NUMBER), text_x, text_y_start + line_height, font_size);
-            text_with_config_color(font_cache, config, "Press [SOUTH] or [EAST] to return.", text_x, text_y_start + line_height * 3.0, font_size);
+            text_with_config_color(font_cache, config, &return_text, text_x, text_y_start + line_height * 3.0, font_size);
         }
         UpdateCheckerScreenState::UpdateAvailable(release) => {
             text_with_config_color(font_cache, config, &format!("New version available: {}", release.tag_name), text_x, text_y_start, font_size);
@@ -300,7 +304,7 @@ pub fn draw(
                 }
             }
 
-            let continue_text = "Press [SOUTH] to Install Update";
+            let continue_text = &format!("Press {} to Install Update", action_label(&bindings, &[Action::Select]));
             let continue_dims = measure_text(continue_text, Some(font), font_size, 1.0);
             text_with_config_color(font_cache, config, continue_text, screen_width() / 2.0 - continue_dims.width / 2.0, container_y + container_h - 20.0 * scale_factor, font_size);
         }
@@ -310,7 +314,7 @@ pub fn draw(
         }
         UpdateCheckerScreenState::UpdateComplete => {
             let line1 = "Update Complete!";
-            let line2 = "Press [SOUTH] to shut down, or [WEST] to reboot.";
+            let line2 = &format!("Press {} to shut down, or {} to reboot.", action_label(&bindings, &[Action::Select]), action_label(&bindings, &[Action::Secondary]));
 
             let dims1 = measure_text(line1, Some(font), font_size, 1.0);
             let dims2 = measure_text(line2, Some(font), font_size, 1.0);
@@ -321,7 +325,7 @@ pub fn draw(
         UpdateCheckerScreenState::Error(msg) => {
             text_with_config_color(font_cache, config, "An error occurred:", text_x, text_y_start, font_size);
             text_with_config_color(font_cache, config, msg, text_x, text_y_start + line_height, font_size);
-            text_with_config_color(font_cache, config, "Press [SOUTH] or [EAST] to return.", text_x, text_y_start + line_height * 3.0, font_size);
+            text_with_config_color(font_cache, config, &return_text, text_x, text_y_start + line_height * 3.0, font_size);
         }
     }
 }
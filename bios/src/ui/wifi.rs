@@ -2,7 +2,7 @@ use crate::{
     text_with_config_color, get_current_font, DEV_MODE, VideoPlayer,
     audio::SoundEffects,
     config::Config, FONT_SIZE, Screen, BackgroundState, render_background, measure_text, InputState,
-    ui::text_with_color,
+    ui::{qr, text_with_color},
 };
 use macroquad::prelude::*;
 use std::{
@@ -60,6 +60,8 @@ pub struct WifiState {
     pub osk_coords: (usize, usize),
     pub osk_shift_active: bool,
     pub show_password: bool,
+    /// QR code for joining the network we just connected to, shown on the success screen
+    pub share_qr: Option<qr::QrGrid>,
     rx: Receiver<WifiMessage>,
     _tx: Sender<WifiMessage>,
 }
@@ -78,6 +80,7 @@ impl WifiState {
             osk_coords: (0, 0),
             osk_shift_active: false,
             show_password: false,
+            share_qr: None,
             rx,
             _tx: tx,
         }
@@ -165,6 +168,7 @@ impl WifiState {
                 match output {
                     Ok(output) => {
                         if output.status.success() {
+                            self.share_qr = qr::encode(&wifi_join_payload(selected_network, password));
                             self.screen_state = WifiScreenState::Connected;
                         } else {
                             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -492,7 +496,23 @@ pub fn draw(
         WifiScreenState::Connected => {
             let text = "Successfully Connected!";
             let text_dims = measure_text(text, Some(font), font_size, 1.0);
-            text_with_config_color(font_cache, config, text, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
+            match &wifi_state.share_qr {
+                Some(grid) => {
+                    text_with_config_color(font_cache, config, text, screen_width() / 2.0 - text_dims.width / 2.0, container_y + 40.0 * scale_factor, font_size);
+
+                    let hint = "Scan to join this network";
+                    let qr_top = container_y + 40.0 * scale_factor + line_height;
+                    let qr_side = (container_h - (qr_top - container_y) - line_height * 1.5).max(0.0);
+                    let qr_x = screen_width() / 2.0 - qr_side / 2.0;
+                    qr::draw_qr(grid, qr_x, qr_top, qr_side);
+
+                    let hint_dims = measure_text(hint, Some(font), font_size, 1.0);
+                    text_with_config_color(font_cache, config, hint, screen_width() / 2.0 - hint_dims.width / 2.0, qr_top + qr_side + line_height, font_size);
+                }
+                None => {
+                    text_with_config_color(font_cache, config, text, screen_width() / 2.0 - text_dims.width / 2.0, screen_height() / 2.0, font_size);
+                }
+            }
         }
         WifiScreenState::Error(msg) => {
             text_with_config_color(font_cache, config, "Connection Failed", text_x, container_y + 80.0 * scale_factor, font_size);
@@ -506,15 +526,12 @@ pub fn draw(
 
             let mut y_offset = container_y + 80.0 * scale_factor + line_height;
 
-            // Split the message into chunks that fit
-            let mut start = 0;
-            let len = msg.len();
-            while start < len {
-                let end = usize::min(start + chars_per_line, len);
-                let slice = &msg[start..end];
-                text_with_config_color(font_cache, config, slice, text_x, y_offset, font_size);
+            // Split the message into chunks that fit (by character, nmcli errors can quote non-ASCII SSIDs)
+            let chars: Vec<char> = msg.chars().collect();
+            for chunk in chars.chunks(chars_per_line.max(1)) {
+                let slice: String = chunk.iter().collect();
+                text_with_config_color(font_cache, config, &slice, text_x, y_offset, font_size);
                 y_offset += line_height;
-                start = end;
             }
         }
         _ => {
@@ -529,6 +546,26 @@ pub fn draw(
     }
 }
 
+/// Builds the standard `WIFI:` payload that phone cameras understand for joining a network.
+fn wifi_join_payload(ap: &AccessPoint, password: &str) -> String {
+    fn escape(value: &str) -> String {
+        value.chars().fold(String::new(), |mut out, c| {
+            if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                out.push('\\');
+            }
+            out.push(c);
+            out
+        })
+    }
+
+    if password.is_empty() {
+        format!("WIFI:S:{};T:nopass;;", escape(&ap.ssid))
+    } else {
+        let auth = if ap.security.contains("WEP") { "WEP" } else { "WPA" };
+        format!("WIFI:S:{};T:{};P:{};;", escape(&ap.ssid), auth, escape(password))
+    }
+}
+
 // --- Background Thread Functions ---
 
 fn prepare_wifi_system(tx: Sender<WifiMessage>) {
@@ -5,10 +5,13 @@ use std::io::BufRead;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use chrono::Local;
 use crate::{save, Child, Arc, Mutex, thread, BufReader};
 use crate::audio::play_new_bgm;
-use crate::types::Screen;
+use crate::types::{DisplayInfo, LaunchMode, RunningGame, Screen};
+use crate::config::{Config, get_user_data_dir};
+use crate::system::get_displays;
 //use macroquad::audio::Sound;
 
 // wrap text in certain menus so it doesn't clip outside the screen
@@ -48,18 +51,26 @@ pub fn wrap_text(text: &str, font: Font, font_size: u16, max_width: f32) -> Vec<
     lines
 }
 
+/// Still image files the background and logo loaders accept.
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// Font files the loader accepts. OTF goes through the same loader as TTF.
+pub const FONT_EXTENSIONS: &[&str] = &["ttf", "otf"];
+
+/// Whether the path's extension is one of `extensions` (compared case-insensitively).
+pub fn has_asset_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
 /// Scans a directory and returns a sorted list of paths for files with given extensions.
 pub fn find_asset_files(dir_path: &str, extensions: &[&str]) -> Vec<PathBuf> {
     if let Ok(entries) = fs::read_dir(dir_path) {
         let mut files: Vec<PathBuf> = entries
         .flatten()
         .map(|e| e.path())
-        .filter(|path| {
-            path.is_file() &&
-            path.extension()
-            .and_then(|s| s.to_str())
-            .map_or(false, |ext| extensions.contains(&ext))
-        })
+        .filter(|path| path.is_file() && has_asset_extension(path, extensions))
         .collect();
         files.sort();
         return files;
@@ -67,6 +78,43 @@ pub fn find_asset_files(dir_path: &str, extensions: &[&str]) -> Vec<PathBuf> {
     vec![]
 }
 
+/// The cache key for an asset file: its filename, or "<theme>/<filename>" for files inside a
+/// theme folder, so two themes can ship identically named assets.
+pub fn asset_key(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let theme_dir = path.parent()?;
+    let in_theme = theme_dir.parent().and_then(|p| p.file_name()).is_some_and(|name| name == "themes");
+    match theme_dir.file_name().and_then(|s| s.to_str()) {
+        Some(theme) if in_theme => Some(format!("{}/{}", theme, file_name)),
+        _ => Some(file_name.to_string()),
+    }
+}
+
+/// Hash of every file's path, size and modification time. Two scans with the same
+/// fingerprint found the same files, so whatever was loaded from the first is still current.
+pub fn asset_fingerprint<'a>(files: impl IntoIterator<Item = &'a PathBuf>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for path in files {
+        path.hash(&mut hasher);
+        if let Ok(metadata) = fs::metadata(path) {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Logs the files in an asset folder that the scan skips because of their type,
+/// so a user wondering why their file isn't listed can find out from the log.
+pub fn log_skipped_asset_files(dir_path: &str, extensions: &[&str]) {
+    let Ok(entries) = fs::read_dir(dir_path) else { return };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_file() && !has_asset_extension(&path, extensions) {
+            log_warn!("Skipping {}: unsupported file type (expected {})", path.display(), extensions.join(", "));
+        }
+    }
+}
+
 // Helper to read the first line from a file containing a specific key
 pub fn read_line_from_file(path: &str, key: &str) -> Option<String> {
     fs::read_to_string(path).ok()?.lines()
@@ -116,14 +164,22 @@ pub fn trigger_session_restart(
     (Screen::FadingOut, Some(get_time()))
 }
 
+/// Starts the selected game according to the configured launch mode.
 pub fn trigger_game_launch(
-    _cart_info: &save::CartInfo,
+    cart_info: &save::CartInfo,
     kzi_path: &Path,
+    launch_mode: LaunchMode,
     //current_bgm: &mut Option<Sound>,
     //music_cache: &HashMap<String, Sound>,
     current_bgm: &mut Option<Sink>,
     music_cache: &HashMap<String, SamplesBuffer>,
+    log_messages: &Arc<Mutex<Vec<String>>>,
+    game_process: &mut Option<RunningGame>,
 ) -> (Screen, Option<f64>) {
+    if launch_mode == LaunchMode::InPlace {
+        return (launch_in_place(cart_info, kzi_path, log_messages, game_process), None);
+    }
+
     // Write the specific launch command for the selected game
     if let Err(e) = save::write_launch_command(kzi_path) {
         // If we fail, we should probably show an error on the debug screen
@@ -136,6 +192,47 @@ pub fn trigger_game_launch(
     trigger_session_restart(current_bgm, music_cache)
 }
 
+/// Runs the game as a child process and sends the user to the debug screen,
+/// which streams the game's output and kills it on back.
+pub fn launch_in_place(
+    cart_info: &save::CartInfo,
+    kzi_path: &Path,
+    log_messages: &Arc<Mutex<Vec<String>>>,
+    game_process: &mut Option<RunningGame>,
+) -> Screen {
+    { // Scoped lock to add messages
+        let mut logs = log_messages.lock().unwrap();
+        logs.push("--- CARTRIDGE FOUND ---".to_string());
+        logs.push(format!("Name: {}", cart_info.name.as_deref().unwrap_or("N/A")));
+        logs.push(format!("ID: {}", cart_info.id));
+        logs.push(format!("Exec: {}", cart_info.exec));
+        logs.push(format!("Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None")));
+        logs.push(format!("KZI Path: {}", kzi_path.display()));
+    }
+    log_debug!("Cartridge selected! Preparing to launch in place...");
+    log_debug!("  Name: {}", cart_info.name.as_deref().unwrap_or("N/A"));
+    log_debug!("  ID: {}", cart_info.id);
+    log_debug!("  Exec: {}", cart_info.exec);
+    log_debug!("  Runtime: {}", cart_info.runtime.as_deref().unwrap_or("None"));
+    log_debug!("  KZI Path: {}", kzi_path.display());
+
+    match save::launch_game(cart_info, kzi_path) {
+        Ok(mut child) => {
+            log_messages.lock().unwrap().push("\n--- LAUNCHING GAME ---".to_string());
+            start_log_reader(&mut child, log_messages.clone());
+            *game_process = Some(RunningGame {
+                process: child,
+                cart: cart_info.clone(),
+                kzi_path: kzi_path.to_path_buf(),
+            });
+        }
+        Err(e) => {
+            log_messages.lock().unwrap().push(format!("\n--- LAUNCH FAILED ---\nError: {}", e));
+        }
+    }
+    Screen::Debug
+}
+
 pub fn save_log_to_file(log_messages: &[String]) -> std::io::Result<String> {
     let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
     let filename = format!("kazeta_log_{}.log", timestamp);
@@ -169,6 +266,20 @@ pub fn start_log_reader(process: &mut Child, logs: Arc<Mutex<Vec<String>>>) {
     }
 }
 
+/// Moves a list cursor one entry up or down. Past either end it wraps around when `wrap`
+/// is set and stays put otherwise.
+pub fn step_selection(current: usize, len: usize, forward: bool, wrap: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    match (forward, wrap) {
+        (true, true) => (current + 1) % len,
+        (true, false) => (current + 1).min(len - 1),
+        (false, true) => if current == 0 { len - 1 } else { current - 1 },
+        (false, false) => current.saturating_sub(1),
+    }
+}
+
 /// Removes the file extension from a filename string slice.
 pub fn trim_extension(filename: &str) -> &str {
     if let Some(dot_index) = filename.rfind('.') {
@@ -178,6 +289,33 @@ pub fn trim_extension(filename: &str) -> &str {
     }
 }
 
+/// Whether a filename is `<name><suffix>.<image extension>`, e.g. ("city_background.jpg", "_background").
+pub fn is_named_image(filename: &str, suffix: &str) -> bool {
+    Path::new(filename).file_stem()
+        .and_then(|s| s.to_str())
+        .is_some_and(|stem| stem.ends_with(suffix))
+        && has_asset_extension(Path::new(filename), IMAGE_EXTENSIONS)
+}
+
+/// Loads a PNG, JPG or WebP file into a texture. macroquad's own loader only handles PNG,
+/// so everything goes through the `image` crate instead.
+pub async fn load_image_texture(path: &str) -> Result<Texture2D, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let image = ::image::load_from_memory(&bytes).map_err(|e| e.to_string())?.to_rgba8();
+    let texture = Texture2D::from_rgba8(image.width() as u16, image.height() as u16, image.as_raw());
+    Ok(texture)
+}
+
+/// Splits a parallax layer filename ("city_bg2.png") into its set name and layer number ("city", 2).
+pub fn parallax_layer(filename: &str) -> Option<(&str, u32)> {
+    let stem = filename.strip_suffix(".png")?;
+    let (name, layer) = stem.rsplit_once("_bg")?;
+    if layer.is_empty() || !layer.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((name, layer.parse().ok()?))
+}
+
 pub fn string_to_color(color_str: &str) -> Color {
     match color_str {
         "BLACK" => BLACK,
@@ -192,13 +330,169 @@ pub fn string_to_color(color_str: &str) -> Color {
     }
 }
 
+/// Parses "#RRGGBB" (the # is optional) into a color.
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(Color::from_hex)
+}
+
+/// Parses an aspect ratio setting like "16:9" into width / height.
+pub fn parse_aspect_ratio(ratio: &str) -> Option<f32> {
+    let (w, h) = ratio.split_once(':')?;
+    let (w, h) = (w.trim().parse::<f32>().ok()?, h.trim().parse::<f32>().ok()?);
+    (w > 0.0 && h > 0.0).then(|| w / h)
+}
+
+/// Whether menus use the portrait layout, per the layout setting (AUTO follows the screen shape).
+pub fn is_portrait(config: &Config) -> bool {
+    match config.layout.as_str() {
+        "PORTRAIT" => true,
+        "LANDSCAPE" => false,
+        _ => screen_height() > screen_width(),
+    }
+}
+
+/// The part of the screen the background is drawn in. With letterboxing on, this is the
+/// largest centered rectangle with the configured aspect ratio (turned on its side in the
+/// portrait layout, so 16:9 becomes 9:16); otherwise the whole screen.
+pub fn letterbox_area(config: &Config) -> Rect {
+    let (width, height) = (screen_width(), screen_height());
+    let full = Rect::new(0.0, 0.0, width, height);
+    if !config.letterbox {
+        return full;
+    }
+    let Some(target) = parse_aspect_ratio(&config.aspect_ratio) else { return full };
+    let target = if is_portrait(config) { 1.0 / target } else { target };
+
+    let screen_ratio = width / height;
+    if (screen_ratio - target).abs() < 0.01 {
+        full
+    } else if screen_ratio > target {
+        // Wider than the target: bars left and right
+        let area_width = height * target;
+        Rect::new((width - area_width) / 2.0, 0.0, area_width, height)
+    } else {
+        // Taller than the target (portrait screens): bars top and bottom
+        let area_height = width / target;
+        Rect::new(0.0, (height - area_height) / 2.0, width, area_height)
+    }
+}
+
+/// UI scale relative to the 360p base layout. With letterboxing on it follows the
+/// letterboxed area, so the layout still fits across on portrait screens. The portrait
+/// layout is 360 wide instead of 360 tall.
+pub fn ui_scale_factor(config: &Config) -> f32 {
+    let area = letterbox_area(config);
+    if is_portrait(config) {
+        area.w / crate::BASE_SCREEN_HEIGHT
+    } else {
+        area.h / crate::BASE_SCREEN_HEIGHT
+    }
+}
+
+/// A color setting that may be either a named color or "#RRGGBB".
+pub fn config_color(value: &str) -> Color {
+    parse_hex_color(value).unwrap_or_else(|| string_to_color(value))
+}
+
+/// The colors the background tint cycles through, per the color_palette setting.
+/// A CUSTOM palette with no valid colors falls back to the default one.
+pub fn color_targets(config: &Config) -> Vec<Color> {
+    match config.color_palette.as_str() {
+        "PASTEL" => crate::PASTEL_COLOR_TARGETS.to_vec(),
+        "NEON" => crate::NEON_COLOR_TARGETS.to_vec(),
+        "GRAYSCALE" => crate::GRAYSCALE_COLOR_TARGETS.to_vec(),
+        "CUSTOM" => {
+            let colors: Vec<Color> = config.custom_palette.iter().filter_map(|c| parse_hex_color(c)).collect();
+            if colors.is_empty() { crate::COLOR_TARGETS.to_vec() } else { colors }
+        }
+        _ => crate::COLOR_TARGETS.to_vec(),
+    }
+}
+
+/// The monitor config.display_index points at, falling back to the primary (or first) one.
+pub fn chosen_display<'a>(config: &Config, displays: &'a [DisplayInfo]) -> Option<&'a DisplayInfo> {
+    displays.get(config.display_index)
+        .or_else(|| displays.iter().find(|d| d.primary))
+        .or_else(|| displays.first())
+}
+
+/// Switches to a preset resolution (and the aspect ratio it belongs to) and resizes the window.
+pub fn switch_resolution(config: &mut Config, resolution: &str, aspect_ratio: &str) {
+    for (key, value) in [("aspect_ratio", aspect_ratio), ("resolution", resolution)] {
+        if let Err(e) = config.set(key, value) {
+            log_warn!("Ignoring display change: {}", e);
+            return;
+        }
+    }
+    log_info!("Switching to {} for the new display.", resolution);
+    apply_resolution(&config.resolution);
+}
+
+/// Moves the window to the monitor picked in settings, centered on it (re-entering fullscreen there if needed).
+/// Falls back to the primary monitor when the saved index no longer exists.
+pub fn apply_display(config: &Config) {
+    let displays = get_displays();
+    let Some(display) = chosen_display(config, &displays) else {
+        return;
+    };
+
+    if config.fullscreen {
+        set_fullscreen(false);
+    }
+    let x = display.x + (display.width - screen_width() as i32) / 2;
+    let y = display.y + (display.height - screen_height() as i32) / 2;
+    miniquad::window::set_window_position(x.max(0) as u32, y.max(0) as u32);
+    if config.fullscreen {
+        set_fullscreen(true);
+    }
+}
+
+/// Saves the current frame as <data dir>/screenshots/screenshot_<timestamp>.png
+pub fn take_screenshot() -> Result<PathBuf, String> {
+    let dir = get_user_data_dir()
+        .ok_or("Could not find the data directory")?
+        .join("screenshots");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("screenshot_{}.png", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+
+    // The GL framebuffer comes back upside down
+    let image = get_screen_data();
+    let row_len = image.width as usize * 4;
+    let flipped: Vec<u8> = image.bytes.chunks(row_len).rev().flatten().copied().collect();
+
+    ::image::save_buffer(&path, &flipped, image.width as u32, image.height as u32, ::image::ColorType::Rgba8)
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
 /// Parses a resolution string and requests a window resize.
+/// Only call this when the setting changes (or once at startup): the window manager has
+/// the final say on the size, and re-requesting it would fight tiling WMs and user resizes.
 pub fn apply_resolution(resolution_str: &str) {
     if let Some((w_str, h_str)) = resolution_str.split_once('x') {
         // Parse to f32 for the resize function
         if let (Ok(w), Ok(h)) = (w_str.parse::<f32>(), h_str.parse::<f32>()) {
-            // Use the correct function name
+            // Nothing to do if the window is already that size
+            if (screen_width(), screen_height()) == (w, h) {
+                return;
+            }
             request_new_screen_size(w, h);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_extensions_ignore_case() {
+        assert!(has_asset_extension(Path::new("bgm/Theme.OGG"), &["ogg", "wav"]));
+        assert!(!has_asset_extension(Path::new("bgm/theme.mp3"), &["ogg", "wav"]));
+        assert!(!has_asset_extension(Path::new("bgm/ogg"), &["ogg"]));
+    }
+}